@@ -0,0 +1,667 @@
+//! This module implements a backend for [Noir](https://noir-lang.org) programs:
+//! it translates ACIR (the Abstract Circuit Intermediate Representation produced by the Noir compiler)
+//! into kimchi gates, and ACIR witness maps into kimchi witnesses.
+//!
+//! ACIR programs and witness maps are read from their serde JSON representation,
+//! where field elements are big-endian hex strings and witnesses are their indexes.
+//! The opcodes are translated as follows:
+//!
+//! - `AssertZero` expressions become generic gates, packed two per row.
+//!   Products beyond the first one and linear terms that do not fit in the last gate
+//!   are reduced to intermediate variables.
+//! - `RANGE` black box calls of at most 64 bits use the range check gate
+//!   (on the value, and on the value shifted to the 64-bit boundary for smaller ranges).
+//! - `XOR` black box calls use the [`Xor16`](crate::circuits::gate::GateType::Xor16) gadget,
+//!   and `AND` black box calls the AND gadget built on top of it.
+//! - `Brillig`, `BrilligCall` and `Directive` opcodes are unconstrained hints for the ACIR solver
+//!   and do not produce any gate: their outputs must be present in the witness map.
+//! - Memory opcodes, calls to other ACIR functions and all other black box functions are rejected.
+//!   In particular `Poseidon2Permutation` is not mapped to the Poseidon gate,
+//!   which implements a different permutation (kimchi's Poseidon),
+//!   and there are no complete Keccak or SHA-256 gadgets yet.
+//!
+//! The public inputs of the kimchi circuit are the public parameters of the program
+//! followed by its return values, in increasing witness order.
+//!
+//! The field elements of the program are interpreted in the scalar field of the curve used by kimchi,
+//! so the program must have been compiled for that field: constants of programs compiled for BN254
+//! (in particular negative ones) do not have the same meaning over the Pasta fields.
+
+use crate::{
+    circuits::{
+        gate::CircuitGate,
+        polynomials::{
+            and,
+            generic::{
+                GenericGatePacker, DOUBLE_GENERIC_REGISTERS, GENERIC_COEFFS, GENERIC_REGISTERS,
+            },
+            range_check,
+            xor::{self, num_xors},
+        },
+        wires::{Wire, COLUMNS},
+    },
+    error::AcirError,
+};
+use ark_ff::{PrimeField, SquareRootField};
+use num_bigint::BigUint;
+use o1_utils::{FieldHelpers, Two};
+use serde::{de::IgnoredAny, Deserialize, Deserializer};
+use serde_with::{serde_as, DeserializeAs};
+use std::{
+    array,
+    collections::{BTreeMap, BTreeSet},
+};
+
+//
+// ACIR
+//
+
+/// An ACIR witness, identified by its index
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+pub struct Witness(pub u32);
+
+/// Deserializes ACIR field elements: big-endian hex strings with an optional `0x` prefix
+struct FieldHex;
+
+impl<'de, F: PrimeField> DeserializeAs<'de, F> for FieldHex {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<F, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        parse_field(&hex).map_err(serde::de::Error::custom)
+    }
+}
+
+fn parse_field<F: PrimeField>(hex: &str) -> Result<F, AcirError> {
+    let digits = hex.strip_prefix("0x").unwrap_or(hex);
+    BigUint::parse_bytes(digits.as_bytes(), 16)
+        .and_then(|value| F::from_biguint(&value).ok())
+        .ok_or_else(|| AcirError::InvalidFieldElement(hex.to_string()))
+}
+
+/// The expression `sum(q_m * a * b) + sum(q_l * w) + q_c`, asserted to be zero
+#[serde_as]
+#[derive(Clone, Debug, Deserialize)]
+#[serde(bound = "F: PrimeField")]
+pub struct Expression<F> {
+    #[serde_as(as = "Vec<(FieldHex, _, _)>")]
+    pub mul_terms: Vec<(F, Witness, Witness)>,
+    #[serde_as(as = "Vec<(FieldHex, _)>")]
+    pub linear_combinations: Vec<(F, Witness)>,
+    #[serde_as(as = "FieldHex")]
+    pub q_c: F,
+}
+
+/// An input of a black box function: a witness of at most `num_bits` bits
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct FunctionInput {
+    pub witness: Witness,
+    pub num_bits: u32,
+}
+
+/// A call to a black box function.
+/// Only the functions with a kimchi gadget are described, the others are recognized by name only.
+#[derive(Clone, Debug, Deserialize)]
+pub enum BlackBoxFuncCall {
+    #[serde(rename = "AND")]
+    And {
+        lhs: FunctionInput,
+        rhs: FunctionInput,
+        output: Witness,
+    },
+    #[serde(rename = "XOR")]
+    Xor {
+        lhs: FunctionInput,
+        rhs: FunctionInput,
+        output: Witness,
+    },
+    #[serde(rename = "RANGE")]
+    Range {
+        input: FunctionInput,
+    },
+    #[serde(rename = "SHA256")]
+    Sha256(IgnoredAny),
+    Sha256Compression(IgnoredAny),
+    Blake2s(IgnoredAny),
+    Blake3(IgnoredAny),
+    Keccak256(IgnoredAny),
+    Keccakf1600(IgnoredAny),
+    Poseidon2Permutation(IgnoredAny),
+    PedersenCommitment(IgnoredAny),
+    PedersenHash(IgnoredAny),
+    SchnorrVerify(IgnoredAny),
+    EcdsaSecp256k1(IgnoredAny),
+    EcdsaSecp256r1(IgnoredAny),
+    MultiScalarMul(IgnoredAny),
+    EmbeddedCurveAdd(IgnoredAny),
+    RecursiveAggregation(IgnoredAny),
+}
+
+/// An ACIR opcode.
+/// The content of the opcodes that are not translated is not described.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(bound = "F: PrimeField")]
+pub enum Opcode<F> {
+    AssertZero(Expression<F>),
+    BlackBoxFuncCall(BlackBoxFuncCall),
+    Directive(IgnoredAny),
+    Brillig(IgnoredAny),
+    BrilligCall(IgnoredAny),
+    MemoryOp(IgnoredAny),
+    MemoryInit(IgnoredAny),
+    Call(IgnoredAny),
+}
+
+/// An ACIR program (a Noir `Circuit`)
+#[derive(Clone, Debug, Deserialize)]
+#[serde(bound = "F: PrimeField")]
+pub struct Program<F> {
+    /// The largest witness index used by the program
+    pub current_witness_index: u32,
+    pub opcodes: Vec<Opcode<F>>,
+    #[serde(default)]
+    pub private_parameters: BTreeSet<Witness>,
+    #[serde(default)]
+    pub public_parameters: BTreeSet<Witness>,
+    #[serde(default)]
+    pub return_values: BTreeSet<Witness>,
+}
+
+impl<F: PrimeField> Program<F> {
+    /// Reads an ACIR program from its JSON representation.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `json` is not a valid ACIR program over the field `F`.
+    pub fn from_json(json: &str) -> Result<Self, AcirError> {
+        serde_json::from_str(json).map_err(|e| AcirError::Deserialization(e.to_string()))
+    }
+}
+
+/// The values of the witnesses of an ACIR program, as produced by the ACIR solver
+#[serde_as]
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(bound = "F: PrimeField", transparent)]
+pub struct WitnessMap<F>(#[serde_as(as = "BTreeMap<_, FieldHex>")] pub BTreeMap<Witness, F>);
+
+impl<F: PrimeField> WitnessMap<F> {
+    /// Reads a witness map from its JSON representation, an object from witness indexes to values.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `json` is not a valid witness map over the field `F`.
+    pub fn from_json(json: &str) -> Result<Self, AcirError> {
+        serde_json::from_str(json).map_err(|e| AcirError::Deserialization(e.to_string()))
+    }
+
+    fn get(&self, witness: Witness) -> Result<F, AcirError> {
+        self.0
+            .get(&witness)
+            .copied()
+            .ok_or(AcirError::MissingWitness(witness.0))
+    }
+}
+
+//
+// Translation
+//
+
+/// A variable of the kimchi circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Var {
+    /// An ACIR witness
+    Witness(Witness),
+    /// A variable created by the translation
+    Internal(usize),
+    /// The constant zero
+    Zero,
+}
+
+/// How to compute an internal variable
+enum InternalVar<F> {
+    /// `sum(coeff * var)`
+    Sum(Vec<(F, Var)>),
+    /// `coeff * left * right`
+    Product(F, Var, Var),
+}
+
+/// The rows of the circuit following the public input rows, and how to fill their witness
+enum Block {
+    /// A double generic gate
+    Generic {
+        row: usize,
+        vars: [Option<Var>; DOUBLE_GENERIC_REGISTERS],
+    },
+    /// A 64-bit range check of `value`
+    RangeCheck { row: usize, value: Var },
+    /// A XOR gadget of `bits` bits
+    Xor {
+        row: usize,
+        lhs: Var,
+        rhs: Var,
+        bits: usize,
+    },
+    /// An AND gadget of `bytes` bytes
+    And {
+        row: usize,
+        lhs: Var,
+        rhs: Var,
+        bytes: usize,
+    },
+}
+
+/// An ACIR program translated to kimchi gates
+pub struct AcirCircuit<F: PrimeField> {
+    /// The number of public inputs of the kimchi circuit
+    pub public_input_size: usize,
+    /// The gates of the kimchi circuit, starting with the public input gates
+    pub gates: Vec<CircuitGate<F>>,
+    /// The witnesses held by the public input rows
+    public: Vec<Witness>,
+    internal_vars: Vec<InternalVar<F>>,
+    blocks: Vec<Block>,
+}
+
+/// The state of the translation of an ACIR program
+struct Translator<F: PrimeField> {
+    packer: GenericGatePacker<F, Var>,
+    blocks: Vec<Block>,
+    internal_vars: Vec<InternalVar<F>>,
+    /// Whether the constant zero has been constrained
+    has_zero: bool,
+    /// Whether the circuit uses the range check lookup table
+    uses_range_check: bool,
+    /// Whether the circuit uses the XOR lookup table
+    uses_xor: bool,
+}
+
+impl<F: PrimeField + SquareRootField> Translator<F> {
+    fn new_internal(&mut self, var: InternalVar<F>) -> Var {
+        self.internal_vars.push(var);
+        Var::Internal(self.internal_vars.len() - 1)
+    }
+
+    fn add_cell(&mut self, var: Var, row: usize, col: usize) {
+        self.packer.add_cell(var, row, col);
+    }
+
+    fn add_generic(&mut self, vars: [Option<Var>; GENERIC_REGISTERS], coeffs: [F; GENERIC_COEFFS]) {
+        if let Some((row, vars)) = self.packer.add_generic(vars, coeffs) {
+            self.blocks.push(Block::Generic { row, vars });
+        }
+    }
+
+    /// Returns the variable holding the constant zero, constraining it the first time
+    fn zero(&mut self) -> Var {
+        if !self.has_zero {
+            self.has_zero = true;
+            self.add_generic(
+                [Some(Var::Zero), None, None],
+                [F::one(), F::zero(), F::zero(), F::zero(), F::zero()],
+            );
+        }
+        Var::Zero
+    }
+
+    /// Adds the gates asserting that `expr` is zero
+    fn assert_zero(&mut self, index: usize, expr: &Expression<F>) -> Result<(), AcirError> {
+        // regroup the terms that share the same witnesses
+        let mut linear = BTreeMap::<Witness, F>::new();
+        for (coeff, w) in &expr.linear_combinations {
+            *linear.entry(*w).or_insert_with(F::zero) += coeff;
+        }
+        let mut mul = BTreeMap::<(Witness, Witness), F>::new();
+        for (coeff, a, b) in &expr.mul_terms {
+            *mul.entry((*a.min(b), *a.max(b))).or_insert_with(F::zero) += coeff;
+        }
+        let mut linear: Vec<(F, Var)> = linear
+            .into_iter()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(w, coeff)| (coeff, Var::Witness(w)))
+            .collect();
+        let mut mul: Vec<(F, Var, Var)> = mul
+            .into_iter()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|((a, b), coeff)| (coeff, Var::Witness(a), Var::Witness(b)))
+            .collect();
+
+        if linear.is_empty() && mul.is_empty() {
+            return if expr.q_c.is_zero() {
+                Ok(())
+            } else {
+                Err(AcirError::UnsatisfiableConstraint(index))
+            };
+        }
+
+        // all the products but the last one become linear terms
+        let last_mul = mul.pop();
+        for (q, a, b) in mul {
+            let res = self.new_internal(InternalVar::Product(q, a, b));
+            self.add_generic(
+                [Some(a), Some(b), Some(res)],
+                [F::zero(), F::zero(), -F::one(), q, F::zero()],
+            );
+            linear.push((F::one(), res));
+        }
+
+        // sum linear terms until they fit in the registers left by the product
+        let room = if last_mul.is_some() {
+            1
+        } else {
+            GENERIC_REGISTERS
+        };
+        while linear.len() > room {
+            let (s1, x1) = linear.remove(0);
+            let (s2, x2) = linear.remove(0);
+            let res = self.new_internal(InternalVar::Sum(vec![(s1, x1), (s2, x2)]));
+            self.add_generic(
+                [Some(x1), Some(x2), Some(res)],
+                [s1, s2, -F::one(), F::zero(), F::zero()],
+            );
+            linear.push((F::one(), res));
+        }
+
+        let mut vars = [None; GENERIC_REGISTERS];
+        let mut coeffs = [F::zero(); GENERIC_COEFFS];
+        coeffs[4] = expr.q_c;
+        let first_linear = match last_mul {
+            Some((q, a, b)) => {
+                vars[0] = Some(a);
+                vars[1] = Some(b);
+                coeffs[3] = q;
+                2
+            }
+            None => 0,
+        };
+        for (i, (s, x)) in linear.into_iter().enumerate() {
+            vars[first_linear + i] = Some(x);
+            coeffs[first_linear + i] = s;
+        }
+        self.add_generic(vars, coeffs);
+        Ok(())
+    }
+
+    /// Adds a range check of `value` to 64 bits
+    fn range_check_64(&mut self, value: Var) {
+        let zero = self.zero();
+        let row = self.packer.gates.len();
+        let mut next_row = row;
+        CircuitGate::extend_range_check(&mut self.packer.gates, &mut next_row);
+        self.uses_range_check = true;
+        // the two most significant 12-bit limbs are zero
+        self.add_cell(value, row, 0);
+        self.add_cell(zero, row, 1);
+        self.add_cell(zero, row, 2);
+        self.blocks.push(Block::RangeCheck { row, value });
+    }
+
+    /// Adds the gates checking that `input` fits in `input.num_bits` bits
+    fn range(&mut self, index: usize, input: &FunctionInput) -> Result<(), AcirError> {
+        let value = Var::Witness(input.witness);
+        match input.num_bits {
+            // every field element fits
+            bits if bits as usize >= F::size_in_bits() => (),
+            bits if bits > 64 => {
+                return Err(AcirError::UnsupportedRange {
+                    index,
+                    num_bits: bits,
+                })
+            }
+            0 => self.add_generic(
+                [Some(value), None, None],
+                [F::one(), F::zero(), F::zero(), F::zero(), F::zero()],
+            ),
+            64 => self.range_check_64(value),
+            bits => {
+                // once value < 2^64 is known, value < 2^bits iff value * 2^(64 - bits) < 2^64,
+                // as the product cannot wrap around the modulus
+                let shift = F::two_pow(64 - u64::from(bits));
+                let shifted = self.new_internal(InternalVar::Sum(vec![(shift, value)]));
+                self.add_generic(
+                    [Some(value), None, Some(shifted)],
+                    [shift, F::zero(), -F::one(), F::zero(), F::zero()],
+                );
+                self.range_check_64(value);
+                self.range_check_64(shifted);
+            }
+        }
+        Ok(())
+    }
+
+    fn black_box(&mut self, index: usize, call: &BlackBoxFuncCall) -> Result<(), AcirError> {
+        let unsupported = |function| AcirError::UnsupportedBlackBox { index, function };
+        match call {
+            BlackBoxFuncCall::Range { input } => self.range(index, input)?,
+            BlackBoxFuncCall::Xor { lhs, rhs, output } => {
+                let bits = lhs.num_bits.max(rhs.num_bits) as usize;
+                let row = self.packer.gates.len();
+                CircuitGate::extend_xor_gadget(&mut self.packer.gates, bits);
+                self.uses_xor = true;
+                self.add_cell(Var::Witness(lhs.witness), row, 0);
+                self.add_cell(Var::Witness(rhs.witness), row, 1);
+                self.add_cell(Var::Witness(*output), row, 2);
+                self.blocks.push(Block::Xor {
+                    row,
+                    lhs: Var::Witness(lhs.witness),
+                    rhs: Var::Witness(rhs.witness),
+                    bits,
+                });
+            }
+            BlackBoxFuncCall::And { lhs, rhs, output } => {
+                let bytes = ((lhs.num_bits.max(rhs.num_bits) as usize + 7) / 8).max(1);
+                let row = self.packer.gates.len();
+                CircuitGate::extend_and(&mut self.packer.gates, bytes);
+                self.uses_xor = true;
+                // the inputs of the XOR are wired to the inputs of the final generic gate
+                let and_row = row + num_xors(bytes * 8) + 1;
+                self.add_cell(Var::Witness(lhs.witness), row, 0);
+                self.add_cell(Var::Witness(rhs.witness), row, 1);
+                self.add_cell(Var::Witness(*output), and_row, 5);
+                self.blocks.push(Block::And {
+                    row,
+                    lhs: Var::Witness(lhs.witness),
+                    rhs: Var::Witness(rhs.witness),
+                    bytes,
+                });
+            }
+            BlackBoxFuncCall::Sha256(_) => return Err(unsupported("SHA256")),
+            BlackBoxFuncCall::Sha256Compression(_) => return Err(unsupported("Sha256Compression")),
+            BlackBoxFuncCall::Blake2s(_) => return Err(unsupported("Blake2s")),
+            BlackBoxFuncCall::Blake3(_) => return Err(unsupported("Blake3")),
+            BlackBoxFuncCall::Keccak256(_) => return Err(unsupported("Keccak256")),
+            BlackBoxFuncCall::Keccakf1600(_) => return Err(unsupported("Keccakf1600")),
+            BlackBoxFuncCall::Poseidon2Permutation(_) => {
+                return Err(unsupported("Poseidon2Permutation"))
+            }
+            BlackBoxFuncCall::PedersenCommitment(_) => {
+                return Err(unsupported("PedersenCommitment"))
+            }
+            BlackBoxFuncCall::PedersenHash(_) => return Err(unsupported("PedersenHash")),
+            BlackBoxFuncCall::SchnorrVerify(_) => return Err(unsupported("SchnorrVerify")),
+            BlackBoxFuncCall::EcdsaSecp256k1(_) => return Err(unsupported("EcdsaSecp256k1")),
+            BlackBoxFuncCall::EcdsaSecp256r1(_) => return Err(unsupported("EcdsaSecp256r1")),
+            BlackBoxFuncCall::MultiScalarMul(_) => return Err(unsupported("MultiScalarMul")),
+            BlackBoxFuncCall::EmbeddedCurveAdd(_) => return Err(unsupported("EmbeddedCurveAdd")),
+            BlackBoxFuncCall::RecursiveAggregation(_) => {
+                return Err(unsupported("RecursiveAggregation"))
+            }
+        }
+        Ok(())
+    }
+
+    fn opcode(&mut self, index: usize, opcode: &Opcode<F>) -> Result<(), AcirError> {
+        let unsupported = |opcode| AcirError::UnsupportedOpcode { index, opcode };
+        match opcode {
+            Opcode::AssertZero(expr) => self.assert_zero(index, expr),
+            Opcode::BlackBoxFuncCall(call) => self.black_box(index, call),
+            // hints for the solver, the values they produce are constrained by other opcodes
+            Opcode::Directive(_) | Opcode::Brillig(_) | Opcode::BrilligCall(_) => Ok(()),
+            Opcode::MemoryOp(_) => Err(unsupported("MemoryOp")),
+            Opcode::MemoryInit(_) => Err(unsupported("MemoryInit")),
+            Opcode::Call(_) => Err(unsupported("Call")),
+        }
+    }
+}
+
+impl<F: PrimeField + SquareRootField> AcirCircuit<F> {
+    /// Translates an ACIR program into kimchi gates.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the program uses opcodes or black box functions that are not supported,
+    /// or if an expression only involves constants and is not zero.
+    pub fn new(program: &Program<F>) -> Result<Self, AcirError> {
+        let public: Vec<Witness> = program
+            .public_parameters
+            .iter()
+            .chain(&program.return_values)
+            .copied()
+            .collect();
+
+        let mut translator = Translator {
+            packer: GenericGatePacker::new(),
+            blocks: vec![],
+            internal_vars: vec![],
+            has_zero: false,
+            uses_range_check: false,
+            uses_xor: false,
+        };
+
+        // the public input rows
+        for w in &public {
+            translator.packer.add_public(Var::Witness(*w));
+        }
+
+        for (index, opcode) in program.opcodes.iter().enumerate() {
+            translator.opcode(index, opcode)?;
+        }
+        if let Some((row, vars)) = translator.packer.flush() {
+            translator.blocks.push(Block::Generic { row, vars });
+        }
+        let Translator {
+            mut packer,
+            blocks,
+            internal_vars,
+            uses_range_check,
+            uses_xor,
+            ..
+        } = translator;
+
+        // the domain is sized after the number of gates, and must fit the lookup tables of the gadgets
+        let mut tables_len = 0;
+        if uses_range_check {
+            tables_len += range_check::gadget::lookup_table::<F>().len();
+        }
+        if uses_xor {
+            tables_len += xor::lookup_table::<F>().len();
+        }
+        if tables_len > 0 {
+            while packer.gates.len() < tables_len + 2 {
+                packer
+                    .gates
+                    .push(CircuitGate::zero(Wire::for_row(packer.gates.len())));
+            }
+        }
+        let gates = packer.finish();
+
+        Ok(Self {
+            public_input_size: public.len(),
+            gates,
+            public,
+            internal_vars,
+            blocks,
+        })
+    }
+
+    /// Computes the kimchi witness from the witness map produced by the ACIR solver.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if a witness used by the circuit is missing from `witness_map`,
+    /// or if an input of a bitwise operation is larger than the operation.
+    pub fn witness(&self, witness_map: &WitnessMap<F>) -> Result<[Vec<F>; COLUMNS], AcirError> {
+        // internal variables only depend on witnesses and previous internal variables
+        let mut internal_values: Vec<F> = Vec::with_capacity(self.internal_vars.len());
+        let value = |internal_values: &[F], var: Var| match var {
+            Var::Witness(w) => witness_map.get(w),
+            Var::Internal(i) => Ok(internal_values[i]),
+            Var::Zero => Ok(F::zero()),
+        };
+        for internal in &self.internal_vars {
+            let v = match internal {
+                InternalVar::Sum(terms) => {
+                    let mut acc = F::zero();
+                    for (coeff, var) in terms {
+                        acc += *coeff * value(&internal_values, *var)?;
+                    }
+                    acc
+                }
+                InternalVar::Product(coeff, left, right) => {
+                    *coeff * value(&internal_values, *left)? * value(&internal_values, *right)?
+                }
+            };
+            internal_values.push(v);
+        }
+
+        let mut witness: [Vec<F>; COLUMNS] = array::from_fn(|_| vec![F::zero(); self.gates.len()]);
+        let mut copy_rows = |row: usize, rows: [Vec<F>; COLUMNS]| {
+            for (col, values) in rows.into_iter().enumerate() {
+                witness[col][row..row + values.len()].copy_from_slice(&values);
+            }
+        };
+        let bitwise_input = |var: Var, num_bits: usize| {
+            let v = value(&internal_values, var)?;
+            match var {
+                Var::Witness(w) if v.to_biguint().bits() as usize > num_bits => {
+                    Err(AcirError::InvalidInput {
+                        witness: w.0,
+                        num_bits,
+                    })
+                }
+                _ => Ok(v),
+            }
+        };
+
+        for (row, w) in self.public.iter().enumerate() {
+            let mut rows: [Vec<F>; COLUMNS] = array::from_fn(|_| vec![F::zero()]);
+            rows[0][0] = witness_map.get(*w)?;
+            copy_rows(row, rows);
+        }
+        for block in &self.blocks {
+            match block {
+                Block::Generic { row, vars } => {
+                    let mut rows: [Vec<F>; COLUMNS] = array::from_fn(|_| vec![F::zero()]);
+                    for (col, var) in vars.iter().enumerate() {
+                        if let Some(var) = var {
+                            rows[col][0] = value(&internal_values, *var)?;
+                        }
+                    }
+                    copy_rows(*row, rows);
+                }
+                Block::RangeCheck { row, value: var } => {
+                    let v = value(&internal_values, *var)?;
+                    copy_rows(*row, range_check::witness::create(v));
+                }
+                Block::Xor {
+                    row,
+                    lhs,
+                    rhs,
+                    bits,
+                } => {
+                    let lhs = bitwise_input(*lhs, *bits)?;
+                    let rhs = bitwise_input(*rhs, *bits)?;
+                    copy_rows(*row, xor::create_xor_witness(lhs, rhs, *bits));
+                }
+                Block::And {
+                    row,
+                    lhs,
+                    rhs,
+                    bytes,
+                } => {
+                    let lhs = bitwise_input(*lhs, bytes * 8)?;
+                    let rhs = bitwise_input(*rhs, bytes * 8)?;
+                    copy_rows(*row, and::create_and_witness(lhs, rhs, *bytes));
+                }
+            }
+        }
+        Ok(witness)
+    }
+}
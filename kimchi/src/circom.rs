@@ -0,0 +1,515 @@
+//! This module implements an importer for circuits written in [circom](https://docs.circom.io).
+//!
+//! It reads the binary constraint files (`.r1cs`) and witness files (`.wtns`)
+//! produced by circom and snarkjs, and translates the rank-1 constraints into generic gates:
+//!
+//! - every linear combination with more than one term (or with a term and a constant)
+//!   is reduced to a single variable with as many addition gates as needed,
+//!   the same way [`SnarkyConstraintSystem`](crate::snarky::constraint_system::SnarkyConstraintSystem) does,
+//! - every constraint `a * b = c` then becomes a single generic gate,
+//! - generic gates are packed two per row (double generic gates),
+//! - all the cells holding the same variable are wired together with [`Connect`](crate::circuits::gate::Connect).
+//!
+//! The public inputs of the kimchi circuit are the public outputs of the circom circuit
+//! followed by its public inputs, in the order of their circom wires.
+//! The circom circuit must be compiled for the scalar field of the curve used by kimchi
+//! (`circom --prime vesta` for Vesta, `circom --prime pallas` for Pallas),
+//! files over any other field are rejected with [`CircomError::FieldMismatch`].
+//!
+//! ```ignore
+//! let r1cs = R1cs::<Fp>::from_bytes(&std::fs::read("circuit.r1cs")?)?;
+//! let circuit = CircomCircuit::new(&r1cs)?;
+//! let wires = read_wtns::<Fp>(&std::fs::read("witness.wtns")?)?;
+//! let witness = circuit.witness(&wires)?;
+//! ```
+
+use crate::{
+    circuits::{
+        gate::CircuitGate,
+        polynomials::generic::{
+            GenericGatePacker, DOUBLE_GENERIC_REGISTERS, GENERIC_COEFFS, GENERIC_REGISTERS,
+        },
+        wires::COLUMNS,
+    },
+    error::CircomError,
+};
+use ark_ff::PrimeField;
+use num_bigint::BigUint;
+use o1_utils::FieldHelpers;
+use std::{array, collections::BTreeMap};
+
+/// The magic bytes at the start of a `.r1cs` file
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+
+/// The magic bytes at the start of a `.wtns` file
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+
+/// The versions of the `.r1cs` format that can be read
+const R1CS_VERSIONS: [u32; 1] = [1];
+
+/// The versions of the `.wtns` format that can be read
+const WTNS_VERSIONS: [u32; 2] = [1, 2];
+
+/// The section of a `.r1cs` file holding its header
+const R1CS_HEADER_SECTION: u32 = 1;
+
+/// The section of a `.r1cs` file holding its constraints
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+
+/// The section of a `.wtns` file holding its header
+const WTNS_HEADER_SECTION: u32 = 1;
+
+/// The section of a `.wtns` file holding the values of the wires
+const WTNS_VALUES_SECTION: u32 = 2;
+
+//
+// Parsing
+//
+
+/// A linear combination of circom wires, as a list of `(wire, coefficient)`.
+/// The wire 0 always holds the constant 1.
+pub type LinearCombination<F> = Vec<(usize, F)>;
+
+/// A rank-1 constraint `a * b = c`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1csConstraint<F> {
+    pub a: LinearCombination<F>,
+    pub b: LinearCombination<F>,
+    pub c: LinearCombination<F>,
+}
+
+/// The content of a circom `.r1cs` file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1cs<F> {
+    /// The number of wires, including the constant wire 0
+    pub num_wires: usize,
+    /// The number of public outputs (wires `1..=num_public_outputs`)
+    pub num_public_outputs: usize,
+    /// The number of public inputs (the wires following the public outputs)
+    pub num_public_inputs: usize,
+    /// The number of private inputs (the wires following the public inputs)
+    pub num_private_inputs: usize,
+    /// The constraints of the circuit
+    pub constraints: Vec<R1csConstraint<F>>,
+}
+
+/// A cursor over the bytes of a file
+struct Reader<'a> {
+    format: &'static str,
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(format: &'static str, bytes: &'a [u8]) -> Self {
+        Self { format, bytes }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CircomError> {
+        if self.bytes.len() < len {
+            return Err(CircomError::Truncated(self.format));
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, CircomError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+    }
+
+    fn u64(&mut self) -> Result<u64, CircomError> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn usize(&mut self) -> Result<usize, CircomError> {
+        Ok(self.u32()? as usize)
+    }
+
+    /// Reads the size of the field elements and the prime of the field,
+    /// and checks that the prime is the modulus of `F`.
+    fn field_header<F: PrimeField>(&mut self) -> Result<usize, CircomError> {
+        let n8 = self.usize()?;
+        let prime = BigUint::from_bytes_le(self.take(n8)?);
+        let modulus = F::modulus_biguint();
+        if prime != modulus {
+            return Err(CircomError::FieldMismatch {
+                format: self.format,
+                expected: modulus.to_string(),
+                found: prime.to_string(),
+            });
+        }
+        Ok(n8)
+    }
+
+    /// Reads a canonical field element of `n8` bytes, in little-endian
+    fn field<F: PrimeField>(&mut self, n8: usize) -> Result<F, CircomError> {
+        let value = BigUint::from_bytes_le(self.take(n8)?);
+        F::from_biguint(&value).map_err(|_| CircomError::InvalidFieldElement(self.format))
+    }
+
+    /// Reads the header of a file and returns the content of its sections by section type
+    fn sections(
+        mut self,
+        magic: &[u8; 4],
+        versions: &[u32],
+    ) -> Result<BTreeMap<u32, Reader<'a>>, CircomError> {
+        let found = self
+            .take(4)
+            .map_err(|_| CircomError::InvalidMagic(self.format))?;
+        if found != magic {
+            return Err(CircomError::InvalidMagic(self.format));
+        }
+        let version = self.u32()?;
+        if !versions.contains(&version) {
+            return Err(CircomError::UnsupportedVersion(self.format, version));
+        }
+
+        let num_sections = self.u32()?;
+        let mut sections = BTreeMap::new();
+        for _ in 0..num_sections {
+            let typ = self.u32()?;
+            let len =
+                usize::try_from(self.u64()?).map_err(|_| CircomError::Truncated(self.format))?;
+            let content = Reader::new(self.format, self.take(len)?);
+            // only the first section of each type is meaningful
+            sections.entry(typ).or_insert(content);
+        }
+        Ok(sections)
+    }
+
+    fn linear_combination<F: PrimeField>(
+        &mut self,
+        n8: usize,
+        constraint: usize,
+        num_wires: usize,
+    ) -> Result<LinearCombination<F>, CircomError> {
+        let num_terms = self.usize()?;
+        // the number of terms is not trusted, so no capacity is reserved
+        let mut lc = vec![];
+        for _ in 0..num_terms {
+            let wire = self.usize()?;
+            if wire >= num_wires {
+                return Err(CircomError::InvalidWire {
+                    constraint,
+                    wire,
+                    num_wires,
+                });
+            }
+            lc.push((wire, self.field(n8)?));
+        }
+        Ok(lc)
+    }
+}
+
+impl<F: PrimeField> R1cs<F> {
+    /// Parses the content of a circom `.r1cs` file.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the file is malformed, or if it is not over the field `F`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircomError> {
+        const FORMAT: &str = "r1cs";
+        let mut sections = Reader::new(FORMAT, bytes).sections(R1CS_MAGIC, &R1CS_VERSIONS)?;
+
+        let mut header = sections
+            .remove(&R1CS_HEADER_SECTION)
+            .ok_or(CircomError::MissingSection(FORMAT, "header"))?;
+        let n8 = header.field_header::<F>()?;
+        let num_wires = header.usize()?;
+        let num_public_outputs = header.usize()?;
+        let num_public_inputs = header.usize()?;
+        let num_private_inputs = header.usize()?;
+        let _num_labels = header.u64()?;
+        let num_constraints = header.usize()?;
+
+        let mut content = sections
+            .remove(&R1CS_CONSTRAINTS_SECTION)
+            .ok_or(CircomError::MissingSection(FORMAT, "constraints"))?;
+        let constraints = (0..num_constraints)
+            .map(|i| {
+                Ok(R1csConstraint {
+                    a: content.linear_combination(n8, i, num_wires)?,
+                    b: content.linear_combination(n8, i, num_wires)?,
+                    c: content.linear_combination(n8, i, num_wires)?,
+                })
+            })
+            .collect::<Result<_, CircomError>>()?;
+
+        Ok(Self {
+            num_wires,
+            num_public_outputs,
+            num_public_inputs,
+            num_private_inputs,
+            constraints,
+        })
+    }
+
+    /// The number of public values of the circuit (its public outputs and public inputs)
+    pub fn public_input_size(&self) -> usize {
+        self.num_public_outputs + self.num_public_inputs
+    }
+}
+
+/// Parses the content of a circom `.wtns` file, and returns the values of all the wires
+/// (starting with the constant wire 0).
+///
+/// # Errors
+///
+/// Will give error if the file is malformed, or if it is not over the field `F`.
+pub fn read_wtns<F: PrimeField>(bytes: &[u8]) -> Result<Vec<F>, CircomError> {
+    const FORMAT: &str = "wtns";
+    let mut sections = Reader::new(FORMAT, bytes).sections(WTNS_MAGIC, &WTNS_VERSIONS)?;
+
+    let mut header = sections
+        .remove(&WTNS_HEADER_SECTION)
+        .ok_or(CircomError::MissingSection(FORMAT, "header"))?;
+    let n8 = header.field_header::<F>()?;
+    let num_wires = header.usize()?;
+
+    let mut values = sections
+        .remove(&WTNS_VALUES_SECTION)
+        .ok_or(CircomError::MissingSection(FORMAT, "values"))?;
+    (0..num_wires).map(|_| values.field(n8)).collect()
+}
+
+//
+// Translation
+//
+
+/// A variable of the kimchi circuit
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Var {
+    /// A circom wire
+    Wire(usize),
+    /// A variable created to reduce a linear combination
+    Internal(usize),
+}
+
+/// A linear combination reduced to a single scaled variable, or to a constant
+enum Reduced<F> {
+    Var(F, Var),
+    Constant(F),
+}
+
+/// A circom circuit translated to kimchi gates
+pub struct CircomCircuit<F: PrimeField> {
+    /// The number of public inputs of the kimchi circuit
+    pub public_input_size: usize,
+    /// The gates of the kimchi circuit, starting with the public input gates
+    pub gates: Vec<CircuitGate<F>>,
+    /// The number of circom wires
+    num_wires: usize,
+    /// How to compute each internal variable: `sum(coeff * var) + constant`
+    internal_vars: Vec<(Vec<(F, Var)>, F)>,
+    /// The variables held by the registers of each row following the public input rows
+    rows: Vec<[Option<Var>; DOUBLE_GENERIC_REGISTERS]>,
+}
+
+/// The state of the translation of a circom circuit
+struct Translator<F> {
+    packer: GenericGatePacker<F, Var>,
+    internal_vars: Vec<(Vec<(F, Var)>, F)>,
+    /// The registers of each row following the public input rows
+    rows: Vec<[Option<Var>; DOUBLE_GENERIC_REGISTERS]>,
+}
+
+impl<F: PrimeField> Translator<F> {
+    fn new_internal(&mut self, terms: Vec<(F, Var)>, constant: F) -> Var {
+        self.internal_vars.push((terms, constant));
+        Var::Internal(self.internal_vars.len() - 1)
+    }
+
+    fn add_generic(&mut self, vars: [Option<Var>; GENERIC_REGISTERS], coeffs: [F; GENERIC_COEFFS]) {
+        if let Some((_, vars)) = self.packer.add_generic(vars, coeffs) {
+            self.rows.push(vars);
+        }
+    }
+
+    /// Reduces a linear combination to a single scaled variable (or a constant),
+    /// adding the gates computing the intermediate sums.
+    fn reduce(&mut self, lc: &LinearCombination<F>) -> Reduced<F> {
+        // regroup the terms that share the same wire
+        let mut acc = BTreeMap::<usize, F>::new();
+        for (wire, coeff) in lc {
+            *acc.entry(*wire).or_insert_with(F::zero) += coeff;
+        }
+        let constant = acc.remove(&0).unwrap_or_else(F::zero);
+        let mut terms = acc
+            .into_iter()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .map(|(wire, coeff)| (coeff, Var::Wire(wire)));
+
+        let (mut s, mut x) = match terms.next() {
+            None => return Reduced::Constant(constant),
+            Some(term) => term,
+        };
+        let rest: Vec<_> = terms.collect();
+
+        if rest.is_empty() {
+            if constant.is_zero() {
+                return Reduced::Var(s, x);
+            }
+            // res = s * x + constant
+            let res = self.new_internal(vec![(s, x)], constant);
+            self.add_generic(
+                [Some(x), None, Some(res)],
+                [s, F::zero(), -F::one(), F::zero(), constant],
+            );
+            return Reduced::Var(F::one(), res);
+        }
+
+        let last = rest.len() - 1;
+        for (i, (s2, x2)) in rest.into_iter().enumerate() {
+            // res = s * x + s2 * x2 (+ constant, for the last term)
+            let c = if i == last { constant } else { F::zero() };
+            let res = self.new_internal(vec![(s, x), (s2, x2)], c);
+            self.add_generic(
+                [Some(x), Some(x2), Some(res)],
+                [s, s2, -F::one(), F::zero(), c],
+            );
+            (s, x) = (F::one(), res);
+        }
+        Reduced::Var(s, x)
+    }
+
+    /// Adds the gates enforcing the constraint `a * b = c`
+    fn add_constraint(
+        &mut self,
+        index: usize,
+        constraint: &R1csConstraint<F>,
+    ) -> Result<(), CircomError> {
+        let a = self.reduce(&constraint.a);
+        let b = self.reduce(&constraint.b);
+        let c = self.reduce(&constraint.c);
+
+        // l * cl + r * cr + o * co + l * r * cm + cc = 0
+        let mut vars = [None; GENERIC_REGISTERS];
+        let mut coeffs = [F::zero(); GENERIC_COEFFS];
+        match (a, b) {
+            (Reduced::Var(s1, x1), Reduced::Var(s2, x2)) => {
+                vars[0] = Some(x1);
+                vars[1] = Some(x2);
+                coeffs[3] = s1 * s2;
+            }
+            (Reduced::Var(s, x), Reduced::Constant(k))
+            | (Reduced::Constant(k), Reduced::Var(s, x)) => {
+                vars[0] = Some(x);
+                coeffs[0] = s * k;
+            }
+            (Reduced::Constant(k1), Reduced::Constant(k2)) => coeffs[4] = k1 * k2,
+        }
+        match c {
+            Reduced::Var(s3, x3) => {
+                vars[2] = Some(x3);
+                coeffs[2] = -s3;
+            }
+            Reduced::Constant(k3) => coeffs[4] -= k3,
+        }
+
+        if vars.iter().all(Option::is_none) {
+            return if coeffs[4].is_zero() {
+                Ok(())
+            } else {
+                Err(CircomError::UnsatisfiableConstraint(index))
+            };
+        }
+        self.add_generic(vars, coeffs);
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CircomCircuit<F> {
+    /// Translates the constraints of a circom circuit into kimchi gates.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if a constraint only involves constants and does not hold,
+    /// or if the circuit translates to fewer than 2 gates (the minimum of kimchi).
+    pub fn new(r1cs: &R1cs<F>) -> Result<Self, CircomError> {
+        let mut translator = Translator {
+            packer: GenericGatePacker::new(),
+            internal_vars: vec![],
+            rows: vec![],
+        };
+
+        // the public input rows, each holding a public wire
+        let public_input_size = r1cs.public_input_size();
+        for wire in 1..=public_input_size {
+            translator.packer.add_public(Var::Wire(wire));
+        }
+
+        // the generic gates, two per row
+        for (i, constraint) in r1cs.constraints.iter().enumerate() {
+            translator.add_constraint(i, constraint)?;
+        }
+        if let Some((_, vars)) = translator.packer.flush() {
+            translator.rows.push(vars);
+        }
+        let Translator {
+            packer,
+            internal_vars,
+            rows,
+        } = translator;
+
+        if packer.gates.len() < 2 {
+            return Err(CircomError::TooFewGates(packer.gates.len()));
+        }
+
+        Ok(Self {
+            public_input_size,
+            gates: packer.finish(),
+            num_wires: r1cs.num_wires,
+            internal_vars,
+            rows,
+        })
+    }
+
+    /// Computes the kimchi witness from the values of the circom wires
+    /// (as read by [`read_wtns`]).
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the number of wires does not match the circuit,
+    /// or if the wire 0 does not hold the constant 1.
+    pub fn witness(&self, wires: &[F]) -> Result<[Vec<F>; COLUMNS], CircomError> {
+        if wires.len() != self.num_wires {
+            return Err(CircomError::WitnessLength {
+                expected: self.num_wires,
+                found: wires.len(),
+            });
+        }
+        if wires.first() != Some(&F::one()) {
+            return Err(CircomError::InvalidConstantWire);
+        }
+
+        // internal variables only depend on wires and previous internal variables
+        let mut internal_values: Vec<F> = Vec::with_capacity(self.internal_vars.len());
+        let value = |internal_values: &[F], var: Var| match var {
+            Var::Wire(wire) => wires[wire],
+            Var::Internal(i) => internal_values[i],
+        };
+        for (terms, constant) in &self.internal_vars {
+            let v = terms.iter().fold(*constant, |acc, (coeff, var)| {
+                acc + *coeff * value(&internal_values, *var)
+            });
+            internal_values.push(v);
+        }
+
+        let num_rows = self.public_input_size + self.rows.len();
+        let mut witness: [Vec<F>; COLUMNS] = array::from_fn(|_| vec![F::zero(); num_rows]);
+        for row in 0..self.public_input_size {
+            witness[0][row] = wires[row + 1];
+        }
+        for (i, vars) in self.rows.iter().enumerate() {
+            let row = self.public_input_size + i;
+            for (col, var) in vars.iter().enumerate() {
+                if let Some(var) = var {
+                    witness[col][row] = value(&internal_values, *var);
+                }
+            }
+        }
+        Ok(witness)
+    }
+}
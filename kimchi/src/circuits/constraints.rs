@@ -5,9 +5,14 @@ use crate::{
         domain_constant_evaluation::DomainConstantEvaluations,
         domains::EvaluationDomains,
         gate::{CircuitGate, GateType},
-        lookup::{index::LookupConstraintSystem, lookups::LookupFeatures, tables::LookupTable},
+        lookup::{
+            index::LookupConstraintSystem,
+            lookups::{LookupFeatures, LookupInfo},
+            tables::LookupTable,
+        },
         polynomial::{WitnessEvals, WitnessOverDomains, WitnessShifts},
         polynomials::permutation::{Shifts, ZK_ROWS},
+        twiddles::Twiddles,
         wires::*,
     },
     curve::KimchiCurve,
@@ -19,6 +24,7 @@ use ark_poly::{
     univariate::DensePolynomial as DP, EvaluationDomain, Evaluations as E,
     Radix2EvaluationDomain as D,
 };
+use commitment_dlog::pcs::OpenProof;
 use o1_utils::ExtendedEvaluations;
 use once_cell::sync::OnceCell;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -173,9 +179,32 @@ pub struct ConstraintSystem<F: PrimeField> {
     /// precomputes
     #[serde(skip)]
     precomputations: OnceCell<Arc<DomainConstantEvaluations<F>>>,
+    /// twiddle factors of the FFTs over the domains of the circuit
+    #[serde(skip)]
+    twiddles: OnceCell<Arc<Twiddles<F>>>,
 
     /// Disable gates checks (for testing; only enables with development builds)
     pub disable_gates_checks: bool,
+
+    // the fields below come last and have defaults,
+    // so that constraint systems serialized before they existed can still be deserialized
+    /// whether proofs for this circuit are zero-knowledge
+    #[serde(default = "default_zero_knowledge")]
+    pub zero_knowledge: bool,
+    /// number of rows at the end of the domain that are not covered by the permutation argument
+    /// (`ZK_ROWS`, or 1 for circuits without zero-knowledge nor lookups)
+    #[serde(default = "default_zk_rows")]
+    pub zk_rows: u64,
+}
+
+/// The value of `zero_knowledge` for constraint systems and verifier indexes serialized without it
+pub(crate) fn default_zero_knowledge() -> bool {
+    true
+}
+
+/// The value of `zk_rows` for constraint systems and verifier indexes serialized without it
+pub(crate) fn default_zk_rows() -> u64 {
+    ZK_ROWS
 }
 
 /// Represents an error found when verifying a witness with a gate
@@ -197,6 +226,7 @@ pub struct Builder<F: PrimeField> {
     runtime_tables: Option<Vec<RuntimeTableCfg<F>>>,
     precomputations: Option<Arc<DomainConstantEvaluations<F>>>,
     disable_gates_checks: bool,
+    zero_knowledge: bool,
 }
 
 /// Create selector polynomial for a circuit gate
@@ -240,6 +270,7 @@ impl<F: PrimeField> ConstraintSystem<F> {
     /// - `runtime_tables: None`,
     /// - `precomputations: None`,
     /// - `disable_gates_checks: false`,
+    /// - `zero_knowledge: true`,
     ///
     /// How to use it:
     /// 1. Create your instance of your builder for the constraint system using `crate(gates, sponge params)`
@@ -254,12 +285,14 @@ impl<F: PrimeField> ConstraintSystem<F> {
             runtime_tables: None,
             precomputations: None,
             disable_gates_checks: false,
+            zero_knowledge: true,
         }
     }
 
     pub fn precomputations(&self) -> &Arc<DomainConstantEvaluations<F>> {
-        self.precomputations
-            .get_or_init(|| Arc::new(DomainConstantEvaluations::create(self.domain).unwrap()))
+        self.precomputations.get_or_init(|| {
+            Arc::new(DomainConstantEvaluations::create(self.domain, self.zk_rows).unwrap())
+        })
     }
 
     pub fn set_precomputations(&self, precomputations: Arc<DomainConstantEvaluations<F>>) {
@@ -267,9 +300,20 @@ impl<F: PrimeField> ConstraintSystem<F> {
             .set(precomputations)
             .expect("Precomputation has been set before");
     }
+
+    /// The twiddle factors of the FFTs over the domains of the circuit, computed once
+    pub fn twiddles(&self) -> &Twiddles<F> {
+        self.twiddles
+            .get_or_init(|| Arc::new(Twiddles::new(self.domain.d8)))
+    }
 }
 
-impl<F: PrimeField + SquareRootField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
+impl<
+        F: PrimeField + SquareRootField,
+        G: KimchiCurve<ScalarField = F>,
+        OpeningProof: OpenProof<G>,
+    > ProverIndex<G, OpeningProof>
+{
     /// This function verifies the consistency of the wire
     /// assignments (witness) against the constraints
     ///     witness: wire assignment witness
@@ -316,7 +360,7 @@ impl<F: PrimeField + SquareRootField, G: KimchiCurve<ScalarField = F>> ProverInd
             }
 
             // check the gate's satisfiability
-            gate.verify::<G>(row, &witness, self, public)
+            gate.verify::<G, OpeningProof>(row, &witness, self, public)
                 .map_err(|err| GateError::Custom { row, err })?;
         }
 
@@ -328,10 +372,12 @@ impl<F: PrimeField + SquareRootField, G: KimchiCurve<ScalarField = F>> ProverInd
 impl<F: PrimeField + SquareRootField> ConstraintSystem<F> {
     /// evaluate witness polynomials over domains
     pub fn evaluate(&self, w: &[DP<F>; COLUMNS], z: &DP<F>) -> WitnessOverDomains<F> {
+        let twiddles = self.twiddles();
+
         // compute shifted witness polynomials
         let w8: [E<F, D<F>>; COLUMNS] =
-            array::from_fn(|i| w[i].evaluate_over_domain_by_ref(self.domain.d8));
-        let z8 = z.evaluate_over_domain_by_ref(self.domain.d8);
+            array::from_fn(|i| twiddles.evaluate_over_domain(&w[i], self.domain.d8));
+        let z8 = twiddles.evaluate_over_domain(z, self.domain.d8);
 
         let w4: [E<F, D<F>>; COLUMNS] = array::from_fn(|i| {
             E::<F, D<F>>::from_vec_and_domain(
@@ -677,6 +723,9 @@ impl<F: PrimeField + SquareRootField> Builder<F> {
 
     /// Set up the shared precomputations.
     /// If not invoked, it is `None` by default.
+    ///
+    /// **Warning:** the precomputations must have been created for the same domain
+    /// and the same number of zero-knowledge rows as the circuit.
     pub fn shared_precomputations(
         mut self,
         shared_precomputations: Arc<DomainConstantEvaluations<F>>,
@@ -691,6 +740,18 @@ impl<F: PrimeField + SquareRootField> Builder<F> {
         self
     }
 
+    /// Enable or disable zero-knowledge.
+    /// If not invoked, it is `true` by default.
+    ///
+    /// Without zero-knowledge, the prover does not blind its commitments nor randomize
+    /// the last rows of its polynomials, and circuits that do not use lookups only
+    /// reserve a single row at the end of the domain (instead of `ZK_ROWS`).
+    /// Only use this for proofs that do not need to hide the witness.
+    pub fn zero_knowledge(mut self, zero_knowledge: bool) -> Self {
+        self.zero_knowledge = zero_knowledge;
+        self
+    }
+
     /// Build the [ConstraintSystem] from a [Builder].
     pub fn build(self) -> Result<ConstraintSystem<F>, SetupError> {
         let mut gates = self.gates;
@@ -701,14 +762,25 @@ impl<F: PrimeField + SquareRootField> Builder<F> {
         // for some reason we need more than 1 gate for the circuit to work, see TODO below
         assert!(gates.len() > 1);
 
-        //~ 2. Create a domain for the circuit. That is,
+        //~ 2. Compute the number of rows to reserve at the end of the circuit:
+        //~    `ZK_ROWS` if the circuit is zero-knowledge or uses lookups
+        //~    (the lookup argument always excludes the last `ZK_ROWS` rows), 1 otherwise.
+        let uses_lookups =
+            LookupInfo::create_from_gates(&gates, runtime_tables.is_some()).is_some();
+        let zk_rows = if self.zero_knowledge || uses_lookups {
+            ZK_ROWS
+        } else {
+            1
+        };
+
+        //~ 3. Create a domain for the circuit. That is,
         //~    compute the smallest subgroup of the field that
-        //~    has order greater or equal to `n + ZK_ROWS` elements.
-        let domain = EvaluationDomains::<F>::create(gates.len() + ZK_ROWS as usize)?;
+        //~    has order greater or equal to `n + zk_rows` elements.
+        let domain = EvaluationDomains::<F>::create(gates.len() + zk_rows as usize)?;
 
-        assert!(domain.d1.size > ZK_ROWS);
+        assert!(domain.d1.size > zk_rows);
 
-        //~ 3. Pad the circuit: add zero gates to reach the domain size.
+        //~ 4. Pad the circuit: add zero gates to reach the domain size.
         let d1_size = domain.d1.size();
         let mut padding = (gates.len()..d1_size)
             .map(|i| {
@@ -749,7 +821,7 @@ impl<F: PrimeField + SquareRootField> Builder<F> {
             }
         }
 
-        //~ 4. sample the `PERMUTS` shifts.
+        //~ 5. sample the `PERMUTS` shifts.
         let shifts = Shifts::new(&domain.d1);
 
         //
@@ -777,7 +849,10 @@ impl<F: PrimeField + SquareRootField> Builder<F> {
             //fr_sponge_params: self.sponge_params,
             lookup_constraint_system,
             feature_flags,
+            zero_knowledge: self.zero_knowledge,
+            zk_rows,
             precomputations: domain_constant_evaluation,
+            twiddles: OnceCell::new(),
             disable_gates_checks: self.disable_gates_checks,
         };
 
@@ -0,0 +1,268 @@
+//! This module implements a versioned, human-readable description of circuits,
+//! that can be cached, reviewed, or generated by programs that are not written in Rust.
+//!
+//! A [`CircuitDescription`] contains everything needed to build a [`ConstraintSystem`]:
+//! the gates (with their types, wiring and coefficients),
+//! the number of public inputs, the number of previous challenges,
+//! whether proofs are zero-knowledge, the lookup tables and the runtime table configurations.
+//! It is imported and exported as JSON with the [`Json`] trait,
+//! following the conventions of [`crate::json`]
+//! (`#/$defs/CircuitDescription` in the JSON schema),
+//! and turned into a [`Builder`] with [`CircuitDescription::into_builder`].
+//!
+//! [`ConstraintSystem::description`] exports a built constraint system:
+//! building its description gives back the same constraint system.
+//! The padding of the circuit is dropped,
+//! except for the zero gates needed to keep the same domain.
+//! Constraint systems with lookups that were serialized before their lookup tables were recorded
+//! (see [`LookupConstraintSystem::fixed_tables`](crate::circuits::lookup::index::LookupConstraintSystem::fixed_tables))
+//! cannot be exported.
+
+use crate::{
+    circuits::{
+        constraints::{Builder, ConstraintSystem},
+        gate::CircuitGate,
+        lookup::{
+            runtime_tables::{RuntimeTableCfg, RuntimeTableSpec},
+            tables::LookupTable,
+        },
+    },
+    error::DescriptionError,
+    json::{self, Json},
+    stats::unpadded_rows,
+};
+use ark_ff::{PrimeField, SquareRootField};
+use ark_poly::EvaluationDomain;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+use std::collections::HashSet;
+
+/// The current version of the circuit description format
+pub const CIRCUIT_DESCRIPTION_VERSION: u32 = 1;
+
+/// A lookup table of a [`CircuitDescription`], given as a list of columns.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LookupTableDescription<F: PrimeField> {
+    /// The table ID.
+    pub id: i32,
+    /// The columns of the table.
+    #[serde_as(as = "Vec<Vec<o1_utils::serialization::SerdeAs>>")]
+    pub data: Vec<Vec<F>>,
+}
+
+/// A runtime table configuration of a [`CircuitDescription`] (see [`RuntimeTableCfg`]).
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum RuntimeTableDescription<F: PrimeField> {
+    /// A runtime table whose first column is a counter starting at zero.
+    Indexed {
+        /// The table ID.
+        id: i32,
+        /// The number of entries of the table.
+        len: usize,
+    },
+    /// A runtime table whose first column is fixed at setup.
+    Custom {
+        /// The table ID.
+        id: i32,
+        /// The first column of the table.
+        #[serde_as(as = "Vec<o1_utils::serialization::SerdeAs>")]
+        first_column: Vec<F>,
+    },
+}
+
+fn default_zero_knowledge() -> bool {
+    true
+}
+
+/// The description of a circuit, see the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitDescription<F: PrimeField> {
+    /// The version of the format ([`CIRCUIT_DESCRIPTION_VERSION`]).
+    pub version: u32,
+    /// The number of public inputs.
+    pub public_input_size: usize,
+    /// The number of previous challenges, for recursive proving.
+    #[serde(default)]
+    pub prev_challenges: usize,
+    /// Whether proofs are zero-knowledge.
+    #[serde(default = "default_zero_knowledge")]
+    pub zero_knowledge: bool,
+    /// The lookup tables, besides the ones used by the gates which are always included.
+    #[serde(
+        default,
+        bound = "LookupTableDescription<F>: Serialize + DeserializeOwned"
+    )]
+    pub lookup_tables: Vec<LookupTableDescription<F>>,
+    /// The runtime table configurations.
+    #[serde(
+        default,
+        bound = "RuntimeTableDescription<F>: Serialize + DeserializeOwned"
+    )]
+    pub runtime_tables: Option<Vec<RuntimeTableDescription<F>>>,
+    /// The gates of the circuit.
+    #[serde(bound = "CircuitGate<F>: Serialize + DeserializeOwned")]
+    pub gates: Vec<CircuitGate<F>>,
+}
+
+impl<F: PrimeField> CircuitDescription<F> {
+    /// Creates the description of a circuit with the given gates and number of public inputs,
+    /// without lookup tables, runtime tables nor previous challenges.
+    pub fn new(gates: Vec<CircuitGate<F>>, public_input_size: usize) -> Self {
+        CircuitDescription {
+            version: CIRCUIT_DESCRIPTION_VERSION,
+            public_input_size,
+            prev_challenges: 0,
+            zero_knowledge: true,
+            lookup_tables: vec![],
+            runtime_tables: None,
+            gates,
+        }
+    }
+}
+
+impl<F: PrimeField + SquareRootField> CircuitDescription<F> {
+    /// Returns a [`Builder`] for the described constraint system.
+    pub fn into_builder(self) -> Builder<F> {
+        let lookup_tables = self
+            .lookup_tables
+            .into_iter()
+            .map(|table| LookupTable {
+                id: table.id,
+                data: table.data,
+            })
+            .collect();
+        let runtime_tables = self.runtime_tables.map(|tables| {
+            tables
+                .into_iter()
+                .map(|table| match table {
+                    RuntimeTableDescription::Indexed { id, len } => {
+                        RuntimeTableCfg::Indexed(RuntimeTableSpec { id, len })
+                    }
+                    RuntimeTableDescription::Custom { id, first_column } => {
+                        RuntimeTableCfg::Custom { id, first_column }
+                    }
+                })
+                .collect()
+        });
+
+        ConstraintSystem::create(self.gates)
+            .public(self.public_input_size)
+            .prev_challenges(self.prev_challenges)
+            .zero_knowledge(self.zero_knowledge)
+            .lookup(lookup_tables)
+            .runtime(runtime_tables)
+    }
+}
+
+impl<F: PrimeField> Json for CircuitDescription<F> {
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        json::to_string(self)
+    }
+
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        json::to_string_pretty(self)
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let description: Self = json::from_str(json)?;
+        if description.version != CIRCUIT_DESCRIPTION_VERSION {
+            return Err(serde::de::Error::custom(format!(
+                "unsupported circuit description version {} (expected {CIRCUIT_DESCRIPTION_VERSION})",
+                description.version
+            )));
+        }
+        Ok(description)
+    }
+}
+
+impl<F: PrimeField> ConstraintSystem<F> {
+    /// Returns the description of this constraint system,
+    /// see the [module documentation](crate::circuits::description).
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the constraint system uses lookup tables that it does not record,
+    /// as when it was serialized before they were recorded.
+    pub fn description(&self) -> Result<CircuitDescription<F>, DescriptionError> {
+        // keep enough rows for the domain not to shrink
+        let d1_size = self.domain.d1.size();
+        let min_rows = (d1_size / 2 + 1).saturating_sub(self.zk_rows as usize);
+        let rows = unpadded_rows(&self.gates).max(min_rows).max(2);
+
+        let mut description = CircuitDescription::new(self.gates[..rows].to_vec(), self.public);
+        description.prev_challenges = self.prev_challenges;
+        description.zero_knowledge = self.zero_knowledge;
+
+        let lcs = match &self.lookup_constraint_system {
+            Some(lcs) => lcs,
+            None => return Ok(description),
+        };
+
+        // only runtime tables can come without any fixed table
+        if lcs.fixed_tables.is_empty() && lcs.runtime_table_offset != Some(0) {
+            return Err(DescriptionError::MissingLookupTables);
+        }
+
+        // the concatenated table starts with the tables of the gates,
+        // which are added again when building the constraint system
+        let gate_tables: HashSet<_> = lcs
+            .configuration
+            .lookup_info
+            .features
+            .patterns
+            .into_iter()
+            .filter_map(|pattern| pattern.table())
+            .collect();
+
+        // the evaluations over d8 contain the evaluations over d1 every 8 points
+        let column = |col: usize, rows: std::ops::Range<usize>| -> Vec<F> {
+            rows.map(|row| lcs.lookup_table8[col].evals[8 * row])
+                .collect()
+        };
+
+        let mut offset = 0;
+        for (i, table) in lcs.fixed_tables.iter().enumerate() {
+            if i >= gate_tables.len() {
+                description.lookup_tables.push(LookupTableDescription {
+                    id: table.id,
+                    data: (0..lcs.lookup_table8.len())
+                        .map(|col| column(col, offset..offset + table.len))
+                        .collect(),
+                });
+            }
+            offset += table.len;
+        }
+
+        description.runtime_tables = lcs.runtime_tables.as_ref().map(|tables| {
+            tables
+                .iter()
+                .map(|table| {
+                    let first_column = column(0, offset..offset + table.len);
+                    offset += table.len;
+                    if first_column
+                        .iter()
+                        .zip(0u64..)
+                        .all(|(value, index)| *value == F::from(index))
+                    {
+                        RuntimeTableDescription::Indexed {
+                            id: table.id,
+                            len: table.len,
+                        }
+                    } else {
+                        RuntimeTableDescription::Custom {
+                            id: table.id,
+                            first_column,
+                        }
+                    }
+                })
+                .collect()
+        });
+
+        Ok(description)
+    }
+}
@@ -1,8 +1,7 @@
 //! This contains the [DomainConstantEvaluations] which is used to provide precomputations to a [ConstraintSystem](super::constraints::ConstraintSystem).
 
 use crate::circuits::domains::EvaluationDomains;
-use crate::circuits::polynomials::permutation::zk_polynomial;
-use crate::circuits::polynomials::permutation::ZK_ROWS;
+use crate::circuits::polynomials::permutation::permutation_vanishing_polynomial;
 use ark_ff::FftField;
 use ark_poly::EvaluationDomain;
 use ark_poly::UVPolynomial;
@@ -37,7 +36,9 @@ pub struct DomainConstantEvaluations<F: FftField> {
 }
 
 impl<F: FftField> DomainConstantEvaluations<F> {
-    pub fn create(domain: EvaluationDomains<F>) -> Option<Self> {
+    /// Computes the precomputations for a circuit over `domain`
+    /// that reserves its last `zk_rows` rows.
+    pub fn create(domain: EvaluationDomains<F>, zk_rows: u64) -> Option<Self> {
         let poly_x_d1 = DP::from_coefficients_slice(&[F::zero(), F::one()])
             .evaluate_over_domain_by_ref(domain.d8);
         let constant_1_d4 =
@@ -48,10 +49,10 @@ impl<F: FftField> DomainConstantEvaluations<F> {
         let vanishes_on_last_4_rows =
             vanishes_on_last_4_rows(domain.d1).evaluate_over_domain(domain.d8);
 
-        assert!(domain.d1.size > ZK_ROWS);
+        assert!(domain.d1.size > zk_rows);
 
-        // x^3 - x^2(w1+w2+w3) + x(w1w2+w1w3+w2w3) - w1w2w3
-        let zkpm = zk_polynomial(domain.d1);
+        // (x-w^{n-zk_rows})...(x-w^{n-1})
+        let zkpm = permutation_vanishing_polynomial(domain.d1, zk_rows);
         let zkpl = zkpm.evaluate_over_domain_by_ref(domain.d8);
 
         Some(DomainConstantEvaluations {
@@ -45,6 +45,20 @@ pub fn zk_patch<R: Rng + ?Sized, F: FftField>(
     Evaluations::<F, D<F>>::from_vec_and_domain(e, d)
 }
 
+/// Pad with zeroes, including the last `ZK_ROWS` rows.
+/// This is the counterpart of [zk_patch] for proofs without zero-knowledge.
+///
+/// # Panics
+///
+/// Will panic if `evaluation` and `domain` length do not meet the requirement.
+pub fn zero_patch<F: FftField>(mut e: Vec<F>, d: D<F>) -> Evaluations<F, D<F>> {
+    let n = d.size();
+    let k = e.len();
+    assert!(k <= n - ZK_ROWS);
+    e.extend((0..(n - k)).map(|_| F::zero()));
+    Evaluations::<F, D<F>>::from_vec_and_domain(e, d)
+}
+
 //~
 //~ Because of our ZK-rows, we can't do the trick in the plookup paper of
 //~ wrapping around to enforce consistency between the sorted lookup columns.
@@ -238,6 +252,7 @@ pub fn aggregation<R, F>(
     beta: F,
     gamma: F,
     sorted: &[Evaluations<F, D<F>>],
+    zero_knowledge: bool,
     rng: &mut R,
     lookup_info: &LookupInfo,
 ) -> Result<Evaluations<F, D<F>>, ProverError>
@@ -318,7 +333,11 @@ where
             lookup_aggreg[i + 1] *= prev;
         });
 
-    let res = zk_patch(lookup_aggreg, d1, rng);
+    let res = if zero_knowledge {
+        zk_patch(lookup_aggreg, d1, rng)
+    } else {
+        zero_patch(lookup_aggreg, d1)
+    };
 
     // check that the final evaluation is equal to 1
     if cfg!(debug_assertions) {
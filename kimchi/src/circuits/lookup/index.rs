@@ -5,7 +5,7 @@ use crate::circuits::{
     lookup::{
         constraints::LookupConfiguration,
         lookups::{LookupInfo, LookupPattern},
-        tables::LookupTable,
+        tables::{LookupTable, LookupTableSpec},
     },
     polynomials::permutation::ZK_ROWS,
 };
@@ -203,6 +203,11 @@ pub struct LookupConstraintSystem<F: FftField> {
     /// Configuration for the lookup constraint.
     #[serde(bound = "LookupConfiguration<F>: Serialize + DeserializeOwned")]
     pub configuration: LookupConfiguration<F>,
+
+    /// The fixed lookup tables, in the order in which they are concatenated.
+    /// Empty for the indexes serialized before it was recorded.
+    #[serde(default)]
+    pub fixed_tables: Vec<LookupTableSpec>,
 }
 
 impl<F: PrimeField + SquareRootField> LookupConstraintSystem<F> {
@@ -239,6 +244,14 @@ impl<F: PrimeField + SquareRootField> LookupConstraintSystem<F> {
                     .chain(lookup_tables.into_iter())
                     .collect();
 
+                let fixed_tables = lookup_tables
+                    .iter()
+                    .map(|table| LookupTableSpec {
+                        id: table.id,
+                        len: table.len(),
+                    })
+                    .collect();
+
                 let mut has_table_id_0 = false;
 
                 // if we are using runtime tables
@@ -455,6 +468,7 @@ impl<F: PrimeField + SquareRootField> LookupConstraintSystem<F> {
                     runtime_tables,
                     runtime_table_offset,
                     configuration,
+                    fixed_tables,
                 }))
             }
         }
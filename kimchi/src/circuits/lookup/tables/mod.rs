@@ -59,6 +59,15 @@ where
     }
 }
 
+/// The ID and number of entries of a fixed lookup table, as recorded in the index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LookupTableSpec {
+    /// The table ID.
+    pub id: i32,
+    /// The number of entries contained in the table.
+    pub len: usize,
+}
+
 /// Returns the lookup table associated to a [`GateLookupTable`].
 pub fn get_table<F: FftField>(table_name: GateLookupTable) -> LookupTable<F> {
     match table_name {
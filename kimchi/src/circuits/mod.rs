@@ -3,6 +3,7 @@ pub mod macros;
 
 pub mod argument;
 pub mod constraints;
+pub mod description;
 pub mod domain_constant_evaluation;
 pub mod domains;
 pub mod expr;
@@ -12,5 +13,6 @@ pub mod polynomial;
 pub mod polynomials;
 pub mod scalars;
 mod serialization_helper;
+pub mod twiddles;
 pub mod wires;
 pub mod witness;
@@ -36,14 +36,15 @@
 use crate::circuits::{
     argument::{Argument, ArgumentEnv, ArgumentType},
     expr::constraints::ExprOps,
-    gate::{CircuitGate, GateType},
+    gate::{CircuitGate, Connect, GateType},
     polynomial::COLUMNS,
-    wires::GateWires,
+    wires::{GateWires, Wire},
 };
 use crate::{curve::KimchiCurve, prover_index::ProverIndex};
 use ark_ff::{FftField, PrimeField, Zero};
 use ark_poly::univariate::DensePolynomial;
 use std::array;
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 
 /// Number of constraints produced by the gate.
@@ -206,6 +207,110 @@ impl<F: PrimeField> CircuitGate<F> {
     }
 }
 
+/// A single generic gate: the variables in its registers and its coefficients
+struct PendingGeneric<F, V> {
+    vars: [Option<V>; GENERIC_REGISTERS],
+    coeffs: [F; GENERIC_COEFFS],
+}
+
+/// Builds the gates of a circuit whose registers hold variables of type `V`
+/// (as translated from another constraint system):
+/// the generic gates are packed two per row,
+/// and the cells holding the same variable are wired together by [`GenericGatePacker::finish`].
+pub(crate) struct GenericGatePacker<F, V> {
+    /// The gates of the circuit so far
+    pub gates: Vec<CircuitGate<F>>,
+    /// The cells holding each variable
+    cells: BTreeMap<V, Vec<(usize, usize)>>,
+    /// Queue (of size 1) of generic gates, to pack them two per row
+    pending: Option<PendingGeneric<F, V>>,
+}
+
+impl<F: PrimeField, V: Copy + Ord> GenericGatePacker<F, V> {
+    pub fn new() -> Self {
+        Self {
+            gates: vec![],
+            cells: BTreeMap::new(),
+            pending: None,
+        }
+    }
+
+    /// Records that the cell in column `col` of row `row` holds `var`
+    pub fn add_cell(&mut self, var: V, row: usize, col: usize) {
+        self.cells.entry(var).or_default().push((row, col));
+    }
+
+    /// Adds a public input row holding `var`
+    pub fn add_public(&mut self, var: V) {
+        let row = self.gates.len();
+        self.gates.push(CircuitGate::create_generic_gadget(
+            Wire::for_row(row),
+            GenericGateSpec::Pub,
+            None,
+        ));
+        self.add_cell(var, row, 0);
+    }
+
+    /// Adds a generic gate, and returns the row and the registers of the double generic gate
+    /// it completes, if any (the gate is queued to share its row with the next one otherwise).
+    pub fn add_generic(
+        &mut self,
+        vars: [Option<V>; GENERIC_REGISTERS],
+        coeffs: [F; GENERIC_COEFFS],
+    ) -> Option<(usize, [Option<V>; DOUBLE_GENERIC_REGISTERS])> {
+        let gate = PendingGeneric { vars, coeffs };
+        match self.pending.take() {
+            None => {
+                self.pending = Some(gate);
+                None
+            }
+            Some(first) => Some(self.add_row(first, Some(gate))),
+        }
+    }
+
+    /// Adds the queued generic gate, if any, alone in its row,
+    /// and returns the row and its registers
+    pub fn flush(&mut self) -> Option<(usize, [Option<V>; DOUBLE_GENERIC_REGISTERS])> {
+        let first = self.pending.take()?;
+        Some(self.add_row(first, None))
+    }
+
+    fn add_row(
+        &mut self,
+        first: PendingGeneric<F, V>,
+        second: Option<PendingGeneric<F, V>>,
+    ) -> (usize, [Option<V>; DOUBLE_GENERIC_REGISTERS]) {
+        let row = self.gates.len();
+        let mut vars = [None; DOUBLE_GENERIC_REGISTERS];
+        let mut coeffs = [F::zero(); DOUBLE_GENERIC_COEFFS];
+        for (i, gate) in std::iter::once(first).chain(second).enumerate() {
+            vars[i * GENERIC_REGISTERS..(i + 1) * GENERIC_REGISTERS].copy_from_slice(&gate.vars);
+            coeffs[i * GENERIC_COEFFS..(i + 1) * GENERIC_COEFFS].copy_from_slice(&gate.coeffs);
+        }
+        for (col, var) in vars.iter().enumerate() {
+            if let Some(var) = var {
+                self.add_cell(*var, row, col);
+            }
+        }
+        self.gates
+            .push(CircuitGate::create_generic(Wire::for_row(row), coeffs));
+        (row, vars)
+    }
+
+    /// Wires together all the cells holding the same variable, and returns the gates.
+    /// A queued generic gate must have been added with [`GenericGatePacker::flush`] before.
+    pub fn finish(self) -> Vec<CircuitGate<F>> {
+        debug_assert!(self.pending.is_none());
+        let mut gates = self.gates;
+        for cells in self.cells.values() {
+            for pair in cells.windows(2) {
+                gates.connect_cell_pair(pair[0], pair[1]);
+            }
+        }
+        gates
+    }
+}
+
 // -------------------------------------------------
 
 //~ The constraints:
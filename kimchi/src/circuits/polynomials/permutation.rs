@@ -58,6 +58,7 @@ use ark_poly::{
 };
 use ark_poly::{Polynomial, UVPolynomial};
 use blake2::{Blake2b512, Digest};
+use commitment_dlog::pcs::OpenProof;
 use o1_utils::{ExtendedDensePolynomial, ExtendedEvaluations};
 use rand::{CryptoRng, RngCore};
 use rayon::prelude::*;
@@ -90,7 +91,29 @@ pub fn vanishes_on_last_4_rows<F: FftField>(domain: D<F>) -> DensePolynomial<F>
 
 /// Returns the end of the circuit, which is used for introducing zero-knowledge in the permutation polynomial
 pub fn zk_w3<F: FftField>(domain: D<F>) -> F {
-    domain.group_gen.pow(&[domain.size - (ZK_ROWS)])
+    zk_w(domain, ZK_ROWS)
+}
+
+/// Returns the end of a circuit that reserves its last `zk_rows` rows,
+/// that is `w^{n - zk_rows}`
+pub fn zk_w<F: FftField>(domain: D<F>, zk_rows: u64) -> F {
+    domain.group_gen.pow(&[domain.size - zk_rows])
+}
+
+/// Computes the polynomial that vanishes on the last `zk_rows` rows of the domain:
+/// `(x-w^{n-zk_rows})...(x-w^{n-1})`.
+/// With `zk_rows = ZK_ROWS` this is [`zk_polynomial`].
+pub fn permutation_vanishing_polynomial<F: FftField>(
+    domain: D<F>,
+    zk_rows: u64,
+) -> DensePolynomial<F> {
+    let mut w = zk_w(domain, zk_rows);
+    let mut res = DensePolynomial::from_coefficients_slice(&[F::one()]);
+    for _ in 0..zk_rows {
+        res = &res * &DensePolynomial::from_coefficients_slice(&[-w, F::one()]);
+        w *= domain.group_gen;
+    }
+    res
 }
 
 /// Evaluates the polynomial
@@ -193,7 +216,9 @@ where
     }
 }
 
-impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
+impl<F: PrimeField, G: KimchiCurve<ScalarField = F>, OpeningProof: OpenProof<G>>
+    ProverIndex<G, OpeningProof>
+{
     /// permutation quotient poly contribution computation
     ///
     /// # Errors
@@ -303,9 +328,9 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
                 return Err(ProverError::Permutation("first division rest"));
             }
 
-            // accumulator end := (z(x) - 1) / (x - sid[n-3])
+            // accumulator end := (z(x) - 1) / (x - sid[n-k])
             let denominator = DensePolynomial::from_coefficients_slice(&[
-                -self.cs.sid[self.cs.domain.d1.size() - 3],
+                -self.cs.sid[self.cs.domain.d1.size() - self.cs.zk_rows as usize],
                 F::one(),
             ]);
             let (bnd2, res) = DenseOrSparsePolynomial::divide_with_q_and_r(
@@ -393,7 +418,9 @@ impl<F: PrimeField> ConstraintSystem<F> {
     }
 }
 
-impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
+impl<F: PrimeField, G: KimchiCurve<ScalarField = F>, OpeningProof: OpenProof<G>>
+    ProverIndex<G, OpeningProof>
+{
     /// permutation aggregation polynomial computation
     ///
     /// # Errors
@@ -411,6 +438,7 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
         rng: &mut (impl RngCore + CryptoRng),
     ) -> Result<DensePolynomial<F>, ProverError> {
         let n = self.cs.domain.d1.size();
+        let zk_rows = self.cs.zk_rows as usize;
 
         // only works if first element is 1
         assert_eq!(self.cs.domain.d1.elements().next(), Some(F::one()));
@@ -423,7 +451,8 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
 
         let mut z = vec![F::one(); n];
 
-        //~ For $i = 0, \cdot, n - 4$, where $n$ is the size of the domain,
+        //~ For $i = 0, \cdot, n - k - 1$, where $n$ is the size of the domain
+        //~ and $k$ the number of zero-knowledge rows (`ZK_ROWS`, or 1 without zero-knowledge),
         //~ evaluations are computed as:
         //~
         //~ $$z(g^{i+1}) = z_1 / z_2$$
@@ -457,7 +486,7 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
         //~ $$
         //~
         //~
-        for j in 0..n - 3 {
+        for j in 0..n - zk_rows {
             z[j + 1] = witness
                 .iter()
                 .zip(self.column_evaluations.permutation_coefficients8.iter())
@@ -465,9 +494,9 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
                 .fold(F::one(), |x, y| x * y);
         }
 
-        ark_ff::fields::batch_inversion::<F>(&mut z[1..=n - 3]);
+        ark_ff::fields::batch_inversion::<F>(&mut z[1..=n - zk_rows]);
 
-        for j in 0..n - 3 {
+        for j in 0..n - zk_rows {
             let x = z[j];
             z[j + 1] *= witness
                 .iter()
@@ -476,16 +505,20 @@ impl<F: PrimeField, G: KimchiCurve<ScalarField = F>> ProverIndex<G> {
                 .fold(x, |z, y| z * y);
         }
 
-        //~ If computed correctly, we should have $z(g^{n-3}) = 1$.
+        //~ If computed correctly, we should have $z(g^{n-k}) = 1$.
         //~
-        if z[n - 3] != F::one() {
+        if z[n - zk_rows] != F::one() {
             return Err(ProverError::Permutation("final value"));
         };
 
         //~ Finally, randomize the last `EVAL_POINTS` evaluations $z(g^{n-2})$ and $z(g^{n-1})$,
         //~ in order to add zero-knowledge to the protocol.
-        z[n - 2] = F::rand(rng);
-        z[n - 1] = F::rand(rng);
+        //~ Without zero-knowledge, they are left to $1$.
+        if self.cs.zero_knowledge {
+            for e in z.iter_mut().skip(n - zk_rows + 1) {
+                *e = F::rand(rng);
+            }
+        }
 
         let res = Evaluations::<F, D<F>>::from_vec_and_domain(z, self.cs.domain.d1).interpolate();
         Ok(res)
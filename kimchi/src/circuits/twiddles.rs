@@ -0,0 +1,168 @@
+//! This module implements FFTs over the evaluation domains of a circuit
+//! with precomputed twiddle factors.
+//!
+//! The FFTs of arkworks recompute the roots of unity of their domain at every call.
+//! [`Twiddles`] computes the roots of unity of the largest domain `d8` once,
+//! and uses them for the FFTs over `d1`, `d2`, `d4` and `d8`
+//! (the generator of each of these domains is a power of the generator of `d8`).
+//! The results are the same as the ones of arkworks.
+
+use ark_ff::FftField;
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain as D,
+    UVPolynomial,
+};
+use rayon::prelude::*;
+
+/// Below this number of butterflies per block, the butterflies of a block are computed sequentially
+const MIN_PARALLEL_BUTTERFLIES: usize = 1 << 10;
+
+/// The powers of the generator of a domain, and of its inverse,
+/// used by the FFTs over this domain and its subdomains
+#[derive(Clone, Debug)]
+pub struct Twiddles<F: FftField> {
+    /// The size of the largest domain
+    size: u64,
+    /// `w^i` for `i < size / 2`, where `w` is the generator of the largest domain
+    roots: Vec<F>,
+    /// `w^-i` for `i < size / 2`
+    inv_roots: Vec<F>,
+}
+
+impl<F: FftField> Twiddles<F> {
+    /// Precomputes the twiddle factors of `domain`,
+    /// for the FFTs over `domain` and any smaller domain.
+    pub fn new(domain: D<F>) -> Self {
+        Twiddles {
+            size: domain.size,
+            roots: powers(domain.group_gen, domain.size() / 2),
+            inv_roots: powers(domain.group_gen_inv, domain.size() / 2),
+        }
+    }
+
+    /// Evaluates `poly` over `domain`, as [`DensePolynomial::evaluate_over_domain_by_ref`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `domain` is larger than the domain of the twiddles.
+    pub fn evaluate_over_domain(
+        &self,
+        poly: &DensePolynomial<F>,
+        domain: D<F>,
+    ) -> Evaluations<F, D<F>> {
+        let mut evals = poly.coeffs.clone();
+        evals.resize(domain.size(), F::zero());
+        self.fft_in_place(&mut evals, &self.roots);
+        Evaluations::from_vec_and_domain(evals, domain)
+    }
+
+    /// Interpolates `evals` over `domain` (padded with zeros), as [`Evaluations::interpolate`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `domain` is larger than the domain of the twiddles.
+    pub fn interpolate(&self, mut evals: Vec<F>, domain: D<F>) -> DensePolynomial<F> {
+        evals.resize(domain.size(), F::zero());
+        self.fft_in_place(&mut evals, &self.inv_roots);
+        evals.par_iter_mut().for_each(|x| *x *= domain.size_inv);
+        DensePolynomial::from_coefficients_vec(evals)
+    }
+
+    /// Interpolates evaluations, as [`Evaluations::interpolate_by_ref`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the domain of `evals` is larger than the domain of the twiddles.
+    pub fn interpolate_by_ref(&self, evals: &Evaluations<F, D<F>>) -> DensePolynomial<F> {
+        self.interpolate(evals.evals.clone(), evals.domain())
+    }
+
+    /// An iterative radix-2 FFT, where `roots` are the powers of the generator of the largest domain
+    /// (or of its inverse).
+    fn fft_in_place(&self, xs: &mut [F], roots: &[F]) {
+        let n = xs.len();
+        assert!(n as u64 <= self.size, "domain larger than the twiddles");
+        if n <= 1 {
+            return;
+        }
+        let log_n = n.trailing_zeros();
+
+        for i in 0..n {
+            let j = i.reverse_bits() >> (usize::BITS - log_n);
+            if i < j {
+                xs.swap(i, j);
+            }
+        }
+
+        // the twiddles of the blocks of size 2m are the powers of the generator of the domain of size 2m,
+        // which is the power `size / 2m` of the generator of the largest domain
+        let mut m = 1;
+        while m < n {
+            let stride = self.size as usize / (2 * m);
+            xs.par_chunks_mut(2 * m).for_each(|block| {
+                let (lo, hi) = block.split_at_mut(m);
+                let butterfly = |(j, (lo, hi)): (usize, (&mut F, &mut F))| {
+                    let t = *hi * roots[j * stride];
+                    *hi = *lo - t;
+                    *lo += t;
+                };
+                if m < MIN_PARALLEL_BUTTERFLIES {
+                    lo.iter_mut().zip(hi).enumerate().for_each(butterfly);
+                } else {
+                    lo.par_iter_mut()
+                        .zip(hi.par_iter_mut())
+                        .enumerate()
+                        .for_each(butterfly);
+                }
+            });
+            m *= 2;
+        }
+    }
+}
+
+/// Returns `x^i` for `i < n`
+fn powers<F: FftField>(x: F, n: usize) -> Vec<F> {
+    let mut res = Vec::with_capacity(n);
+    let mut acc = F::one();
+    for _ in 0..n {
+        res.push(acc);
+        acc *= x;
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuits::domains::EvaluationDomains;
+    use ark_ff::UniformRand;
+    use mina_curves::pasta::Fp;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_twiddles_match_arkworks() {
+        let rng = &mut StdRng::from_seed([0u8; 32]);
+        let domains = EvaluationDomains::<Fp>::create(1 << 10).unwrap();
+        let twiddles = Twiddles::new(domains.d8);
+
+        for domain in [domains.d1, domains.d2, domains.d4, domains.d8] {
+            let evals: Vec<_> = (0..domain.size()).map(|_| Fp::rand(rng)).collect();
+            let evals = Evaluations::from_vec_and_domain(evals, domain);
+            let poly = twiddles.interpolate_by_ref(&evals);
+            assert_eq!(poly, evals.interpolate_by_ref());
+            assert_eq!(twiddles.evaluate_over_domain(&poly, domain), evals);
+
+            // a polynomial of lower degree, and fewer evaluations than the domain
+            let small = DensePolynomial::<Fp>::rand(domains.d1.size() - 1, rng);
+            assert_eq!(
+                twiddles.evaluate_over_domain(&small, domain),
+                small.evaluate_over_domain_by_ref(domain)
+            );
+            let few = evals.evals[..3].to_vec();
+            assert_eq!(
+                twiddles.interpolate(few.clone(), domain),
+                Evaluations::from_vec_and_domain(few, domain).interpolate()
+            );
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! This module implements a versioned, self-describing binary container
+//! for [`ProverProof`] and [`VerifierIndex`].
+//!
+//! A container is laid out as follows (integers are little-endian):
+//!
+//! | field        | size            | description                                           |
+//! |--------------|-----------------|-------------------------------------------------------|
+//! | magic        | 4               | `b"KIMC"`                                             |
+//! | version      | 2               | the format version ([`FORMAT_VERSION`])               |
+//! | kind         | 1               | the [`ContainerKind`] of the content                  |
+//! | curve length | 1               | the length of the curve name                          |
+//! | curve        | curve length    | the curve name ([`KimchiCurve::NAME`])                |
+//! | flags        | 4               | the feature flags of the content (see [`flags`])      |
+//! | length       | 8               | the length of the payload                             |
+//! | payload      | length          | the content, serialized with `rmp_serde`              |
+//! | checksum     | 32              | the first 32 bytes of Blake2b-512 of all of the above |
+//!
+//! Field elements and curve points are serialized with arkworks (points are compressed).
+//!
+//! Nothing may follow the checksum.
+//!
+//! Payloads written with an older format version are upgraded to the current one
+//! by the [migrations](migrate) before being deserialized.
+//! The format versions are:
+//!
+//! 1. the first version,
+//! 2. proofs end with the digest of the verifier index they were created for (`nil` if omitted),
+//! 3. proofs end with the evaluation of $ft$ at $\zeta$ computed by the prover (`nil` if omitted).
+
+use crate::{
+    curve::KimchiCurve, error::ContainerError, proof::ProverProof, verifier_index::VerifierIndex,
+};
+use blake2::{Blake2b512, Digest};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt,
+    io::{Read, Write},
+};
+
+/// The magic bytes that start every container
+pub const MAGIC: [u8; 4] = *b"KIMC";
+
+/// The current version of the format
+pub const FORMAT_VERSION: u16 = 3;
+
+/// The size of the checksum at the end of the container
+const CHECKSUM_SIZE: usize = 32;
+
+/// The feature flags stored in the header of a container
+pub mod flags {
+    pub const CHACHA: u32 = 1 << 0;
+    pub const RANGE_CHECK0: u32 = 1 << 1;
+    pub const RANGE_CHECK1: u32 = 1 << 2;
+    pub const FOREIGN_FIELD_ADD: u32 = 1 << 3;
+    pub const FOREIGN_FIELD_MUL: u32 = 1 << 4;
+    pub const XOR: u32 = 1 << 5;
+    pub const ROT: u32 = 1 << 6;
+    pub const LOOKUP: u32 = 1 << 7;
+    pub const RUNTIME_TABLES: u32 = 1 << 8;
+    pub const NON_ZERO_KNOWLEDGE: u32 = 1 << 9;
+    pub const RECURSION: u32 = 1 << 10;
+}
+
+/// The kind of content stored in a container
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContainerKind {
+    Proof,
+    VerifierIndex,
+}
+
+impl ContainerKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            ContainerKind::Proof => 1,
+            ContainerKind::VerifierIndex => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self, ContainerError> {
+        match byte {
+            1 => Ok(ContainerKind::Proof),
+            2 => Ok(ContainerKind::VerifierIndex),
+            _ => Err(ContainerError::UnknownKind(byte)),
+        }
+    }
+}
+
+impl fmt::Display for ContainerKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerKind::Proof => write!(f, "proof"),
+            ContainerKind::VerifierIndex => write!(f, "verifier index"),
+        }
+    }
+}
+
+/// The header of a container, which describes its content
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContainerHeader {
+    /// the format version the content was written with
+    pub version: u16,
+    /// the kind of content
+    pub kind: ContainerKind,
+    /// the name of the curve of the content
+    pub curve: String,
+    /// the feature flags of the content
+    pub flags: u32,
+}
+
+/// A migration upgrades a payload of the given kind from a format version to the next one
+pub type Migration = fn(ContainerKind, Vec<u8>) -> Result<Vec<u8>, ContainerError>;
+
+/// The migrations between format versions:
+/// `MIGRATIONS[i]` upgrades a payload from version `i + 1` to version `i + 2`.
+/// When bumping [`FORMAT_VERSION`], add the migration from the previous version here.
+const MIGRATIONS: [Migration; FORMAT_VERSION as usize - 1] =
+    [append_verifier_index_digest, append_ft_eval0];
+
+/// The number of fields of a proof in format version 1
+const PROOF_FIELDS_V1: u8 = 6;
+
+/// The number of fields of a proof in format version 2
+const PROOF_FIELDS_V2: u8 = PROOF_FIELDS_V1 + 1;
+
+/// The `rmp_serde` marker of an array of `len` elements (for `len < 16`)
+const fn fixarray(len: u8) -> u8 {
+    0x90 | len
+}
+
+/// The `rmp_serde` encoding of a missing optional value
+const NIL: u8 = 0xc0;
+
+/// Migrates from version 1 to version 2: appends an absent verifier index digest to proofs.
+/// Proofs are encoded as arrays of their fields, so this extends the array of their fields with `nil`.
+fn append_verifier_index_digest(
+    kind: ContainerKind,
+    payload: Vec<u8>,
+) -> Result<Vec<u8>, ContainerError> {
+    append_nil_to_proof(1, PROOF_FIELDS_V1, kind, payload)
+}
+
+/// Migrates from version 2 to version 3: appends an absent evaluation of $ft$ at $\zeta$ to proofs.
+fn append_ft_eval0(kind: ContainerKind, payload: Vec<u8>) -> Result<Vec<u8>, ContainerError> {
+    append_nil_to_proof(2, PROOF_FIELDS_V2, kind, payload)
+}
+
+/// Extends the array of the `fields` fields of a proof written with format `version` with `nil`.
+fn append_nil_to_proof(
+    version: u16,
+    fields: u8,
+    kind: ContainerKind,
+    mut payload: Vec<u8>,
+) -> Result<Vec<u8>, ContainerError> {
+    if kind != ContainerKind::Proof {
+        return Ok(payload);
+    }
+    match payload.first() {
+        Some(&marker) if marker == fixarray(fields) => {
+            payload[0] = fixarray(fields + 1);
+            payload.push(NIL);
+            Ok(payload)
+        }
+        _ => Err(ContainerError::Migration(
+            version,
+            format!("a proof should be an array of {fields} fields"),
+        )),
+    }
+}
+
+/// Upgrades a payload written with format `version` to the current format.
+///
+/// # Errors
+///
+/// Will give error if `version` is not supported, or if one of the migrations fails.
+pub fn migrate(
+    version: u16,
+    kind: ContainerKind,
+    mut payload: Vec<u8>,
+) -> Result<Vec<u8>, ContainerError> {
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(ContainerError::UnsupportedVersion(version, FORMAT_VERSION));
+    }
+    for migration in &MIGRATIONS[(version as usize - 1)..] {
+        payload = migration(kind, payload)?;
+    }
+    Ok(payload)
+}
+
+/// Encodes a container with the given header and payload.
+///
+/// # Errors
+///
+/// Will give error if the name of the curve is too long.
+pub fn encode(header: &ContainerHeader, payload: &[u8]) -> Result<Vec<u8>, ContainerError> {
+    let curve = header.curve.as_bytes();
+    let curve_len = u8::try_from(curve.len())
+        .map_err(|_| ContainerError::Serialization("curve name too long".to_string()))?;
+
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&MAGIC);
+    bytes.extend_from_slice(&header.version.to_le_bytes());
+    bytes.push(header.kind.to_byte());
+    bytes.push(curve_len);
+    bytes.extend_from_slice(curve);
+    bytes.extend_from_slice(&header.flags.to_le_bytes());
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(payload);
+
+    let checksum = Blake2b512::digest(&bytes);
+    bytes.extend_from_slice(&checksum[..CHECKSUM_SIZE]);
+
+    Ok(bytes)
+}
+
+/// A small cursor over the bytes of a container
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ContainerError> {
+        if self.0.len() < len {
+            return Err(ContainerError::Truncated);
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ContainerError> {
+        let mut res = [0u8; N];
+        res.copy_from_slice(self.take(N)?);
+        Ok(res)
+    }
+}
+
+/// Reads and checks the header of a container.
+///
+/// # Errors
+///
+/// Will give error if the data is not a container, or if it was written
+/// with a format version that is not supported.
+pub fn read_header(bytes: &[u8]) -> Result<ContainerHeader, ContainerError> {
+    read_header_and_payload(bytes).map(|(header, _)| header)
+}
+
+fn read_header_and_payload(bytes: &[u8]) -> Result<(ContainerHeader, &[u8]), ContainerError> {
+    let mut reader = Reader(bytes);
+
+    if reader.array::<4>()? != MAGIC {
+        return Err(ContainerError::InvalidMagic);
+    }
+
+    let version = u16::from_le_bytes(reader.array()?);
+    if version == 0 || version > FORMAT_VERSION {
+        return Err(ContainerError::UnsupportedVersion(version, FORMAT_VERSION));
+    }
+
+    let kind = ContainerKind::from_byte(reader.array::<1>()?[0])?;
+
+    let curve_len = reader.array::<1>()?[0] as usize;
+    let curve = String::from_utf8_lossy(reader.take(curve_len)?).into_owned();
+
+    let flags = u32::from_le_bytes(reader.array()?);
+
+    let payload_len = u64::from_le_bytes(reader.array()?);
+    let payload_len = usize::try_from(payload_len).map_err(|_| ContainerError::Truncated)?;
+    let payload = reader.take(payload_len)?;
+
+    let content_len = bytes.len() - reader.0.len();
+    let checksum = reader.take(CHECKSUM_SIZE)?;
+    if checksum != &Blake2b512::digest(&bytes[..content_len])[..CHECKSUM_SIZE] {
+        return Err(ContainerError::InvalidChecksum);
+    }
+
+    if !reader.0.is_empty() {
+        return Err(ContainerError::TrailingBytes(reader.0.len()));
+    }
+
+    let header = ContainerHeader {
+        version,
+        kind,
+        curve,
+        flags,
+    };
+    Ok((header, payload))
+}
+
+/// Content that can be stored in a container.
+pub trait Container: Serialize + DeserializeOwned {
+    /// The kind of content
+    const KIND: ContainerKind;
+
+    /// The name of the curve of the content
+    fn curve() -> &'static str;
+
+    /// The feature flags of the content (see [`flags`])
+    fn feature_flags(&self) -> u32;
+
+    /// Restores the parts of the content that are not serialized
+    fn restore(&mut self) {}
+
+    /// Encodes the content in a container.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the content cannot be serialized.
+    fn to_container_bytes(&self) -> Result<Vec<u8>, ContainerError> {
+        let payload =
+            rmp_serde::to_vec(self).map_err(|e| ContainerError::Serialization(e.to_string()))?;
+
+        let header = ContainerHeader {
+            version: FORMAT_VERSION,
+            kind: Self::KIND,
+            curve: Self::curve().to_string(),
+            flags: self.feature_flags(),
+        };
+        encode(&header, &payload)
+    }
+
+    /// Decodes the content of a container, migrating it to the current format version if needed.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the container is malformed, corrupted,
+    /// or if it does not contain this kind of content over this curve.
+    fn from_container_bytes(bytes: &[u8]) -> Result<Self, ContainerError> {
+        let (header, payload) = read_header_and_payload(bytes)?;
+
+        if header.kind != Self::KIND {
+            return Err(ContainerError::UnexpectedKind {
+                expected: Self::KIND,
+                found: header.kind,
+            });
+        }
+
+        if header.curve != Self::curve() {
+            return Err(ContainerError::UnexpectedCurve {
+                expected: Self::curve(),
+                found: header.curve,
+            });
+        }
+
+        let payload = migrate(header.version, header.kind, payload.to_vec())?;
+
+        let mut content: Self = rmp_serde::from_slice(&payload)
+            .map_err(|e| ContainerError::Deserialization(e.to_string()))?;
+
+        let content_flags = content.feature_flags();
+        if content_flags != header.flags {
+            return Err(ContainerError::FeatureFlagsMismatch {
+                header: header.flags,
+                content: content_flags,
+            });
+        }
+
+        content.restore();
+
+        Ok(content)
+    }
+
+    /// Writes the content as a container.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the content cannot be serialized or written.
+    fn write_container(&self, writer: &mut impl Write) -> Result<(), ContainerError> {
+        writer.write_all(&self.to_container_bytes()?)?;
+        Ok(())
+    }
+
+    /// Reads a container until the end of `reader`, and decodes its content.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the container cannot be read or decoded.
+    fn read_container(reader: &mut impl Read) -> Result<Self, ContainerError> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+        Self::from_container_bytes(&bytes)
+    }
+}
+
+impl<G: KimchiCurve> Container for ProverProof<G> {
+    const KIND: ContainerKind = ContainerKind::Proof;
+
+    fn curve() -> &'static str {
+        G::NAME
+    }
+
+    fn feature_flags(&self) -> u32 {
+        let mut res = 0;
+        if let Some(lookup) = &self.commitments.lookup {
+            res |= flags::LOOKUP;
+            if lookup.runtime.is_some() {
+                res |= flags::RUNTIME_TABLES;
+            }
+        }
+        if !self.prev_challenges.is_empty() {
+            res |= flags::RECURSION;
+        }
+        res
+    }
+}
+
+impl<G: KimchiCurve> Container for VerifierIndex<G> {
+    const KIND: ContainerKind = ContainerKind::VerifierIndex;
+
+    fn curve() -> &'static str {
+        G::NAME
+    }
+
+    fn feature_flags(&self) -> u32 {
+        let feature_flags = self.feature_flags();
+        let mut res = 0;
+        for (enabled, flag) in [
+            (feature_flags.chacha, flags::CHACHA),
+            (feature_flags.range_check0, flags::RANGE_CHECK0),
+            (feature_flags.range_check1, flags::RANGE_CHECK1),
+            (feature_flags.foreign_field_add, flags::FOREIGN_FIELD_ADD),
+            (feature_flags.foreign_field_mul, flags::FOREIGN_FIELD_MUL),
+            (feature_flags.xor, flags::XOR),
+            (feature_flags.rot, flags::ROT),
+            (self.lookup_index.is_some(), flags::LOOKUP),
+            (
+                feature_flags.lookup_features.uses_runtime_tables,
+                flags::RUNTIME_TABLES,
+            ),
+            (!self.zero_knowledge, flags::NON_ZERO_KNOWLEDGE),
+            (self.prev_challenges > 0, flags::RECURSION),
+        ] {
+            if enabled {
+                res |= flag;
+            }
+        }
+        res
+    }
+
+    fn restore(&mut self) {
+        self.restore_skipped_fields();
+    }
+}
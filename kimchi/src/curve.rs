@@ -16,6 +16,9 @@ pub trait KimchiCurve: CommitmentCurve {
         OtherCurve = Self,
     >;
 
+    /// A unique name for this curve, used to identify it in serialized data
+    const NAME: &'static str;
+
     /// Provides the sponge params to be used with this curve
     /// If the params for the base field are needed, they can be obtained from [`KimchiCurve::OtherCurve`]
     fn sponge_params() -> &'static ArithmeticSpongeParams<Self::ScalarField>;
@@ -28,6 +31,8 @@ pub trait KimchiCurve: CommitmentCurve {
 impl KimchiCurve for GroupAffine<VestaParameters> {
     type OtherCurve = GroupAffine<PallasParameters>;
 
+    const NAME: &'static str = "vesta";
+
     fn sponge_params() -> &'static ArithmeticSpongeParams<Self::ScalarField> {
         mina_poseidon::pasta::fp_kimchi::static_params()
     }
@@ -44,6 +49,8 @@ impl KimchiCurve for GroupAffine<VestaParameters> {
 impl KimchiCurve for GroupAffine<PallasParameters> {
     type OtherCurve = GroupAffine<VestaParameters>;
 
+    const NAME: &'static str = "pallas";
+
     fn sponge_params() -> &'static ArithmeticSpongeParams<Self::ScalarField> {
         mina_poseidon::pasta::fq_kimchi::static_params()
     }
@@ -64,6 +71,8 @@ impl KimchiCurve for GroupAffine<PallasParameters> {
 impl KimchiCurve for GroupAffine<LegacyVestaParameters> {
     type OtherCurve = GroupAffine<LegacyPallasParameters>;
 
+    const NAME: &'static str = "legacy_vesta";
+
     fn sponge_params() -> &'static ArithmeticSpongeParams<Self::ScalarField> {
         mina_poseidon::pasta::fp_legacy::static_params()
     }
@@ -75,6 +84,8 @@ impl KimchiCurve for GroupAffine<LegacyVestaParameters> {
 impl KimchiCurve for GroupAffine<LegacyPallasParameters> {
     type OtherCurve = GroupAffine<LegacyVestaParameters>;
 
+    const NAME: &'static str = "legacy_pallas";
+
     fn sponge_params() -> &'static ArithmeticSpongeParams<Self::ScalarField> {
         mina_poseidon::pasta::fq_legacy::static_params()
     }
@@ -0,0 +1,705 @@
+//! This module computes structural differences between two circuits,
+//! to find out why the digest of a verifier index changed.
+//!
+//! Gates are aligned before being compared, so that a gadget inserted or removed
+//! in the middle of a circuit only shows up as the gates it adds or removes,
+//! and not as a change of every gate that follows it.
+//! Two gates are aligned when they have the same type and the same coefficients;
+//! the wiring of aligned gates is then compared through the alignment,
+//! so that wires pointing to shifted rows are not reported as changed.
+//!
+//! The diffs are available for gates ([`diff_gates`]),
+//! constraint systems ([`ConstraintSystem::diff`])
+//! and verifier indexes ([`VerifierIndex::diff`]),
+//! and their [`Display`](fmt::Display) implementations format them for humans.
+
+use crate::{
+    circuits::{
+        constraints::ConstraintSystem,
+        gate::{CircuitGate, GateType},
+        lookup::{
+            index::LookupConstraintSystem, runtime_tables::RuntimeTableSpec,
+            tables::LookupTableSpec,
+        },
+        wires::{Wire, COLUMNS, PERMUTS},
+    },
+    curve::KimchiCurve,
+    stats::{enabled_features, unpadded_rows},
+    verifier_index::VerifierIndex,
+};
+use ark_ff::{PrimeField, Zero};
+use ark_poly::EvaluationDomain;
+use std::{collections::BTreeMap, fmt, ops::Range};
+
+/// The maximum number of inserted and removed gates searched for when aligning two circuits.
+/// Beyond it, the remaining gates are compared row by row.
+const MAX_EDIT_DISTANCE: usize = 1 << 10;
+
+/// A difference between the gates of two circuits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateChange {
+    /// A gate of the new circuit, at the given row, that is not in the old circuit.
+    Inserted { row: usize, typ: GateType },
+    /// A gate of the old circuit, at the given row, that is not in the new circuit.
+    Removed { row: usize, typ: GateType },
+    /// A gate that is in both circuits, but differs.
+    Changed {
+        /// The row of the gate in the old circuit.
+        old_row: usize,
+        /// The row of the gate in the new circuit.
+        new_row: usize,
+        /// The type of the gate in the old circuit.
+        old_typ: GateType,
+        /// The type of the gate in the new circuit.
+        new_typ: GateType,
+        /// The indices of the coefficients that differ.
+        coeffs: Vec<usize>,
+        /// The columns whose wiring differs.
+        wires: Vec<usize>,
+    },
+}
+
+/// A difference between the lookup tables of two circuits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableChange {
+    /// A table of the new circuit that is not in the old circuit.
+    Inserted { id: i32, len: usize },
+    /// A table of the old circuit that is not in the new circuit.
+    Removed { id: i32, len: usize },
+    /// A table whose number of entries changed.
+    Resized {
+        id: i32,
+        old_len: usize,
+        new_len: usize,
+    },
+    /// A table with the same number of entries, but different values.
+    Modified { id: i32 },
+}
+
+/// The differences between two constraint systems.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConstraintSystemDiff {
+    /// The differences between the gates, padding excluded.
+    pub gates: Vec<GateChange>,
+    /// The old and new number of public inputs, if it changed.
+    pub public: Option<(usize, usize)>,
+    /// The old and new number of previous challenges, if it changed.
+    pub prev_challenges: Option<(usize, usize)>,
+    /// The old and new domain sizes, if it changed.
+    pub domain_size: Option<(usize, usize)>,
+    /// The old and new zero-knowledge settings, if it changed.
+    pub zero_knowledge: Option<(bool, bool)>,
+    /// The optional features used by the new circuit only.
+    pub added_features: Vec<&'static str>,
+    /// The optional features used by the old circuit only.
+    pub removed_features: Vec<&'static str>,
+    /// The differences between the fixed lookup tables.
+    pub lookup_tables: Vec<TableChange>,
+    /// The differences between the runtime table configurations.
+    pub runtime_tables: Vec<TableChange>,
+}
+
+/// The differences between two verifier indexes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerifierIndexDiff {
+    /// The old and new number of public inputs, if it changed.
+    pub public: Option<(usize, usize)>,
+    /// The old and new number of previous challenges, if it changed.
+    pub prev_challenges: Option<(usize, usize)>,
+    /// The old and new domain sizes, if it changed.
+    pub domain_size: Option<(usize, usize)>,
+    /// The old and new maximum polynomial sizes, if it changed.
+    pub max_poly_size: Option<(usize, usize)>,
+    /// The old and new zero-knowledge settings, if it changed.
+    pub zero_knowledge: Option<(bool, bool)>,
+    /// The names of the commitments that differ, or that are only present in one of the indexes.
+    pub commitments: Vec<String>,
+    /// Whether the lookup configurations differ.
+    pub lookup_info: bool,
+}
+
+/// Returns `Some((old, new))` if the values differ.
+fn changed<T: PartialEq>(old: T, new: T) -> Option<(T, T)> {
+    (old != new).then_some((old, new))
+}
+
+/// Whether the furthest path on the diagonal `k` after `d` edits extends the one of the diagonal `k + 1`
+/// (with an insertion) rather than the one of the diagonal `k - 1` (with a removal),
+/// given the furthest indices `at` of the diagonals after `d - 1` edits.
+fn from_above(d: isize, k: isize, at: impl Fn(isize) -> isize) -> bool {
+    k == -d || (k != d && at(k - 1) < at(k + 1))
+}
+
+/// Aligns two sequences with Myers' algorithm,
+/// returning the pairs of indices of elements that are kept,
+/// in increasing order.
+fn align<T>(old: &[T], new: &[T], eq: impl Fn(&T, &T) -> bool) -> Vec<(usize, usize)> {
+    // the common prefix and suffix are kept as is
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| eq(old, new))
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| eq(old, new))
+        .count();
+
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+    let (n, m) = (a.len() as isize, b.len() as isize);
+
+    let mut pairs: Vec<_> = (0..prefix).map(|i| (i, i)).collect();
+
+    // `v[idx(k)]` is the furthest index reached in `a` on the diagonal `k`,
+    // and `trace[d]` keeps the diagonals `-d..=d` of `v` after `d` edits
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let idx = |k: isize| (offset + k) as usize;
+    let mut v = vec![0isize; 2 * max + 3];
+    let mut trace: Vec<Vec<isize>> = vec![];
+
+    let mut end = None;
+    for d in 0..=(max.min(MAX_EDIT_DISTANCE) as isize) {
+        let mut done = false;
+        for k in (-d..=d).step_by(2) {
+            let mut x = if from_above(d, k, |k| v[idx(k)]) {
+                v[idx(k + 1)]
+            } else {
+                v[idx(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && eq(&a[x as usize], &b[y as usize]) {
+                x += 1;
+                y += 1;
+            }
+            v[idx(k)] = x;
+            if x >= n && y >= m {
+                done = true;
+                break;
+            }
+        }
+        trace.push(v[idx(-d)..=idx(d)].to_vec());
+        if done {
+            end = Some(d);
+            break;
+        }
+    }
+
+    // walk back the edits to find the kept elements,
+    // the diagonal `k` of `trace[d - 1]` being at index `k + d - 1`
+    let mut kept = vec![];
+    if let Some(end) = end {
+        let (mut x, mut y) = (n, m);
+        for d in (1..=end).rev() {
+            let at = |k: isize| trace[d as usize - 1][(k + d - 1) as usize];
+            let k = x - y;
+            let prev_k = if from_above(d, k, at) { k + 1 } else { k - 1 };
+            let (prev_x, prev_y) = (at(prev_k), at(prev_k) - prev_k);
+            while x > prev_x && y > prev_y {
+                x -= 1;
+                y -= 1;
+                kept.push((x as usize, y as usize));
+            }
+            (x, y) = (prev_x, prev_y);
+        }
+        kept.extend((0..x.min(y) as usize).rev().map(|i| (i, i)));
+    }
+    pairs.extend(
+        kept.into_iter()
+            .rev()
+            .map(|(x, y)| (prefix + x, prefix + y)),
+    );
+
+    pairs.extend((0..suffix).map(|i| (old.len() - suffix + i, new.len() - suffix + i)));
+    pairs
+}
+
+/// How a row of the old or new circuit is aligned.
+enum Alignment {
+    Pair(usize, usize),
+    Removed(usize),
+    Inserted(usize),
+}
+
+/// Aligns the gates that are not kept between two kept gates:
+/// when both sides have the same number of gates, they are paired row by row,
+/// otherwise gates are paired with the next gate of the same type.
+fn align_gap<F: PrimeField>(
+    old: &[CircuitGate<F>],
+    new: &[CircuitGate<F>],
+    old_rows: Range<usize>,
+    new_rows: Range<usize>,
+    alignment: &mut Vec<Alignment>,
+) {
+    if old_rows.len() == new_rows.len() {
+        alignment.extend(old_rows.zip(new_rows).map(|(o, n)| Alignment::Pair(o, n)));
+        return;
+    }
+
+    let mut next_new = new_rows.start;
+    for o in old_rows {
+        match (next_new..new_rows.end).find(|&n| new[n].typ == old[o].typ) {
+            Some(n) => {
+                alignment.extend((next_new..n).map(Alignment::Inserted));
+                alignment.push(Alignment::Pair(o, n));
+                next_new = n + 1;
+            }
+            None => alignment.push(Alignment::Removed(o)),
+        }
+    }
+    alignment.extend((next_new..new_rows.end).map(Alignment::Inserted));
+}
+
+/// Returns the differences between two lists of gates, see the [module documentation](self).
+pub fn diff_gates<F: PrimeField>(
+    old: &[CircuitGate<F>],
+    new: &[CircuitGate<F>],
+) -> Vec<GateChange> {
+    let kept = align(old, new, |a, b| a.typ == b.typ && a.coeffs == b.coeffs);
+
+    let mut alignment = vec![];
+    let (mut o, mut n) = (0, 0);
+    for (next_o, next_n) in kept.into_iter().chain([(old.len(), new.len())]) {
+        align_gap(old, new, o..next_o, n..next_n, &mut alignment);
+        if next_o < old.len() {
+            alignment.push(Alignment::Pair(next_o, next_n));
+        }
+        o = next_o + 1;
+        n = next_n + 1;
+    }
+
+    // where each row of the old circuit went
+    let mut new_row_of = vec![None; old.len()];
+    for a in &alignment {
+        if let Alignment::Pair(o, n) = a {
+            new_row_of[*o] = Some(*n);
+        }
+    }
+
+    alignment
+        .into_iter()
+        .filter_map(|a| match a {
+            Alignment::Removed(row) => Some(GateChange::Removed {
+                row,
+                typ: old[row].typ,
+            }),
+            Alignment::Inserted(row) => Some(GateChange::Inserted {
+                row,
+                typ: new[row].typ,
+            }),
+            Alignment::Pair(old_row, new_row) => {
+                let (old_gate, new_gate) = (&old[old_row], &new[new_row]);
+
+                let num_coeffs = old_gate.coeffs.len().max(new_gate.coeffs.len());
+                let coeffs: Vec<_> = (0..num_coeffs)
+                    .filter(|&i| old_gate.coeffs.get(i) != new_gate.coeffs.get(i))
+                    .collect();
+
+                let wires: Vec<_> = (0..PERMUTS)
+                    .filter(|&col| {
+                        let wire = old_gate.wires[col];
+                        let moved = new_row_of
+                            .get(wire.row)
+                            .copied()
+                            .flatten()
+                            .map(|row| Wire { row, col: wire.col });
+                        moved != Some(new_gate.wires[col])
+                    })
+                    .collect();
+
+                (old_gate.typ != new_gate.typ || !coeffs.is_empty() || !wires.is_empty()).then(
+                    || GateChange::Changed {
+                        old_row,
+                        new_row,
+                        old_typ: old_gate.typ,
+                        new_typ: new_gate.typ,
+                        coeffs,
+                        wires,
+                    },
+                )
+            }
+        })
+        .collect()
+}
+
+/// Returns the differences between two lists of tables, matched by ID.
+/// The `same_entries` closure tells if the tables with the given ID and length have the same entries.
+fn diff_tables(
+    old: &[LookupTableSpec],
+    new: &[LookupTableSpec],
+    same_entries: impl Fn(i32, usize) -> bool,
+) -> Vec<TableChange> {
+    let old_tables: BTreeMap<_, _> = old.iter().map(|t| (t.id, t.len)).collect();
+    let new_tables: BTreeMap<_, _> = new.iter().map(|t| (t.id, t.len)).collect();
+
+    let mut changes = vec![];
+    for (&id, &len) in &old_tables {
+        match new_tables.get(&id) {
+            None => changes.push(TableChange::Removed { id, len }),
+            Some(&new_len) if new_len != len => changes.push(TableChange::Resized {
+                id,
+                old_len: len,
+                new_len,
+            }),
+            Some(_) if !same_entries(id, len) => changes.push(TableChange::Modified { id }),
+            Some(_) => (),
+        }
+    }
+    for (&id, &len) in &new_tables {
+        if !old_tables.contains_key(&id) {
+            changes.push(TableChange::Inserted { id, len });
+        }
+    }
+    changes
+}
+
+/// Returns the entries of the fixed table with the given ID, one vector per row.
+fn table_entries<F: PrimeField>(lcs: &LookupConstraintSystem<F>, id: i32) -> Vec<Vec<F>> {
+    let mut offset = 0;
+    for table in &lcs.fixed_tables {
+        if table.id == id {
+            // the evaluations over d8 contain the evaluations over d1 every 8 points
+            return (offset..offset + table.len)
+                .map(|row| {
+                    lcs.lookup_table8
+                        .iter()
+                        .map(|col| col.evals[8 * row])
+                        .collect()
+                })
+                .collect();
+        }
+        offset += table.len;
+    }
+    vec![]
+}
+
+fn fixed_table_specs<F: PrimeField>(cs: &ConstraintSystem<F>) -> &[LookupTableSpec] {
+    cs.lookup_constraint_system
+        .as_ref()
+        .map(|lcs| lcs.fixed_tables.as_slice())
+        .unwrap_or_default()
+}
+
+fn runtime_table_specs<F: PrimeField>(cs: &ConstraintSystem<F>) -> Vec<LookupTableSpec> {
+    let runtime_tables: &[RuntimeTableSpec] = cs
+        .lookup_constraint_system
+        .as_ref()
+        .and_then(|lcs| lcs.runtime_tables.as_deref())
+        .unwrap_or_default();
+    runtime_tables
+        .iter()
+        .map(|table| LookupTableSpec {
+            id: table.id,
+            len: table.len,
+        })
+        .collect()
+}
+
+impl<F: PrimeField> ConstraintSystem<F> {
+    /// Returns the differences from this constraint system to a `new` one,
+    /// see the [module documentation](crate::diff).
+    pub fn diff(&self, new: &ConstraintSystem<F>) -> ConstraintSystemDiff {
+        let gates = diff_gates(
+            &self.gates[..unpadded_rows(&self.gates)],
+            &new.gates[..unpadded_rows(&new.gates)],
+        );
+
+        let old_features = enabled_features(&self.feature_flags);
+        let new_features = enabled_features(&new.feature_flags);
+
+        let lookup_tables =
+            diff_tables(fixed_table_specs(self), fixed_table_specs(new), |id, _| {
+                match (
+                    &self.lookup_constraint_system,
+                    &new.lookup_constraint_system,
+                ) {
+                    (Some(old), Some(new)) => {
+                        // tables are padded with zeros to the same width
+                        let pad = |mut entries: Vec<Vec<F>>, width| {
+                            for entry in &mut entries {
+                                entry.resize(width, F::zero());
+                            }
+                            entries
+                        };
+                        let width = old.lookup_table8.len().max(new.lookup_table8.len());
+                        pad(table_entries(old, id), width) == pad(table_entries(new, id), width)
+                    }
+                    _ => false,
+                }
+            });
+
+        let runtime_tables = diff_tables(
+            &runtime_table_specs(self),
+            &runtime_table_specs(new),
+            |_, _| true,
+        );
+
+        ConstraintSystemDiff {
+            gates,
+            public: changed(self.public, new.public),
+            prev_challenges: changed(self.prev_challenges, new.prev_challenges),
+            domain_size: changed(self.domain.d1.size(), new.domain.d1.size()),
+            zero_knowledge: changed(self.zero_knowledge, new.zero_knowledge),
+            added_features: new_features
+                .iter()
+                .filter(|f| !old_features.contains(f))
+                .copied()
+                .collect(),
+            removed_features: old_features
+                .iter()
+                .filter(|f| !new_features.contains(f))
+                .copied()
+                .collect(),
+            lookup_tables,
+            runtime_tables,
+        }
+    }
+}
+
+impl ConstraintSystemDiff {
+    /// Returns `true` if the two constraint systems are structurally identical.
+    pub fn is_empty(&self) -> bool {
+        self == &ConstraintSystemDiff::default()
+    }
+}
+
+impl<G: KimchiCurve> VerifierIndex<G> {
+    /// Returns the differences from this verifier index to a `new` one.
+    ///
+    /// A verifier index only contains commitments to the circuit,
+    /// so this reports which commitments changed;
+    /// use [`ConstraintSystem::diff`] to find the gates that changed.
+    pub fn diff(&self, new: &VerifierIndex<G>) -> VerifierIndexDiff {
+        let mut commitments = vec![];
+        let mut compare = |name: String, same: bool| {
+            if !same {
+                commitments.push(name);
+            }
+        };
+
+        for i in 0..PERMUTS {
+            compare(
+                format!("sigma_comm[{i}]"),
+                self.sigma_comm[i] == new.sigma_comm[i],
+            );
+        }
+        for i in 0..COLUMNS {
+            compare(
+                format!("coefficients_comm[{i}]"),
+                self.coefficients_comm[i] == new.coefficients_comm[i],
+            );
+        }
+        compare("generic_comm".into(), self.generic_comm == new.generic_comm);
+        compare("psm_comm".into(), self.psm_comm == new.psm_comm);
+        compare(
+            "complete_add_comm".into(),
+            self.complete_add_comm == new.complete_add_comm,
+        );
+        compare("mul_comm".into(), self.mul_comm == new.mul_comm);
+        compare("emul_comm".into(), self.emul_comm == new.emul_comm);
+        compare(
+            "endomul_scalar_comm".into(),
+            self.endomul_scalar_comm == new.endomul_scalar_comm,
+        );
+        compare("chacha_comm".into(), self.chacha_comm == new.chacha_comm);
+        compare(
+            "range_check0_comm".into(),
+            self.range_check0_comm == new.range_check0_comm,
+        );
+        compare(
+            "range_check1_comm".into(),
+            self.range_check1_comm == new.range_check1_comm,
+        );
+        compare(
+            "foreign_field_add_comm".into(),
+            self.foreign_field_add_comm == new.foreign_field_add_comm,
+        );
+        compare(
+            "foreign_field_mul_comm".into(),
+            self.foreign_field_mul_comm == new.foreign_field_mul_comm,
+        );
+        compare("xor_comm".into(), self.xor_comm == new.xor_comm);
+        compare("rot_comm".into(), self.rot_comm == new.rot_comm);
+
+        let mut lookup_info = false;
+        match (&self.lookup_index, &new.lookup_index) {
+            (None, None) => (),
+            (Some(old), Some(new)) => {
+                let tables = old.lookup_table.len().max(new.lookup_table.len());
+                for i in 0..tables {
+                    compare(
+                        format!("lookup_table[{i}]"),
+                        old.lookup_table.get(i) == new.lookup_table.get(i),
+                    );
+                }
+                let (old_selectors, new_selectors) =
+                    (old.lookup_selectors.as_ref(), new.lookup_selectors.as_ref());
+                compare(
+                    "lookup_selectors.xor".into(),
+                    old_selectors.xor == new_selectors.xor,
+                );
+                compare(
+                    "lookup_selectors.chacha_final".into(),
+                    old_selectors.chacha_final == new_selectors.chacha_final,
+                );
+                compare(
+                    "lookup_selectors.lookup".into(),
+                    old_selectors.lookup == new_selectors.lookup,
+                );
+                compare(
+                    "lookup_selectors.range_check".into(),
+                    old_selectors.range_check == new_selectors.range_check,
+                );
+                compare(
+                    "lookup_selectors.ffmul".into(),
+                    old_selectors.ffmul == new_selectors.ffmul,
+                );
+                compare("table_ids".into(), old.table_ids == new.table_ids);
+                compare(
+                    "runtime_tables_selector".into(),
+                    old.runtime_tables_selector == new.runtime_tables_selector,
+                );
+                lookup_info = old.joint_lookup_used != new.joint_lookup_used
+                    || old.lookup_info.max_per_row != new.lookup_info.max_per_row
+                    || old.lookup_info.max_joint_size != new.lookup_info.max_joint_size
+                    || old.lookup_info.features.patterns != new.lookup_info.features.patterns
+                    || old.lookup_info.features.uses_runtime_tables
+                        != new.lookup_info.features.uses_runtime_tables;
+            }
+            _ => {
+                compare("lookup_index".into(), false);
+                lookup_info = true;
+            }
+        }
+
+        VerifierIndexDiff {
+            public: changed(self.public, new.public),
+            prev_challenges: changed(self.prev_challenges, new.prev_challenges),
+            domain_size: changed(self.domain.size(), new.domain.size()),
+            max_poly_size: changed(self.max_poly_size, new.max_poly_size),
+            zero_knowledge: changed(self.zero_knowledge, new.zero_knowledge),
+            commitments,
+            lookup_info,
+        }
+    }
+}
+
+impl VerifierIndexDiff {
+    /// Returns `true` if the two verifier indexes are identical.
+    pub fn is_empty(&self) -> bool {
+        self == &VerifierIndexDiff::default()
+    }
+}
+
+//
+// Formatting
+//
+
+fn write_change<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    change: &Option<(T, T)>,
+) -> fmt::Result {
+    match change {
+        Some((old, new)) => writeln!(f, "{name}: {old} -> {new}"),
+        None => Ok(()),
+    }
+}
+
+impl fmt::Display for GateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateChange::Inserted { row, typ } => write!(f, "+ row {row}: {typ:?}"),
+            GateChange::Removed { row, typ } => write!(f, "- row {row}: {typ:?}"),
+            GateChange::Changed {
+                old_row,
+                new_row,
+                old_typ,
+                new_typ,
+                coeffs,
+                wires,
+            } => {
+                write!(f, "~ row {old_row} -> {new_row}: ")?;
+                let mut changes = vec![];
+                if old_typ != new_typ {
+                    changes.push(format!("type {old_typ:?} -> {new_typ:?}"));
+                } else {
+                    changes.push(format!("{new_typ:?}"));
+                }
+                if !coeffs.is_empty() {
+                    changes.push(format!("coefficients {coeffs:?}"));
+                }
+                if !wires.is_empty() {
+                    changes.push(format!("wiring of columns {wires:?}"));
+                }
+                write!(f, "{}", changes.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for TableChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableChange::Inserted { id, len } => write!(f, "+ table {id} ({len} entries)"),
+            TableChange::Removed { id, len } => write!(f, "- table {id} ({len} entries)"),
+            TableChange::Resized {
+                id,
+                old_len,
+                new_len,
+            } => write!(f, "~ table {id}: {old_len} -> {new_len} entries"),
+            TableChange::Modified { id } => write!(f, "~ table {id}: entries changed"),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintSystemDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        write_change(f, "public inputs", &self.public)?;
+        write_change(f, "previous challenges", &self.prev_challenges)?;
+        write_change(f, "domain size", &self.domain_size)?;
+        write_change(f, "zero-knowledge", &self.zero_knowledge)?;
+        for feature in &self.added_features {
+            writeln!(f, "+ feature {feature}")?;
+        }
+        for feature in &self.removed_features {
+            writeln!(f, "- feature {feature}")?;
+        }
+        for change in &self.lookup_tables {
+            writeln!(f, "{change}")?;
+        }
+        for change in &self.runtime_tables {
+            writeln!(f, "{change} (runtime)")?;
+        }
+        for change in &self.gates {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for VerifierIndexDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no differences");
+        }
+        write_change(f, "public inputs", &self.public)?;
+        write_change(f, "previous challenges", &self.prev_challenges)?;
+        write_change(f, "domain size", &self.domain_size)?;
+        write_change(f, "maximum polynomial size", &self.max_poly_size)?;
+        write_change(f, "zero-knowledge", &self.zero_knowledge)?;
+        if self.lookup_info {
+            writeln!(f, "~ lookup configuration")?;
+        }
+        for commitment in &self.commitments {
+            writeln!(f, "~ {commitment}")?;
+        }
+        Ok(())
+    }
+}
@@ -30,10 +30,16 @@ pub enum ProverError {
 
     #[error("wrong number of custom blinders given: {0}")]
     WrongBlinders(CommitmentError),
+
+    #[error("the polynomial commitments could not be opened: {0}")]
+    Opening(#[from] CommitmentError),
+
+    #[error("the proof creation was cancelled")]
+    Cancelled,
 }
 
 /// Errors that can arise when verifying a proof
-#[derive(Error, Debug, Clone, Copy)]
+#[derive(Error, Debug, Clone)]
 pub enum VerifyError {
     #[error("the commitment to {0} is of an unexpected size")]
     IncorrectCommitmentLength(&'static str),
@@ -44,9 +50,30 @@ pub enum VerifyError {
     #[error("the previous challenges have an unexpected length (expected {0}, got {1})")]
     IncorrectPrevChallengesLength(usize, usize),
 
-    #[error("the opening proof failed to verify")]
+    #[error("the opening proof of the batch failed to verify")]
     OpenProof,
 
+    /// The proof at the given position of a batch failed to verify, for the given reason.
+    #[error("the proof {0} of the batch failed to verify: {1}")]
+    BatchProofFailed(usize, Box<VerifyError>),
+
+    /// The evaluation of $ft$ at $\zeta$ included in the proof by the prover
+    /// differs from the one recomputed by the verifier:
+    /// the evaluations of the proof do not satisfy the constraints of the circuit
+    /// for its public input.
+    #[error("the evaluation of ft at zeta does not match the one recomputed from the evaluations")]
+    FtEval0Mismatch,
+
+    /// The opening proof does not satisfy its equation for the evaluations claimed by the proof:
+    /// either one of the evaluations, or `delta`, `z1` or `z2`, is incorrect.
+    #[error("the opening proof does not match the combined inner product of the evaluations")]
+    CombinedInnerProduct,
+
+    /// The commitment to the challenge polynomial of the opening proof (`sg`)
+    /// does not match the challenges of the opening proof.
+    #[error("the commitment to the challenge polynomial of the opening proof is incorrect")]
+    ChallengePolynomial,
+
     #[error("lookup used in circuit, but proof is missing lookup commitments")]
     LookupCommitmentMissing,
 
@@ -70,6 +97,9 @@ pub enum VerifyError {
 
     #[error("the commitment for {0:?} is missing")]
     MissingCommitment(crate::circuits::expr::Column),
+
+    #[error("the proof was created for a different verifier index (the digests do not match)")]
+    IndexMismatch,
 }
 
 /// Errors that can arise when preparing the setup
@@ -88,3 +118,143 @@ pub enum VerifierIndexError {
     #[error("srs has already been set")]
     SRSHasBeenSet,
 }
+
+/// Errors that can arise when encoding or decoding a [container](crate::container)
+#[derive(Error, Debug)]
+pub enum ContainerError {
+    #[error("the data is truncated")]
+    Truncated,
+
+    #[error("the data does not start with the expected magic bytes")]
+    InvalidMagic,
+
+    #[error("unsupported format version {0} (latest supported version is {1})")]
+    UnsupportedVersion(u16, u16),
+
+    #[error("unknown content kind {0}")]
+    UnknownKind(u8),
+
+    #[error("expected a {expected}, found a {found}")]
+    UnexpectedKind {
+        expected: crate::container::ContainerKind,
+        found: crate::container::ContainerKind,
+    },
+
+    #[error("the data is for curve {found}, expected curve {expected}")]
+    UnexpectedCurve {
+        expected: &'static str,
+        found: String,
+    },
+
+    #[error("the checksum does not match the content")]
+    InvalidChecksum,
+
+    #[error("{0} unexpected bytes after the checksum")]
+    TrailingBytes(usize),
+
+    #[error(
+        "the feature flags of the header ({header:#x}) do not match the content ({content:#x})"
+    )]
+    FeatureFlagsMismatch { header: u32, content: u32 },
+
+    #[error("the migration from format version {0} failed: {1}")]
+    Migration(u16, String),
+
+    #[error("the content could not be serialized: {0}")]
+    Serialization(String),
+
+    #[error("the content could not be deserialized: {0}")]
+    Deserialization(String),
+
+    #[error("i/o error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    VerifierIndex(#[from] VerifierIndexError),
+}
+
+/// Errors that can arise when exporting the [description](crate::circuits::description)
+/// of a constraint system
+#[derive(Error, Debug, Clone)]
+pub enum DescriptionError {
+    #[error("the lookup tables of the constraint system are unknown (it was serialized before they were recorded)")]
+    MissingLookupTables,
+}
+
+/// Errors that can arise when importing a [circom](crate::circom) circuit or witness
+#[derive(Error, Debug)]
+pub enum CircomError {
+    #[error("the {0} file is truncated")]
+    Truncated(&'static str),
+
+    #[error("the file is not a {0} file")]
+    InvalidMagic(&'static str),
+
+    #[error("unsupported {0} format version {1}")]
+    UnsupportedVersion(&'static str, u32),
+
+    #[error("the {0} file has no {1} section")]
+    MissingSection(&'static str, &'static str),
+
+    #[error("the {format} file is over the field of prime {found}, expected prime {expected}")]
+    FieldMismatch {
+        format: &'static str,
+        expected: String,
+        found: String,
+    },
+
+    #[error("the {0} file contains a non-canonical field element")]
+    InvalidFieldElement(&'static str),
+
+    #[error(
+        "constraint {constraint} uses wire {wire}, but the circuit only has {num_wires} wires"
+    )]
+    InvalidWire {
+        constraint: usize,
+        wire: usize,
+        num_wires: usize,
+    },
+
+    #[error("constraint {0} only involves constants and does not hold")]
+    UnsatisfiableConstraint(usize),
+
+    #[error("the circuit has {0} gates, but kimchi needs at least 2")]
+    TooFewGates(usize),
+
+    #[error("the witness has {found} wires, expected {expected}")]
+    WitnessLength { expected: usize, found: usize },
+
+    #[error("the wire 0 of the witness must be the constant 1")]
+    InvalidConstantWire,
+}
+
+/// Errors that can arise when translating a Noir [ACIR](crate::acir) program or its witness
+#[derive(Error, Debug)]
+pub enum AcirError {
+    #[error("the ACIR data could not be deserialized: {0}")]
+    Deserialization(String),
+
+    #[error("{0} is not an element of the field of the circuit")]
+    InvalidFieldElement(String),
+
+    #[error("opcode {index} ({opcode}) is not supported")]
+    UnsupportedOpcode { index: usize, opcode: &'static str },
+
+    #[error("opcode {index} calls the black box function {function}, which is not supported")]
+    UnsupportedBlackBox {
+        index: usize,
+        function: &'static str,
+    },
+
+    #[error("opcode {index} checks a range of {num_bits} bits, at most 64 bits are supported")]
+    UnsupportedRange { index: usize, num_bits: u32 },
+
+    #[error("opcode {0} only involves constants and does not hold")]
+    UnsatisfiableConstraint(usize),
+
+    #[error("the witness map has no value for witness {0}")]
+    MissingWitness(u32),
+
+    #[error("the value of witness {witness} does not fit in {num_bits} bits")]
+    InvalidInput { witness: u32, num_bits: usize },
+}
@@ -0,0 +1,163 @@
+//! This module implements a canonical JSON representation of
+//! [`ProverProof`], [`VerifierIndex`], lists of [`CircuitGate`] and witness tables,
+//! for consumers that are not written in Rust.
+//!
+//! The representation is the one of the serde implementations of these types,
+//! where the byte strings of the arkworks encodings are written as hex strings
+//! (see [`to_string`] and [`from_str`]), with the following conventions:
+//!
+//! - field elements are lowercase hex strings of their canonical arkworks encoding
+//!   (32 bytes, little-endian, not in Montgomery form),
+//! - curve points are lowercase hex strings of their compressed arkworks encoding
+//!   (the little-endian x-coordinate, with the sign of y and the point at infinity
+//!   encoded in the two most significant bits of the last byte),
+//! - the evaluation domain of a [`VerifierIndex`] is the hex string of its arkworks encoding,
+//! - optional values are `null` when absent,
+//! - a witness table is an array of [`COLUMNS`] columns, each an array of field elements.
+//!
+//! The layout is described by the JSON schema [`JSON_SCHEMA`],
+//! which lives in `kimchi/schema/kimchi.schema.json`.
+//! The fields of a [`VerifierIndex`] that are not serialized
+//! (the SRS, the linearization, the powers of alpha and the endoscalar coefficient)
+//! are recomputed when it is imported.
+
+use crate::{
+    circuits::{gate::CircuitGate, wires::COLUMNS},
+    curve::KimchiCurve,
+    proof::ProverProof,
+    verifier_index::VerifierIndex,
+};
+use ark_ff::PrimeField;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+
+mod hex;
+
+use self::hex::{Hex, HexDeserializer};
+
+/// The JSON schema describing the JSON representation of
+/// proofs (`#/$defs/Proof`), verifier indexes (`#/$defs/VerifierIndex`),
+/// lists of gates (`#/$defs/Gates`), circuit descriptions (`#/$defs/CircuitDescription`)
+/// and witness tables (`#/$defs/Witness`).
+pub const JSON_SCHEMA: &str = include_str!("../../schema/kimchi.schema.json");
+
+/// Serializes `value` as compact JSON, with byte strings written as hex strings.
+///
+/// # Errors
+///
+/// Will give error if `value` cannot be serialized.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    to_string(&Hex(value))
+}
+
+/// Serializes `value` as indented JSON, with byte strings written as hex strings.
+///
+/// # Errors
+///
+/// Will give error if `value` cannot be serialized.
+pub fn to_string_pretty<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    to_string_pretty(&Hex(value))
+}
+
+/// Deserializes a value from JSON written by [`to_string`] or [`to_string_pretty`],
+/// with byte strings read from hex strings.
+///
+/// # Errors
+///
+/// Will give error if `json` is not valid JSON, or does not represent a `T`.
+pub fn from_str<T: DeserializeOwned>(json: &str) -> Result<T, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value = T::deserialize(HexDeserializer(&mut deserializer))?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Types that can be exported to and imported from canonical JSON.
+pub trait Json: Sized {
+    /// Exports `self` as compact JSON.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `self` cannot be serialized.
+    fn to_json(&self) -> Result<String, serde_json::Error>;
+
+    /// Exports `self` as indented JSON.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `self` cannot be serialized.
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error>;
+
+    /// Imports a value from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `json` does not follow the canonical representation.
+    fn from_json(json: &str) -> Result<Self, serde_json::Error>;
+}
+
+impl<G: KimchiCurve> Json for ProverProof<G> {
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        to_string(self)
+    }
+
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        to_string_pretty(self)
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        from_str(json)
+    }
+}
+
+impl<G: KimchiCurve> Json for VerifierIndex<G> {
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        to_string(self)
+    }
+
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        to_string_pretty(self)
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let mut index: Self = from_str(json)?;
+        index.restore_skipped_fields();
+        Ok(index)
+    }
+}
+
+impl<F: PrimeField> Json for Vec<CircuitGate<F>> {
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        to_string(self)
+    }
+
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        to_string_pretty(self)
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        from_str(json)
+    }
+}
+
+/// The serde representation of a witness table
+#[serde_as]
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct WitnessTable<F: PrimeField>(
+    #[serde_as(as = "[Vec<o1_utils::serialization::SerdeAs>; COLUMNS]")] [Vec<F>; COLUMNS],
+);
+
+impl<F: PrimeField> Json for [Vec<F>; COLUMNS] {
+    fn to_json(&self) -> Result<String, serde_json::Error> {
+        to_string(&WitnessTable(self.clone()))
+    }
+
+    fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        to_string_pretty(&WitnessTable(self.clone()))
+    }
+
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        from_str::<WitnessTable<F>>(json).map(|witness| witness.0)
+    }
+}
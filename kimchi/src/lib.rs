@@ -10,19 +10,28 @@ pub use mina_poseidon;
 pub use o1_utils;
 pub use turshi;
 
+pub mod acir;
 pub mod alphas;
 pub mod bench;
+pub mod circom;
 pub mod circuits;
+pub mod container;
 pub mod curve;
+pub mod diff;
 pub mod error;
+pub mod json;
 pub mod lagrange_basis_evaluations;
 pub mod linearization;
 pub mod oracles;
 pub mod plonk_sponge;
 pub mod proof;
 pub mod prover;
+pub mod prover_hook;
 pub mod prover_index;
+pub mod prover_rng;
 pub mod snarky;
+pub mod stats;
+pub mod transcript;
 pub mod verifier;
 pub mod verifier_index;
 
@@ -9,8 +9,9 @@ use ark_ec::AffineCurve;
 use ark_ff::{FftField, One, Zero};
 use ark_poly::univariate::DensePolynomial;
 use commitment_dlog::{
+    accumulator::Accumulator,
     commitment::{b_poly, b_poly_coefficients, PolyComm},
-    evaluation_proof::OpeningProof,
+    evaluation_proof::OpeningProof as DlogOpeningProof,
 };
 use o1_utils::ExtendedDensePolynomial;
 use serde::{Deserialize, Serialize};
@@ -107,15 +108,20 @@ pub struct ProverCommitments<G: AffineCurve> {
 }
 
 /// The proof that the prover creates from a [ProverIndex](super::prover_index::ProverIndex) and a `witness`.
+/// The batched opening proof is the one of the polynomial commitment scheme of the index
+/// (see [`OpenProof`](commitment_dlog::pcs::OpenProof)), the inner-product argument by default.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(bound = "G: ark_serialize::CanonicalDeserialize + ark_serialize::CanonicalSerialize")]
-pub struct ProverProof<G: AffineCurve> {
+#[serde(bound(
+    serialize = "G: ark_serialize::CanonicalSerialize, OpeningProof: Serialize",
+    deserialize = "G: ark_serialize::CanonicalDeserialize, OpeningProof: Deserialize<'de>"
+))]
+pub struct ProverProof<G: AffineCurve, OpeningProof = DlogOpeningProof<G>> {
     /// All the polynomial commitments required in the proof
     pub commitments: ProverCommitments<G>,
 
     /// batched commitment opening proof
-    pub proof: OpeningProof<G>,
+    pub proof: OpeningProof,
 
     /// Two evaluations over a number of committed polynomials
     pub evals: ProofEvaluations<PointEvaluations<Vec<G::ScalarField>>>,
@@ -130,6 +136,22 @@ pub struct ProverProof<G: AffineCurve> {
 
     /// The challenges underlying the optional polynomials folded into the proof
     pub prev_challenges: Vec<RecursionChallenge<G>>,
+
+    /// The digest of the [VerifierIndex](super::verifier_index::VerifierIndex) the proof was created for, if included.
+    /// When present, the verifier checks it before anything else,
+    /// so that a proof verified against the wrong index is reported as such.
+    #[serde(default)]
+    #[serde_as(as = "Option<o1_utils::serialization::SerdeAs>")]
+    pub verifier_index_digest: Option<G::BaseField>,
+
+    /// The evaluation of $ft$ at $\zeta$ as computed by the prover,
+    /// if included (see [`ProverIndex::include_ft_eval0`](crate::prover_index::ProverIndex::include_ft_eval0)).
+    /// The verifier recomputes it from the other evaluations and never uses this one,
+    /// except to report a proof whose evaluations do not satisfy the constraints
+    /// (for example because of an incorrect witness or public input) as such.
+    #[serde(default)]
+    #[serde_as(as = "Option<o1_utils::serialization::SerdeAs>")]
+    pub ft_eval0: Option<G::ScalarField>,
 }
 
 /// A struct to store the challenges inside a `ProverProof`
@@ -320,6 +342,13 @@ impl<F> ProofEvaluations<F> {
     }
 }
 
+/// An accumulator of the inner-product argument is passed to the next proof of a recursion as a challenge
+impl<G: AffineCurve> From<Accumulator<G>> for RecursionChallenge<G> {
+    fn from(acc: Accumulator<G>) -> Self {
+        RecursionChallenge::new(acc.chals, PolyComm::new(vec![acc.sg], None))
+    }
+}
+
 impl<G: AffineCurve> RecursionChallenge<G> {
     pub fn new(chals: Vec<G::ScalarField>, comm: PolyComm<G>) -> RecursionChallenge<G> {
         RecursionChallenge { chals, comm }
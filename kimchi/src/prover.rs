@@ -31,7 +31,10 @@ use crate::{
         LookupCommitments, LookupEvaluations, PointEvaluations, ProofEvaluations,
         ProverCommitments, ProverProof, RecursionChallenge,
     },
+    prover_hook::{ProverHook, ProverStep},
     prover_index::ProverIndex,
+    prover_rng::DeterministicRng,
+    transcript,
 };
 use ark_ec::ProjectiveCurve;
 use ark_ff::{FftField, Field, One, PrimeField, UniformRand, Zero};
@@ -44,10 +47,12 @@ use commitment_dlog::{
         absorb_commitment, b_poly_coefficients, BlindedCommitment, CommitmentCurve, PolyComm,
     },
     evaluation_proof::DensePolynomialOrEvaluations,
+    pcs::{CommitmentKey, OpenProof},
 };
 use itertools::Itertools;
 use mina_poseidon::{sponge::ScalarChallenge, FqSponge};
 use o1_utils::ExtendedDensePolynomial as _;
+use rand_core::{CryptoRng, RngCore};
 use rayon::prelude::*;
 use std::array;
 use std::collections::HashMap;
@@ -115,7 +120,24 @@ where
     runtime_second_col_d8: Option<Evaluations<F, D<F>>>,
 }
 
-impl<G: KimchiCurve> ProverProof<G>
+/// An instance to prove with [`ProverProof::create_batch`]
+pub struct BatchWitness<F> {
+    /// The witness columns
+    pub witness: [Vec<F>; COLUMNS],
+    /// The runtime tables, if the circuit uses any
+    pub runtime_tables: Vec<RuntimeTable<F>>,
+}
+
+impl<F> From<[Vec<F>; COLUMNS]> for BatchWitness<F> {
+    fn from(witness: [Vec<F>; COLUMNS]) -> Self {
+        BatchWitness {
+            witness,
+            runtime_tables: vec![],
+        }
+    }
+}
+
+impl<G: KimchiCurve, OpeningProof: OpenProof<G>> ProverProof<G, OpeningProof>
 where
     G::BaseField: PrimeField,
 {
@@ -131,7 +153,7 @@ where
         groupmap: &G::Map,
         witness: [Vec<G::ScalarField>; COLUMNS],
         runtime_tables: &[RuntimeTable<G::ScalarField>],
-        index: &ProverIndex<G>,
+        index: &ProverIndex<G, OpeningProof>,
     ) -> Result<Self> {
         Self::create_recursive::<EFqSponge, EFrSponge>(
             groupmap,
@@ -155,25 +177,218 @@ where
     pub fn create_recursive<
         EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
         EFrSponge: FrSponge<G::ScalarField>,
+    >(
+        group_map: &G::Map,
+        witness: [Vec<G::ScalarField>; COLUMNS],
+        runtime_tables: &[RuntimeTable<G::ScalarField>],
+        index: &ProverIndex<G, OpeningProof>,
+        prev_challenges: Vec<RecursionChallenge<G>>,
+        blinders: Option<[Option<PolyComm<G::ScalarField>>; COLUMNS]>,
+    ) -> Result<Self> {
+        Self::create_recursive_with_rng::<EFqSponge, EFrSponge, _>(
+            group_map,
+            witness,
+            runtime_tables,
+            index,
+            prev_challenges,
+            blinders,
+            &mut rand::rngs::OsRng,
+            None,
+        )
+    }
+
+    /// Same as [`ProverProof::create_recursive`], except that all the randomness
+    /// is derived from `seed` (see [`DeterministicRng`] for the schedule).
+    /// Two calls with the same inputs and the same seed produce byte-identical proofs.
+    ///
+    /// Warning: reusing a seed across different witnesses breaks zero-knowledge.
+    /// This mode is meant to reproduce proofs for debugging and testing.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `create_recursive_with_rng` process fails.
+    pub fn create_deterministic<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+    >(
+        group_map: &G::Map,
+        witness: [Vec<G::ScalarField>; COLUMNS],
+        runtime_tables: &[RuntimeTable<G::ScalarField>],
+        index: &ProverIndex<G, OpeningProof>,
+        prev_challenges: Vec<RecursionChallenge<G>>,
+        seed: [u8; 32],
+    ) -> Result<Self> {
+        Self::create_recursive_with_rng::<EFqSponge, EFrSponge, _>(
+            group_map,
+            witness,
+            runtime_tables,
+            index,
+            prev_challenges,
+            None,
+            &mut DeterministicRng::from_seed(seed),
+            None,
+        )
+    }
+
+    /// Creates one proof per witness, all for the same circuit.
+    ///
+    /// Everything that only depends on the index is computed once, and cached in the index for later proofs:
+    /// the digest of the verifier index ([`ProverIndex::verifier_index_digest`]),
+    /// the twiddle factors of the FFTs ([`ConstraintSystem::twiddles`](crate::circuits::constraints::ConstraintSystem::twiddles)),
+    /// the evaluations of the selectors and permutation polynomials ([`ProverIndex::column_evaluations`]),
+    /// and the domain constants ([`DomainConstantEvaluations`](crate::circuits::domain_constant_evaluation::DomainConstantEvaluations)).
+    ///
+    /// Proofs are created in parallel, in rounds of as many proofs as fit in `max_memory` bytes
+    /// according to [`ProverIndex::proof_memory_estimate`] (and at least one proof per round).
+    ///
+    /// If `hook` is given, it is called at each step of each proof (see [`ProverHook`]),
+    /// possibly from several threads at once.
+    ///
+    /// Returns one result per witness, in the same order as `witnesses`.
+    pub fn create_batch<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+    >(
+        group_map: &G::Map,
+        witnesses: Vec<BatchWitness<G::ScalarField>>,
+        index: &ProverIndex<G, OpeningProof>,
+        max_memory: usize,
+        hook: Option<&(dyn ProverHook + Sync)>,
+    ) -> Vec<Result<Self>>
+    where
+        G::Map: Sync,
+    {
+        // compute the index-dependent values once, before sharing the index between threads
+        index.verifier_index_digest::<EFqSponge>();
+        index.cs.twiddles();
+        index.cs.precomputations();
+
+        let proofs_per_round = std::cmp::max(
+            max_memory / std::cmp::max(index.proof_memory_estimate(), 1),
+            1,
+        );
+
+        let mut proofs = Vec::with_capacity(witnesses.len());
+        let mut witnesses = witnesses.into_iter();
+        loop {
+            let round: Vec<_> = witnesses.by_ref().take(proofs_per_round).collect();
+            if round.is_empty() {
+                break;
+            }
+
+            proofs.par_extend(round.into_par_iter().map(|instance| {
+                Self::create_internal::<EFqSponge, EFrSponge, _>(
+                    group_map,
+                    instance.witness,
+                    &instance.runtime_tables,
+                    index,
+                    Vec::new(),
+                    None,
+                    &mut rand::rngs::OsRng,
+                    hook.map(|hook| hook as &dyn ProverHook),
+                )
+            }));
+        }
+
+        proofs
+    }
+
+    /// Same as [`ProverProof::create_recursive`], except that `hook` is called
+    /// at each step of the prover (see [`ProverStep`]).
+    /// If the hook returns an error, the prover stops and returns that error.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `hook` does (for example [`ProverError::Cancelled`]),
+    /// or if `create_recursive` would.
+    pub fn create_recursive_with_hook<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+    >(
+        group_map: &G::Map,
+        witness: [Vec<G::ScalarField>; COLUMNS],
+        runtime_tables: &[RuntimeTable<G::ScalarField>],
+        index: &ProverIndex<G, OpeningProof>,
+        prev_challenges: Vec<RecursionChallenge<G>>,
+        blinders: Option<[Option<PolyComm<G::ScalarField>>; COLUMNS]>,
+        hook: &dyn ProverHook,
+    ) -> Result<Self> {
+        Self::create_internal::<EFqSponge, EFrSponge, _>(
+            group_map,
+            witness,
+            runtime_tables,
+            index,
+            prev_challenges,
+            blinders,
+            &mut rand::rngs::OsRng,
+            Some(hook),
+        )
+    }
+
+    /// Same as [`ProverProof::create_recursive`], except that the randomness
+    /// (blinders, zero-knowledge rows, opening proof) is drawn from `rng`,
+    /// and that `hook`, if given, is called at each step of the prover
+    /// (see [`ProverProof::create_recursive_with_hook`]).
+    ///
+    /// # Errors
+    ///
+    /// Will give error if inputs(like `lookup_context.joint_lookup_table_d8`) are None.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `lookup_context.joint_lookup_table_d8` is None.
+    pub fn create_recursive_with_rng<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+    >(
+        group_map: &G::Map,
+        witness: [Vec<G::ScalarField>; COLUMNS],
+        runtime_tables: &[RuntimeTable<G::ScalarField>],
+        index: &ProverIndex<G, OpeningProof>,
+        prev_challenges: Vec<RecursionChallenge<G>>,
+        blinders: Option<[Option<PolyComm<G::ScalarField>>; COLUMNS]>,
+        rng: &mut RNG,
+        hook: Option<&dyn ProverHook>,
+    ) -> Result<Self> {
+        Self::create_internal::<EFqSponge, EFrSponge, _>(
+            group_map,
+            witness,
+            runtime_tables,
+            index,
+            prev_challenges,
+            blinders,
+            rng,
+            hook,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_internal<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+        RNG: RngCore + CryptoRng,
     >(
         group_map: &G::Map,
         mut witness: [Vec<G::ScalarField>; COLUMNS],
         runtime_tables: &[RuntimeTable<G::ScalarField>],
-        index: &ProverIndex<G>,
+        index: &ProverIndex<G, OpeningProof>,
         prev_challenges: Vec<RecursionChallenge<G>>,
         blinders: Option<[Option<PolyComm<G::ScalarField>>; COLUMNS]>,
+        rng: &mut RNG,
+        hook: Option<&dyn ProverHook>,
     ) -> Result<Self> {
+        // report the progress of the prover, and stop if the hook says so
+        let report = |step| hook.map_or(Ok(()), |hook| hook.on_step(step));
+
         // make sure that the SRS is not smaller than the domain size
         let d1_size = index.cs.domain.d1.size();
-        if index.srs.max_degree() < d1_size {
+        if index.srs.max_poly_size() < d1_size {
             return Err(ProverError::SRSTooSmall);
         }
 
         let (_, endo_r) = G::endos();
 
-        // TODO: rng should be passed as arg
-        let rng = &mut rand::rngs::OsRng;
-
         // Verify the circuit satisfiability by the computed witness (baring plookup constraints)
         // Catch mistakes before proof generation.
         if cfg!(debug_assertions) && !index.cs.disable_gates_checks {
@@ -181,22 +396,25 @@ where
             index.verify(&witness, &public).expect("incorrect witness");
         }
 
+        let zero_knowledge = index.cs.zero_knowledge;
+
         //~ 1. Ensure we have room in the witness for the zero-knowledge rows.
         //~    We currently expect the witness not to be of the same length as the domain,
         //~    but instead be of the length of the (smaller) circuit.
-        //~    If we cannot add `ZK_ROWS` rows to the columns of the witness before reaching
+        //~    If we cannot add `zk_rows` rows (`ZK_ROWS`, or 1 for circuits without
+        //~    zero-knowledge nor lookups) to the columns of the witness before reaching
         //~    the size of the domain, abort.
         let length_witness = witness[0].len();
         let length_padding = d1_size
             .checked_sub(length_witness)
             .ok_or(ProverError::NoRoomForZkInWitness)?;
 
-        if length_padding < ZK_ROWS as usize {
+        if length_padding < index.cs.zk_rows as usize {
             return Err(ProverError::NoRoomForZkInWitness);
         }
 
         //~ 1. Pad the witness columns with Zero gates to make them the same length as the domain.
-        //~    Then, randomize the last `ZK_ROWS` of each columns.
+        //~    Then, if the circuit is zero-knowledge, randomize the last `ZK_ROWS` of each columns.
         for w in &mut witness {
             if w.len() != length_witness {
                 return Err(ProverError::WitnessCsInconsistent);
@@ -206,12 +424,28 @@ where
             w.extend(std::iter::repeat(G::ScalarField::zero()).take(length_padding));
 
             // zk-rows
-            for row in w.iter_mut().rev().take(ZK_ROWS as usize) {
-                *row = <G::ScalarField as UniformRand>::rand(rng);
+            if zero_knowledge {
+                for row in w.iter_mut().rev().take(ZK_ROWS as usize) {
+                    *row = <G::ScalarField as UniformRand>::rand(rng);
+                }
             }
         }
 
+        // without zero-knowledge, commitments are not blinded
+        // (which is the same as blinding them with zeros)
+        let mask = |comm: PolyComm<G>, rng: &mut RNG| {
+            if zero_knowledge {
+                index.srs.mask(comm, rng)
+            } else {
+                BlindedCommitment {
+                    blinders: comm.map(|_| G::ScalarField::zero()),
+                    commitment: comm,
+                }
+            }
+        };
+
         //~ 1. Setup the Fq-Sponge.
+        transcript::phase("setup");
         let mut fq_sponge = EFqSponge::new(G::OtherCurve::sponge_params());
 
         //~ 1. Absorb the digest of the VerifierIndex.
@@ -226,12 +460,9 @@ where
         //~ 1. Compute the negated public input polynomial as
         //~    the polynomial that evaluates to $-p_i$ for the first `public_input_size` values of the domain,
         //~    and $0$ for the rest.
+        let twiddles = index.cs.twiddles();
         let public = witness[0][0..index.cs.public].to_vec();
-        let public_poly = -Evaluations::<G::ScalarField, D<G::ScalarField>>::from_vec_and_domain(
-            public.clone(),
-            index.cs.domain.d1,
-        )
-        .interpolate();
+        let public_poly = -twiddles.interpolate(public.clone(), index.cs.domain.d1);
 
         //~ 1. Commit (non-hiding) to the negated public input polynomial.
         let public_comm = index.srs.commit_non_hiding(&public_poly, None);
@@ -251,6 +482,7 @@ where
         //~    Note: unlike the original PLONK protocol,
         //~    the prover also provides evaluations of the public polynomial to help the verifier circuit.
         //~    This is why we need to absorb the commitment to the public polynomial at this point.
+        transcript::phase("public input");
         absorb_commitment(&mut fq_sponge, &public_comm);
 
         //~ 1. Commit to the witness columns by creating `COLUMNS` hidding commitments.
@@ -259,6 +491,8 @@ where
         //~    we can use the `commit_evaluation` optimization.
         let mut w_comm = vec![];
         for col in 0..COLUMNS {
+            report(ProverStep::WitnessCommitment(col))?;
+
             // witness coeff -> witness eval
             let witness_eval =
                 Evaluations::<G::ScalarField, D<G::ScalarField>>::from_vec_and_domain(
@@ -268,9 +502,12 @@ where
 
             let com = match blinders.as_ref().and_then(|b| b[col].as_ref()) {
                 // no blinders: blind the witness
-                None => index
-                    .srs
-                    .commit_evaluations(index.cs.domain.d1, &witness_eval, rng),
+                None => mask(
+                    index
+                        .srs
+                        .commit_evaluations_non_hiding(index.cs.domain.d1, &witness_eval),
+                    rng,
+                ),
                 // blinders: blind the witness with them
                 Some(blinder) => {
                     // TODO: make this a function rather no? mask_with_custom()
@@ -292,6 +529,7 @@ where
             .expect("previous loop is of the correct length");
 
         //~ 1. Absorb the witness commitments with the Fq-Sponge.
+        transcript::phase("witness");
         w_comm
             .iter()
             .for_each(|c| absorb_commitment(&mut fq_sponge, &c.commitment));
@@ -300,18 +538,16 @@ where
         //~    As mentioned above, we commit using the evaluations form rather than the coefficients
         //~    form so we can take advantage of the sparsity of the evaluations (i.e., there are many
         //~    0 entries and entries that have less-than-full-size field elemnts.)
-        let witness_poly: [DensePolynomial<G::ScalarField>; COLUMNS] = array::from_fn(|i| {
-            Evaluations::<G::ScalarField, D<G::ScalarField>>::from_vec_and_domain(
-                witness[i].clone(),
-                index.cs.domain.d1,
-            )
-            .interpolate()
-        });
+        let witness_poly: [DensePolynomial<G::ScalarField>; COLUMNS] =
+            array::from_fn(|i| twiddles.interpolate(witness[i].clone(), index.cs.domain.d1));
 
         let mut lookup_context = LookupContext::default();
 
         //~ 1. If using lookup:
         if let Some(lcs) = &index.cs.lookup_constraint_system {
+            transcript::phase("lookup");
+            report(ProverStep::Lookup)?;
+
             //~~ - if using runtime table:
             if let Some(cfg_runtime_tables) = &lcs.runtime_tables {
                 //~~~ - check that all the provided runtime tables have length and IDs that match the runtime table configuration of the index
@@ -343,23 +579,30 @@ where
                     }
 
                     // zero-knowledge
-                    for e in evals.iter_mut().rev().take(ZK_ROWS as usize) {
-                        *e = <G::ScalarField as UniformRand>::rand(rng);
+                    if zero_knowledge {
+                        for e in evals.iter_mut().rev().take(ZK_ROWS as usize) {
+                            *e = <G::ScalarField as UniformRand>::rand(rng);
+                        }
                     }
 
                     // get coeff and evaluation form
                     let runtime_table_contribution =
-                        Evaluations::from_vec_and_domain(evals, index.cs.domain.d1).interpolate();
+                        twiddles.interpolate(evals, index.cs.domain.d1);
 
-                    let runtime_table_contribution_d8 =
-                        runtime_table_contribution.evaluate_over_domain_by_ref(index.cs.domain.d8);
+                    let runtime_table_contribution_d8 = twiddles
+                        .evaluate_over_domain(&runtime_table_contribution, index.cs.domain.d8);
 
                     (runtime_table_contribution, runtime_table_contribution_d8)
                 };
 
                 // commit the runtime polynomial
                 // (and save it to the proof)
-                let runtime_table_comm = index.srs.commit(&runtime_table_contribution, None, rng);
+                let runtime_table_comm = mask(
+                    index
+                        .srs
+                        .commit_non_hiding(&runtime_table_contribution, None),
+                    rng,
+                );
 
                 // absorb the commitment
                 absorb_commitment(&mut fq_sponge, &runtime_table_comm.commitment);
@@ -462,7 +705,7 @@ where
             };
 
             // TODO: This interpolation is avoidable.
-            let joint_lookup_table = joint_lookup_table_d8.interpolate_by_ref();
+            let joint_lookup_table = twiddles.interpolate_by_ref(&joint_lookup_table_d8);
 
             //~~ - Compute the sorted evaluations.
             // TODO: Once we switch to committing using lagrange commitments,
@@ -480,17 +723,30 @@ where
             )?;
 
             //~~ - Randomize the last `EVALS` rows in each of the sorted polynomials
-            //~~   in order to add zero-knowledge to the protocol.
+            //~~   in order to add zero-knowledge to the protocol
+            //~~   (without zero-knowledge, they are set to zero).
             let sorted: Vec<_> = sorted
                 .into_iter()
-                .map(|chunk| lookup::constraints::zk_patch(chunk, index.cs.domain.d1, rng))
+                .map(|chunk| {
+                    if zero_knowledge {
+                        lookup::constraints::zk_patch(chunk, index.cs.domain.d1, rng)
+                    } else {
+                        lookup::constraints::zero_patch(chunk, index.cs.domain.d1)
+                    }
+                })
                 .collect();
 
             //~~ - Commit each of the sorted polynomials.
-            let sorted_comms: Vec<_> = sorted
-                .iter()
-                .map(|v| index.srs.commit_evaluations(index.cs.domain.d1, v, rng))
-                .collect();
+            let mut sorted_comms = Vec::with_capacity(sorted.len());
+            for (i, v) in sorted.iter().enumerate() {
+                report(ProverStep::SortedCommitment(i))?;
+                sorted_comms.push(mask(
+                    index
+                        .srs
+                        .commit_evaluations_non_hiding(index.cs.domain.d1, v),
+                    rng,
+                ));
+            }
 
             //~~ - Absorb each commitments to the sorted polynomials.
             sorted_comms
@@ -499,10 +755,13 @@ where
 
             // precompute different forms of the sorted polynomials for later
             // TODO: We can avoid storing these coefficients.
-            let sorted_coeffs: Vec<_> = sorted.iter().map(|e| e.clone().interpolate()).collect();
+            let sorted_coeffs: Vec<_> = sorted
+                .iter()
+                .map(|e| twiddles.interpolate_by_ref(e))
+                .collect();
             let sorted8: Vec<_> = sorted_coeffs
                 .iter()
-                .map(|v| v.evaluate_over_domain_by_ref(index.cs.domain.d8))
+                .map(|v| twiddles.evaluate_over_domain(v, index.cs.domain.d8))
                 .collect();
 
             lookup_context.joint_combiner = Some(joint_combiner);
@@ -515,6 +774,8 @@ where
         }
 
         //~ 1. Sample $\beta$ with the Fq-Sponge.
+        transcript::phase("permutation");
+        report(ProverStep::Permutation)?;
         let beta = fq_sponge.challenge();
 
         //~ 1. Sample $\gamma$ with the Fq-Sponge.
@@ -536,23 +797,27 @@ where
                 beta,
                 gamma,
                 lookup_context.sorted.as_ref().unwrap(),
+                zero_knowledge,
                 rng,
                 &lcs.configuration.lookup_info,
             )?;
 
             //~~ - Commit to the aggregation polynomial.
-            let aggreg_comm = index
-                .srs
-                .commit_evaluations(index.cs.domain.d1, &aggreg, rng);
+            let aggreg_comm = mask(
+                index
+                    .srs
+                    .commit_evaluations_non_hiding(index.cs.domain.d1, &aggreg),
+                rng,
+            );
 
             //~~ - Absorb the commitment to the aggregation polynomial with the Fq-Sponge.
             absorb_commitment(&mut fq_sponge, &aggreg_comm.commitment);
 
             // precompute different forms of the aggregation polynomial for later
-            let aggreg_coeffs = aggreg.interpolate();
+            let aggreg_coeffs = twiddles.interpolate(aggreg.evals, index.cs.domain.d1);
             // TODO: There's probably a clever way to expand the domain without
             // interpolating
-            let aggreg8 = aggreg_coeffs.evaluate_over_domain_by_ref(index.cs.domain.d8);
+            let aggreg8 = twiddles.evaluate_over_domain(&aggreg_coeffs, index.cs.domain.d8);
 
             lookup_context.aggreg_comm = Some(aggreg_comm);
             lookup_context.aggreg_coeffs = Some(aggreg_coeffs);
@@ -563,7 +828,7 @@ where
         let z_poly = index.perm_aggreg(&witness, &beta, &gamma, rng)?;
 
         //~ 1. Commit (hidding) to the permutation aggregation polynomial $z$.
-        let z_comm = index.srs.commit(&z_poly, None, rng);
+        let z_comm = mask(index.srs.commit_non_hiding(&z_poly, None), rng);
 
         //~ 1. Absorb the permutation aggregation polynomial $z$ with the Fq-Sponge.
         absorb_commitment(&mut fq_sponge, &z_comm.commitment);
@@ -686,6 +951,7 @@ where
 
         let quotient_poly = {
             // generic
+            report(ProverStep::Quotient(ArgumentType::Gate(GateType::Generic)))?;
             let mut t4 = {
                 let generic_constraint = generic::Generic::combined_constraints(&all_alphas);
                 let generic4 = generic_constraint.evaluations(&env);
@@ -700,6 +966,7 @@ where
                 generic4
             };
             // permutation
+            report(ProverStep::Quotient(ArgumentType::Permutation))?;
             let (mut t8, bnd) = {
                 let alphas =
                     all_alphas.get_alphas(ArgumentType::Permutation, permutation::CONSTRAINTS);
@@ -761,6 +1028,7 @@ where
                 .into_iter()
                 .filter_map(|(gate, is_enabled)| if is_enabled { Some(gate) } else { None })
                 {
+                    report(ProverStep::Quotient(gate.argument_type()))?;
                     let constraint = gate.combined_constraints(&all_alphas);
                     let eval = constraint.evaluations(&env);
                     if eval.domain().size == t4.domain().size {
@@ -777,6 +1045,7 @@ where
             // lookup
             {
                 if let Some(lcs) = index.cs.lookup_constraint_system.as_ref() {
+                    report(ProverStep::Quotient(ArgumentType::Lookup))?;
                     let constraints = lookup::constraints::constraints(&lcs.configuration, false);
                     let constraints_len = u32::try_from(constraints.len())
                         .expect("not expecting a large amount of constraints");
@@ -807,7 +1076,8 @@ where
             }
 
             // public polynomial
-            let mut f = t4.interpolate() + t8.interpolate();
+            let mut f = twiddles.interpolate(t4.evals, index.cs.domain.d4)
+                + twiddles.interpolate(t8.evals, index.cs.domain.d8);
             f += &public_poly;
 
             // divide contributions with vanishing polynomial
@@ -827,25 +1097,52 @@ where
         //~ 1. commit (hiding) to the quotient polynomial $t$
         //~    TODO: specify the dummies
         let t_comm = {
-            let mut t_comm = index.srs.commit(&quotient_poly, None, rng);
+            // commit to each chunk separately, to report the progress between them
+            let mut unshifted = vec![];
+            for (i, chunk) in quotient_poly
+                .coeffs
+                .chunks(index.srs.max_poly_size())
+                .enumerate()
+            {
+                report(ProverStep::QuotientCommitment(i))?;
+                let chunk = DensePolynomial::from_coefficients_slice(chunk);
+                unshifted.extend(index.srs.commit_non_hiding(&chunk, None).unshifted);
+            }
+            if unshifted.is_empty() {
+                // the commitment to the zero polynomial
+                unshifted.push(G::zero());
+            }
+            let mut t_comm = mask(
+                PolyComm {
+                    unshifted,
+                    shifted: None,
+                },
+                rng,
+            );
 
             let expected_t_size = PERMUTS;
             let dummies = expected_t_size - t_comm.commitment.unshifted.len();
             // Add `dummies` many hiding commitments to the 0 polynomial, since if the
             // number of commitments in `t_comm` is less than the max size, it means that
             // the higher degree coefficients of `t` are 0.
+            // Without zero-knowledge, these are commitments to zero (points at infinity).
             for _ in 0..dummies {
-                let w = <G::ScalarField as UniformRand>::rand(rng);
+                let w = if zero_knowledge {
+                    <G::ScalarField as UniformRand>::rand(rng)
+                } else {
+                    G::ScalarField::zero()
+                };
                 t_comm
                     .commitment
                     .unshifted
-                    .push(index.srs.h.mul(w).into_affine());
+                    .push(index.srs.blinding_commitment().mul(w).into_affine());
                 t_comm.blinders.unshifted.push(w);
             }
             t_comm
         };
 
         //~ 1. Absorb the the commitment of the quotient polynomial with the Fq-Sponge.
+        transcript::phase("quotient");
         absorb_commitment(&mut fq_sponge, &t_comm.commitment);
 
         //~ 1. Sample $\zeta'$ with the Fq-Sponge.
@@ -857,6 +1154,8 @@ where
         let omega = index.cs.domain.d1.group_gen;
         let zeta_omega = zeta * omega;
 
+        report(ProverStep::Evaluations)?;
+
         //~ 1. If lookup is used, evaluate the following polynomials at $\zeta$ and $\zeta \omega$:
         if index.cs.lookup_constraint_system.is_some() {
             //~~ - the aggregation polynomial
@@ -1038,7 +1337,12 @@ where
         //~ 1. Evaluate the ft polynomial at $\zeta\omega$ only.
         let ft_eval1 = ft.evaluate(&zeta_omega);
 
+        // the evaluation at $\zeta$ is recomputed by the verifier,
+        // the proof only includes it on demand, so that the verifier can report a mismatch
+        let ft_eval0 = index.include_ft_eval0.then(|| ft.evaluate(&zeta));
+
         //~ 1. Setup the Fr-Sponge
+        transcript::phase("evaluations");
         let fq_sponge_before_evaluations = fq_sponge.clone();
         let mut fr_sponge = EFrSponge::new(G::sponge_params());
 
@@ -1221,7 +1525,10 @@ where
         }
 
         //~ 1. Create an aggregated evaluation proof for all of these polynomials at $\zeta$ and $\zeta\omega$ using $u$ and $v$.
-        let proof = index.srs.open(
+        transcript::phase("opening");
+        report(ProverStep::Opening)?;
+        let proof = OpeningProof::open_with_progress(
+            &*index.srs,
             group_map,
             &polynomials,
             &[zeta, zeta_omega],
@@ -1229,7 +1536,8 @@ where
             u,
             fq_sponge_before_evaluations,
             rng,
-        );
+            &mut |round| report(ProverStep::OpeningRound(round)),
+        )?;
 
         let lookup = lookup_context
             .aggreg_comm
@@ -1252,6 +1560,10 @@ where
             ft_eval1,
             public,
             prev_challenges,
+            verifier_index_digest: index
+                .include_verifier_index_digest
+                .then_some(verifier_index_digest),
+            ft_eval0,
         })
     }
 }
@@ -1498,6 +1810,8 @@ pub mod caml {
                     .into_iter()
                     .map(Into::into)
                     .collect(),
+                verifier_index_digest: None,
+                ft_eval0: None,
             }
         }
     }
@@ -0,0 +1,57 @@
+//! This module implements [`ProverHook`], which lets callers follow the progress of the prover
+//! (see [`ProverProof::create_recursive_with_hook`](crate::proof::ProverProof::create_recursive_with_hook))
+//! and cancel it.
+//!
+//! The hook is called every time the prover reaches a new [`ProverStep`].
+//! If it returns an error (typically [`ProverError::Cancelled`]),
+//! the prover stops there and returns that error.
+//! Note that a step is never interrupted: the hook is only called between
+//! multi-scalar multiplications (the commitments to the columns of the witness,
+//! to the sorted lookup polynomials and to the chunks of the quotient polynomial,
+//! and the rounds of the opening proof) and between the constraints of the quotient polynomial.
+
+use crate::{circuits::argument::ArgumentType, error::ProverError};
+
+/// A step of the prover, in the order in which they are reached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProverStep {
+    /// Committing to the witness column of the given index (out of `COLUMNS`)
+    WitnessCommitment(usize),
+    /// Computing the lookup polynomials (only if lookups are used)
+    Lookup,
+    /// Committing to the sorted lookup polynomial of the given index (only if lookups are used)
+    SortedCommitment(usize),
+    /// Computing and committing to the permutation aggregation polynomial
+    Permutation,
+    /// Computing the contribution of an argument to the quotient polynomial
+    Quotient(ArgumentType),
+    /// Committing to the chunk of the given index of the quotient polynomial
+    QuotientCommitment(usize),
+    /// Evaluating the polynomials at $\zeta$ and $\zeta\omega$
+    Evaluations,
+    /// Creating the opening proof
+    Opening,
+    /// Computing the round of the given index of the opening proof
+    /// (only for polynomial commitment schemes that open in several rounds)
+    OpeningRound(usize),
+}
+
+/// A hook that is called by the prover at each [`ProverStep`].
+pub trait ProverHook {
+    /// Reports that the prover reached `step`.
+    /// Returning an error makes the prover stop and return that error.
+    ///
+    /// # Errors
+    ///
+    /// Should return [`ProverError::Cancelled`] to cancel the proof.
+    fn on_step(&self, step: ProverStep) -> Result<(), ProverError>;
+}
+
+impl<F> ProverHook for F
+where
+    F: Fn(ProverStep) -> Result<(), ProverError>,
+{
+    fn on_step(&self, step: ProverStep) -> Result<(), ProverError> {
+        self(step)
+    }
+}
@@ -5,23 +5,30 @@ use crate::{
     circuits::{
         constraints::{ColumnEvaluations, ConstraintSystem},
         expr::{Linearization, PolishToken},
+        wires::COLUMNS,
     },
     curve::KimchiCurve,
     linearization::expr_linearization,
     verifier_index::VerifierIndex,
 };
 use ark_poly::EvaluationDomain;
-use commitment_dlog::srs::SRS;
+use commitment_dlog::{
+    evaluation_proof::OpeningProof as DlogOpeningProof,
+    pcs::{CommitmentKey, OpenProof},
+};
 use mina_poseidon::FqSponge;
+use once_cell::sync::OnceCell;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_with::serde_as;
 use std::sync::Arc;
 
-/// The index used by the prover
+/// The index used by the prover.
+/// It is generic over the polynomial commitment scheme, given by its opening proof
+/// (see [`OpenProof`]), which defaults to the inner-product argument.
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug)]
 //~spec:startcode
-pub struct ProverIndex<G: KimchiCurve> {
+pub struct ProverIndex<G: KimchiCurve, OpeningProof: OpenProof<G> = DlogOpeningProof<G>> {
     /// constraints system polynomials
     #[serde(bound = "ConstraintSystem<G::ScalarField>: Serialize + DeserializeOwned")]
     pub cs: ConstraintSystem<G::ScalarField>,
@@ -36,7 +43,7 @@ pub struct ProverIndex<G: KimchiCurve> {
 
     /// polynomial commitment keys
     #[serde(skip)]
-    pub srs: Arc<SRS<G>>,
+    pub srs: Arc<OpeningProof::SRS>,
 
     /// maximal size of polynomial section
     pub max_poly_size: usize,
@@ -46,15 +53,36 @@ pub struct ProverIndex<G: KimchiCurve> {
 
     /// The verifier index corresponding to this prover index
     #[serde(skip)]
-    pub verifier_index: Option<VerifierIndex<G>>,
+    pub verifier_index: Option<VerifierIndex<G, OpeningProof>>,
 
     /// The verifier index digest corresponding to this prover index
     #[serde_as(as = "Option<o1_utils::serialization::SerdeAs>")]
     pub verifier_index_digest: Option<G::BaseField>,
+
+    /// Whether the proofs created with this index include the verifier index digest.
+    /// The digest is absorbed in the transcript either way.
+    #[serde(default = "include_verifier_index_digest_default")]
+    pub include_verifier_index_digest: bool,
+
+    /// Whether the proofs created with this index include the evaluation of $ft$ at $\zeta$,
+    /// which the verifier only uses to report proofs that do not satisfy the constraints as such.
+    #[serde(default)]
+    pub include_ft_eval0: bool,
+
+    /// The verifier index digest computed by [`ProverIndex::verifier_index_digest`],
+    /// when it is not given in `verifier_index_digest`
+    #[serde(skip)]
+    computed_verifier_index_digest: OnceCell<G::BaseField>,
 }
 //~spec:endcode
 
-impl<G: KimchiCurve> ProverIndex<G> {
+/// By default, proofs include the verifier index digest
+/// (also for prover indexes serialized before this setting existed)
+fn include_verifier_index_digest_default() -> bool {
+    true
+}
+
+impl<G: KimchiCurve, OpeningProof: OpenProof<G>> ProverIndex<G, OpeningProof> {
     /// this function compiles the index from constraints
     ///
     /// # Panics
@@ -63,9 +91,9 @@ impl<G: KimchiCurve> ProverIndex<G> {
     pub fn create(
         mut cs: ConstraintSystem<G::ScalarField>,
         endo_q: G::ScalarField,
-        srs: Arc<SRS<G>>,
+        srs: Arc<OpeningProof::SRS>,
     ) -> Self {
-        let max_poly_size = srs.g.len();
+        let max_poly_size = srs.max_poly_size();
         if cs.public > 0 {
             assert!(
                 max_poly_size >= cs.domain.d1.size(),
@@ -90,6 +118,9 @@ impl<G: KimchiCurve> ProverIndex<G> {
             column_evaluations,
             verifier_index: None,
             verifier_index_digest: None,
+            include_verifier_index_digest: true,
+            include_ft_eval0: false,
+            computed_verifier_index_digest: OnceCell::new(),
         }
     }
 
@@ -114,6 +145,7 @@ impl<G: KimchiCurve> ProverIndex<G> {
     }
 
     /// Retrieve or compute the digest for the corresponding verifier index.
+    /// A computed digest is cached, so that it is only computed once per index.
     pub fn verifier_index_digest<EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>>(
         &self,
     ) -> G::BaseField {
@@ -121,13 +153,41 @@ impl<G: KimchiCurve> ProverIndex<G> {
             return verifier_index_digest;
         }
 
-        match &self.verifier_index {
-            None => {
-                let verifier_index = self.verifier_index();
-                verifier_index.digest::<EFqSponge>()
-            }
-            Some(verifier_index) => verifier_index.digest::<EFqSponge>(),
+        *self
+            .computed_verifier_index_digest
+            .get_or_init(|| match &self.verifier_index {
+                None => {
+                    let verifier_index = self.verifier_index();
+                    verifier_index.digest::<EFqSponge>()
+                }
+                Some(verifier_index) => verifier_index.digest::<EFqSponge>(),
+            })
+    }
+
+    /// A rough estimate (in bytes) of the memory used by the prover to create a single proof
+    /// with this index, on top of the index itself.
+    /// It accounts for the witness, permutation and lookup polynomials
+    /// (in coefficient form and in evaluation form over `d1`, `d4` and `d8`),
+    /// and for the evaluations of the quotient polynomial.
+    pub fn proof_memory_estimate(&self) -> usize {
+        let n = self.cs.domain.d1.size();
+
+        // the witness columns and the permutation aggregation polynomial
+        let mut num_polys = COLUMNS + 1;
+
+        // the sorted polynomials, the lookup aggregation polynomial and the runtime table
+        if let Some(lcs) = &self.cs.lookup_constraint_system {
+            num_polys += lcs.configuration.lookup_info.max_per_row + 3;
         }
+
+        // each polynomial is kept in coefficient form, over d1,
+        // and over d4 and d8 (both at x and at x * omega)
+        let polys = num_polys * (1 + 1 + 2 * 4 + 2 * 8) * n;
+
+        // the quotient polynomial and the intermediate results of its computation over d8
+        let quotient = 16 * 8 * n;
+
+        (polys + quotient) * std::mem::size_of::<G::ScalarField>()
     }
 }
 
@@ -138,7 +198,7 @@ pub mod testing {
         lookup::{runtime_tables::RuntimeTableCfg, tables::LookupTable},
     };
     use ark_ff::{PrimeField, SquareRootField};
-    use commitment_dlog::srs::endos;
+    use commitment_dlog::srs::{endos, SRS};
 
     /// Create new index for lookups.
     ///
@@ -0,0 +1,83 @@
+//! This module implements [`DeterministicRng`], the source of randomness of the deterministic proving mode
+//! (see [`ProverProof::create_deterministic`](crate::proof::ProverProof::create_deterministic)).
+//!
+//! The 32-byte seed given by the user is hashed with Blake2b-512 together with the domain separator
+//! `"kimchi-deterministic-prover-v1"`, and the first 32 bytes of the digest key a ChaCha20 stream.
+//! The prover then draws all of its randomness from that single stream, in this order:
+//!
+//! 1. the `ZK_ROWS` random rows of each witness column (column by column, last row first),
+//! 1. one blinder per chunk of each witness commitment,
+//! 1. if runtime tables are used, the `ZK_ROWS` random rows of the runtime table and its commitment blinder,
+//! 1. if lookups are used, the `ZK_ROWS` random rows of each sorted polynomial,
+//!    then one blinder per chunk of each sorted commitment,
+//! 1. if lookups are used, the `ZK_ROWS` random rows of the lookup aggregation polynomial
+//!    and its commitment blinders,
+//! 1. the two random evaluations of the permutation aggregation polynomial and its commitment blinders,
+//! 1. the blinders of the quotient commitment, then one blinder per dummy chunk,
+//! 1. for the opening proof, the blinders `(l, r)` of each round, then `d` and `r_delta`.
+//!
+//! Field elements are sampled with [`UniformRand`](ark_ff::UniformRand).
+//! Changing this schedule changes the proofs produced for a given seed,
+//! and requires bumping the version in the domain separator.
+
+use blake2::{Blake2b512, Digest};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// The domain separator used to derive the ChaCha20 key from the user seed
+pub const DETERMINISTIC_PROVER_DOMAIN: &[u8] = b"kimchi-deterministic-prover-v1";
+
+/// A seedable random number generator that is reproducible across runs and machines.
+#[derive(Clone, Debug)]
+pub struct DeterministicRng(ChaCha20Rng);
+
+impl DeterministicRng {
+    /// Derives the random stream of the prover from a user-provided seed.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let mut hasher = Blake2b512::new();
+        hasher.update(DETERMINISTIC_PROVER_DOMAIN);
+        hasher.update(seed);
+        let digest = hasher.finalize();
+
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&digest[..32]);
+        Self(ChaCha20Rng::from_seed(key))
+    }
+}
+
+impl RngCore for DeterministicRng {
+    fn next_u32(&mut self) -> u32 {
+        self.0.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.0.try_fill_bytes(dest)
+    }
+}
+
+impl CryptoRng for DeterministicRng {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_stream() {
+        let mut rng1 = DeterministicRng::from_seed([1u8; 32]);
+        let mut rng2 = DeterministicRng::from_seed([1u8; 32]);
+        let mut rng3 = DeterministicRng::from_seed([2u8; 32]);
+
+        let x1 = rng1.next_u64();
+        assert_eq!(x1, rng2.next_u64());
+        assert_ne!(x1, rng3.next_u64());
+    }
+}
@@ -0,0 +1,422 @@
+//! This module computes a report on the size of a circuit and the cost of proving it:
+//! the gates it is made of, how much of the domain it fills,
+//! the lookup tables it uses, and estimates of the proof size and of the prover work.
+//!
+//! The report is available as a [`CircuitStats`] struct,
+//! from [`ConstraintSystem::stats`] or [`ProverIndex::stats`],
+//! and its [`Display`](fmt::Display) implementation formats it as a table.
+
+use crate::{
+    circuits::{
+        constraints::{ConstraintSystem, FeatureFlags},
+        gate::{CircuitGate, CurrOrNext, GateType},
+        lookup::{
+            lookups::{LookupPattern, LookupTableID},
+            runtime_tables::RuntimeTableSpec,
+        },
+        polynomials::permutation::ZK_ROWS,
+        wires::{COLUMNS, PERMUTS},
+    },
+    curve::KimchiCurve,
+    prover_index::ProverIndex,
+};
+use ark_ec::AffineCurve;
+use ark_ff::{PrimeField, Zero};
+use ark_poly::EvaluationDomain;
+use ark_serialize::CanonicalSerialize;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt};
+
+/// Statistics on a circuit, see the [module documentation](self).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CircuitStats {
+    /// The number of public inputs.
+    pub public_inputs: usize,
+    /// The number of gates of each type, padding excluded.
+    pub gates: BTreeMap<GateType, usize>,
+    /// The number of rows of the circuit, padding excluded.
+    pub rows: usize,
+    /// The size of the evaluation domain.
+    pub domain_size: usize,
+    /// The number of zero gates added to reach the domain size.
+    pub padding: usize,
+    /// Whether proofs for this circuit are zero-knowledge.
+    pub zero_knowledge: bool,
+    /// The number of rows at the end of the domain reserved for zero-knowledge.
+    pub zk_rows: u64,
+    /// The optional features used by the circuit.
+    pub feature_flags: FeatureFlags,
+    /// Statistics on the lookup argument, if the circuit uses lookups.
+    pub lookup: Option<LookupStats>,
+    /// The estimated size of a proof.
+    pub proof_size: ProofSize,
+    /// The estimated work of the prover.
+    pub prover_cost: ProverCost,
+}
+
+/// Statistics on the lookup argument of a circuit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupStats {
+    /// The fixed lookup tables, with the number of lookups into each of them.
+    pub tables: Vec<LookupTableStats>,
+    /// The number of lookups into a table whose ID is read from the witness
+    /// (see [`LookupTableID::WitnessColumn`]).
+    pub witness_table_lookups: usize,
+    /// The runtime tables that were configured at setup.
+    pub runtime_tables: Vec<RuntimeTableSpec>,
+    /// The maximum number of lookups performed on a single row.
+    pub max_per_row: usize,
+    /// The maximum number of values combined in a single lookup.
+    pub max_joint_size: u32,
+    /// The number of table entries used by all tables, runtime tables included.
+    pub table_entries: usize,
+    /// The maximum number of table entries the domain can hold.
+    pub table_capacity: usize,
+}
+
+/// A fixed lookup table and how often it is used.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LookupTableStats {
+    /// The table ID.
+    pub id: i32,
+    /// The number of entries of the table.
+    pub len: usize,
+    /// The number of lookups in the circuit with this table ID.
+    pub lookups: usize,
+}
+
+/// The estimated size of a proof.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofSize {
+    /// The number of curve points (commitments and opening proof elements).
+    pub group_elements: usize,
+    /// The number of field elements (evaluations, public inputs, challenges and digest).
+    pub field_elements: usize,
+    /// The number of bytes of the compressed elements,
+    /// only known when the curve is known (see [`ProverIndex::stats`]).
+    pub bytes: Option<usize>,
+}
+
+/// The estimated work of the prover, in number of multi-scalar multiplications and FFTs.
+///
+/// This follows the structure of the prover and excludes the work done at setup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverCost {
+    /// The number of MSMs over the full SRS (one per polynomial chunk committed to).
+    pub msms: usize,
+    /// The number of rounds of the opening proof, each requiring two MSMs of halving size.
+    pub opening_rounds: usize,
+    /// The number of FFTs (forward or inverse) over the domain of size `n`.
+    pub ffts_d1: usize,
+    /// The number of FFTs (forward or inverse) over the domain of size `4n`.
+    pub ffts_d4: usize,
+    /// The number of FFTs (forward or inverse) over the domain of size `8n`.
+    pub ffts_d8: usize,
+}
+
+impl<F: PrimeField> ConstraintSystem<F> {
+    /// Returns the statistics of this circuit,
+    /// assuming an SRS of the size of the domain,
+    /// and proofs that include the verifier index digest but not `ft_eval0`
+    /// (the default of a [`ProverIndex`]).
+    pub fn stats(&self) -> CircuitStats {
+        let domain_size = self.domain.d1.size();
+        CircuitStats::create(self, domain_size, domain_size, true, false)
+    }
+}
+
+impl<G: KimchiCurve> ProverIndex<G> {
+    /// Returns the statistics of the circuit of this index, for its SRS,
+    /// and for the proofs created with its settings.
+    pub fn stats(&self) -> CircuitStats {
+        let mut stats = CircuitStats::create(
+            &self.cs,
+            self.max_poly_size,
+            self.srs.g.len(),
+            self.include_verifier_index_digest,
+            self.include_ft_eval0,
+        );
+
+        let point_size = G::prime_subgroup_generator().serialized_size();
+        let scalar_size = G::ScalarField::zero().serialized_size();
+        let base_size = G::BaseField::zero().serialized_size();
+
+        // the verifier index digest is the only element of the base field in a proof
+        let base_elements = usize::from(self.include_verifier_index_digest);
+        stats.proof_size.bytes = Some(
+            stats.proof_size.group_elements * point_size
+                + (stats.proof_size.field_elements - base_elements) * scalar_size
+                + base_elements * base_size,
+        );
+        stats
+    }
+}
+
+/// Returns `true` for the zero gates added at the end of a circuit to reach the domain size,
+/// which are not wired to any other cell.
+fn is_padding<F: PrimeField>(row: usize, gate: &CircuitGate<F>) -> bool {
+    gate.typ == GateType::Zero
+        && gate.coeffs.is_empty()
+        && gate
+            .wires
+            .iter()
+            .enumerate()
+            .all(|(col, wire)| wire.row == row && wire.col == col)
+}
+
+/// Returns the number of rows of a circuit, without the padding at its end.
+///
+/// The padding added by the constraint system cannot be told apart
+/// from zero gates at the end of the circuit that are not wired.
+pub(crate) fn unpadded_rows<F: PrimeField>(gates: &[CircuitGate<F>]) -> usize {
+    gates
+        .iter()
+        .enumerate()
+        .rposition(|(row, gate)| !is_padding(row, gate))
+        .map_or(0, |row| row + 1)
+}
+
+/// Returns the names of the optional features enabled by the flags.
+pub(crate) fn enabled_features(flags: &FeatureFlags) -> Vec<&'static str> {
+    let lookup = &flags.lookup_features;
+    [
+        (flags.chacha, "chacha"),
+        (flags.range_check0, "range_check0"),
+        (flags.range_check1, "range_check1"),
+        (flags.foreign_field_add, "foreign_field_add"),
+        (flags.foreign_field_mul, "foreign_field_mul"),
+        (flags.xor, "xor"),
+        (flags.rot, "rot"),
+        (lookup.patterns.xor, "lookup_pattern_xor"),
+        (lookup.patterns.chacha_final, "lookup_pattern_chacha_final"),
+        (lookup.patterns.lookup, "lookup_pattern_lookup"),
+        (lookup.patterns.range_check, "lookup_pattern_range_check"),
+        (
+            lookup.patterns.foreign_field_mul,
+            "lookup_pattern_foreign_field_mul",
+        ),
+        (lookup.joint_lookup_used, "joint_lookups"),
+        (lookup.uses_runtime_tables, "runtime_tables"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect()
+}
+
+impl CircuitStats {
+    fn create<F: PrimeField>(
+        cs: &ConstraintSystem<F>,
+        max_poly_size: usize,
+        srs_size: usize,
+        include_verifier_index_digest: bool,
+        include_ft_eval0: bool,
+    ) -> Self {
+        let domain_size = cs.domain.d1.size();
+
+        let rows = unpadded_rows(&cs.gates);
+
+        let mut gates = BTreeMap::new();
+        for gate in &cs.gates[..rows] {
+            *gates.entry(gate.typ).or_default() += 1;
+        }
+
+        let lookup = cs.lookup_constraint_system.as_ref().map(|lcs| {
+            let mut lookups = BTreeMap::<i32, usize>::new();
+            let mut witness_table_lookups = 0;
+            for gate in &cs.gates {
+                for curr_or_next in [CurrOrNext::Curr, CurrOrNext::Next] {
+                    let pattern = match LookupPattern::from_gate(gate.typ, curr_or_next) {
+                        Some(pattern) => pattern,
+                        None => continue,
+                    };
+                    for lookup in pattern.lookups::<F>() {
+                        match lookup.table_id {
+                            LookupTableID::Constant(id) => *lookups.entry(id).or_default() += 1,
+                            LookupTableID::WitnessColumn(_) => witness_table_lookups += 1,
+                        }
+                    }
+                }
+            }
+
+            let tables = lcs
+                .fixed_tables
+                .iter()
+                .map(|table| LookupTableStats {
+                    id: table.id,
+                    len: table.len,
+                    lookups: lookups.get(&table.id).copied().unwrap_or(0),
+                })
+                .collect();
+            let runtime_tables = lcs.runtime_tables.clone().unwrap_or_default();
+
+            let table_entries = lcs
+                .fixed_tables
+                .iter()
+                .map(|table| table.len)
+                .sum::<usize>()
+                + runtime_tables.iter().map(|table| table.len).sum::<usize>();
+
+            let lookup_info = &lcs.configuration.lookup_info;
+            LookupStats {
+                tables,
+                witness_table_lookups,
+                runtime_tables,
+                max_per_row: lookup_info.max_per_row,
+                max_joint_size: lookup_info.max_joint_size,
+                table_entries,
+                // see `LookupConstraintSystem::create`
+                table_capacity: domain_size - ZK_ROWS as usize - 2,
+            }
+        });
+
+        let (proof_size, prover_cost) =
+            Self::estimate_costs(cs, lookup.as_ref(), max_poly_size, srs_size);
+
+        CircuitStats {
+            public_inputs: cs.public,
+            gates,
+            rows,
+            domain_size,
+            padding: domain_size - rows,
+            zero_knowledge: cs.zero_knowledge,
+            zk_rows: cs.zk_rows,
+            feature_flags: cs.feature_flags,
+            lookup,
+            proof_size,
+            prover_cost,
+        }
+    }
+
+    fn estimate_costs<F: PrimeField>(
+        cs: &ConstraintSystem<F>,
+        lookup: Option<&LookupStats>,
+        max_poly_size: usize,
+        srs_size: usize,
+    ) -> (ProofSize, ProverCost) {
+        let chunks = (cs.domain.d1.size() + max_poly_size - 1) / max_poly_size;
+        let opening_rounds = srs_size.trailing_zeros() as usize;
+
+        // the polynomials committed to by the prover, besides the public input and the quotient
+        let mut committed = COLUMNS + 1;
+        // the polynomials evaluated at zeta and zeta * omega
+        let mut evaluated = COLUMNS + 1 + (PERMUTS - 1) + COLUMNS + 2;
+
+        let mut ffts_d1 = 1 + COLUMNS + 1;
+        let mut ffts_d4 = 1 + 1;
+        let mut ffts_d8 = COLUMNS + 1 + 1;
+
+        if let Some(lookup) = lookup {
+            let sorted = lookup.max_per_row + 1;
+            let runtime = usize::from(!lookup.runtime_tables.is_empty());
+            committed += sorted + 1 + runtime;
+            evaluated += sorted + 2 + runtime;
+            // the sorted, aggregation and runtime polynomials are interpolated
+            // and evaluated over d8, and the joint table is interpolated from d8
+            ffts_d1 += sorted + 1 + runtime;
+            ffts_d8 += sorted + 1 + runtime + 1;
+        }
+
+        let recursion_group_elements = cs.prev_challenges * chunks;
+        let recursion_field_elements = cs.prev_challenges * opening_rounds;
+
+        let proof_size = ProofSize {
+            group_elements: (committed + PERMUTS) * chunks
+                + recursion_group_elements
+                + 2 * opening_rounds
+                + 2,
+            // the evaluations, `ft_eval1`, the public input, `z1` and `z2`,
+            // and the optional digest and `ft_eval0`
+            field_elements: 2 * evaluated * chunks
+                + 1
+                + cs.public
+                + recursion_field_elements
+                + 2
+                + usize::from(include_verifier_index_digest)
+                + usize::from(include_ft_eval0),
+            bytes: None,
+        };
+
+        let prover_cost = ProverCost {
+            msms: (1 + committed + PERMUTS) * chunks,
+            opening_rounds,
+            ffts_d1,
+            ffts_d4,
+            ffts_d8,
+        };
+
+        (proof_size, prover_cost)
+    }
+
+    /// Returns the names of the optional features used by the circuit.
+    pub fn features(&self) -> Vec<&'static str> {
+        enabled_features(&self.feature_flags)
+    }
+}
+
+/// Writes a line of the report: a label and a right-aligned value.
+fn line(f: &mut fmt::Formatter<'_>, label: &str, value: impl fmt::Display) -> fmt::Result {
+    writeln!(f, "{label:<32}{value:>12}")
+}
+
+impl fmt::Display for CircuitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        line(f, "public inputs", self.public_inputs)?;
+        line(f, "rows", self.rows)?;
+        line(f, "padding", self.padding)?;
+        line(f, "domain size", self.domain_size)?;
+        line(f, "zero-knowledge", self.zero_knowledge)?;
+        line(f, "zero-knowledge rows", self.zk_rows)?;
+
+        writeln!(f, "\ngates")?;
+        for (typ, count) in &self.gates {
+            line(f, &format!("  {typ:?}"), count)?;
+        }
+
+        writeln!(f, "\nfeatures")?;
+        let features = self.features();
+        if features.is_empty() {
+            writeln!(f, "  none")?;
+        }
+        for feature in features {
+            writeln!(f, "  {feature}")?;
+        }
+
+        if let Some(lookup) = &self.lookup {
+            writeln!(f, "\nlookup tables (id, length, lookups)")?;
+            for table in &lookup.tables {
+                let len_and_lookups = format!("{:>12}{:>12}", table.len, table.lookups);
+                line(f, &format!("  {}", table.id), len_and_lookups)?;
+            }
+            for table in &lookup.runtime_tables {
+                let len_and_lookups = format!("{:>12}{:>12}", table.len, "-");
+                line(f, &format!("  {} (runtime)", table.id), len_and_lookups)?;
+            }
+            line(
+                f,
+                "lookups with a witness table ID",
+                lookup.witness_table_lookups,
+            )?;
+            line(f, "lookups per row", lookup.max_per_row)?;
+            line(f, "joint lookup size", lookup.max_joint_size)?;
+            line(
+                f,
+                "table entries used",
+                format!("{}/{}", lookup.table_entries, lookup.table_capacity),
+            )?;
+        }
+
+        writeln!(f, "\nproof size")?;
+        line(f, "  group elements", self.proof_size.group_elements)?;
+        line(f, "  field elements", self.proof_size.field_elements)?;
+        if let Some(bytes) = self.proof_size.bytes {
+            line(f, "  bytes", bytes)?;
+        }
+
+        writeln!(f, "\nprover cost")?;
+        line(f, "  MSMs", self.prover_cost.msms)?;
+        line(f, "  opening proof rounds", self.prover_cost.opening_rounds)?;
+        line(f, "  FFTs over d1", self.prover_cost.ffts_d1)?;
+        line(f, "  FFTs over d4", self.prover_cost.ffts_d4)?;
+        line(f, "  FFTs over d8", self.prover_cost.ffts_d8)
+    }
+}
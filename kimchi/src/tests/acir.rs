@@ -0,0 +1,153 @@
+use crate::{
+    acir::{AcirCircuit, Program, Witness, WitnessMap},
+    error::AcirError,
+    proof::ProverProof,
+    prover_index::testing::new_index_for_test,
+    verifier::verify,
+};
+use ark_ff::One;
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+// The corpus of ACIR programs, with the witness maps produced by the ACIR solver
+const ARITHMETIC: &str = include_str!("acir/arithmetic.acir.json");
+const ARITHMETIC_WITNESS: &str = include_str!("acir/arithmetic.witness.json");
+const BITWISE: &str = include_str!("acir/bitwise.acir.json");
+const BITWISE_WITNESS: &str = include_str!("acir/bitwise.witness.json");
+const KECCAK: &str = include_str!("acir/keccak.acir.json");
+
+fn prove_and_verify(program: &str, witness_map: &str) -> Vec<Fp> {
+    let program = Program::<Fp>::from_json(program).unwrap();
+    let circuit = AcirCircuit::new(&program).unwrap();
+    let witness_map = WitnessMap::from_json(witness_map).unwrap();
+    let witness = circuit.witness(&witness_map).unwrap();
+    let public = witness[0][..circuit.public_input_size].to_vec();
+
+    let index = new_index_for_test::<Vesta>(circuit.gates, circuit.public_input_size);
+    index.cs.verify(&witness, &public).unwrap();
+
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proof =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index).unwrap();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index.verifier_index(), &proof).unwrap();
+
+    public
+}
+
+#[test]
+fn test_acir_arithmetic() {
+    // the public parameter x, then the return value
+    let public = prove_and_verify(ARITHMETIC, ARITHMETIC_WITNESS);
+    assert_eq!(public, vec![Fp::from(2u64), Fp::from(17u64)]);
+}
+
+#[test]
+fn test_acir_bitwise() {
+    let public = prove_and_verify(BITWISE, BITWISE_WITNESS);
+    assert_eq!(
+        public,
+        vec![
+            Fp::from(0xdeadbeefu64),
+            Fp::from(0xdeadbeefu64 & 0x12345678)
+        ]
+    );
+}
+
+#[test]
+fn test_acir_wrong_witness() {
+    let program = Program::<Fp>::from_json(ARITHMETIC).unwrap();
+    let circuit = AcirCircuit::new(&program).unwrap();
+    let mut witness_map = WitnessMap::<Fp>::from_json(ARITHMETIC_WITNESS).unwrap();
+
+    // a wrong return value
+    *witness_map.0.get_mut(&Witness(4)).unwrap() += Fp::one();
+    let witness = circuit.witness(&witness_map).unwrap();
+    let public = witness[0][..circuit.public_input_size].to_vec();
+    let index = new_index_for_test::<Vesta>(circuit.gates.clone(), circuit.public_input_size);
+    assert!(index.cs.verify(&witness, &public).is_err());
+
+    // a missing witness
+    witness_map.0.remove(&Witness(6));
+    assert!(matches!(
+        circuit.witness(&witness_map),
+        Err(AcirError::MissingWitness(6))
+    ));
+
+    // an input of a bitwise operation that is too large
+    let program = Program::<Fp>::from_json(BITWISE).unwrap();
+    let circuit = AcirCircuit::new(&program).unwrap();
+    let mut witness_map = WitnessMap::<Fp>::from_json(BITWISE_WITNESS).unwrap();
+    witness_map.0.insert(Witness(1), Fp::from(1u64 << 40));
+    assert!(matches!(
+        circuit.witness(&witness_map),
+        Err(AcirError::InvalidInput {
+            witness: 1,
+            num_bits: 32
+        })
+    ));
+}
+
+#[test]
+fn test_acir_unsupported() {
+    let program = Program::<Fp>::from_json(KECCAK).unwrap();
+    assert!(matches!(
+        AcirCircuit::new(&program),
+        Err(AcirError::UnsupportedBlackBox {
+            index: 0,
+            function: "Keccakf1600"
+        })
+    ));
+
+    let memory = r#"{
+        "current_witness_index": 1,
+        "opcodes": [
+            { "AssertZero": { "mul_terms": [], "linear_combinations": [["01", 1]], "q_c": "00" } },
+            { "MemoryInit": { "block_id": 0, "init": [1] } }
+        ]
+    }"#;
+    let program = Program::<Fp>::from_json(memory).unwrap();
+    assert!(matches!(
+        AcirCircuit::new(&program),
+        Err(AcirError::UnsupportedOpcode {
+            index: 1,
+            opcode: "MemoryInit"
+        })
+    ));
+
+    let range = r#"{
+        "current_witness_index": 1,
+        "opcodes": [
+            { "BlackBoxFuncCall": { "RANGE": { "input": { "witness": 1, "num_bits": 128 } } } }
+        ]
+    }"#;
+    let program = Program::<Fp>::from_json(range).unwrap();
+    assert!(matches!(
+        AcirCircuit::new(&program),
+        Err(AcirError::UnsupportedRange {
+            index: 0,
+            num_bits: 128
+        })
+    ));
+
+    // the Pallas base field modulus is not an element of the field
+    let not_in_field = r#"{
+        "current_witness_index": 1,
+        "opcodes": [
+            { "AssertZero": { "mul_terms": [], "linear_combinations": [],
+              "q_c": "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001" } }
+        ]
+    }"#;
+    assert!(matches!(
+        Program::<Fp>::from_json(not_in_field),
+        Err(AcirError::Deserialization(_))
+    ));
+}
@@ -0,0 +1,96 @@
+use crate::circuits::polynomials::generic::testing::{create_circuit, fill_in_witness};
+use crate::circuits::wires::COLUMNS;
+use crate::{
+    error::ProverError,
+    proof::ProverProof,
+    prover::BatchWitness,
+    prover_index::testing::new_index_for_test,
+    verifier::{batch_verify, verify},
+};
+use ark_ff::Zero;
+use ark_poly::EvaluationDomain;
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use std::array;
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+const PUBLIC: usize = 5;
+
+fn witnesses(num: usize, num_rows: usize) -> Vec<BatchWitness<Fp>> {
+    (0..num)
+        .map(|i| {
+            let public = vec![Fp::from(i as u64); PUBLIC];
+            let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); num_rows]);
+            fill_in_witness(0, &mut witness, &public);
+            witness.into()
+        })
+        .collect()
+}
+
+#[test]
+fn test_batch_proving() {
+    let gates = create_circuit(0, PUBLIC);
+    let num_rows = gates.len();
+    let index = new_index_for_test::<Vesta>(gates, PUBLIC);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // a memory bound that forces a single proof per round, and one that does not bound anything
+    for max_memory in [0, usize::MAX] {
+        let proofs = ProverProof::create_batch::<BaseSponge, ScalarSponge>(
+            &group_map,
+            witnesses(4, num_rows),
+            &index,
+            max_memory,
+            None,
+        );
+        assert_eq!(proofs.len(), 4);
+        let proofs: Vec<_> = proofs.into_iter().map(Result::unwrap).collect();
+
+        // the proofs are returned in order
+        for (i, proof) in proofs.iter().enumerate() {
+            assert_eq!(proof.public, vec![Fp::from(i as u64); PUBLIC]);
+        }
+
+        let verifier_index = index.verifier_index();
+        let batch: Vec<_> = proofs.iter().map(|p| (&verifier_index, p)).collect();
+        batch_verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch).unwrap();
+    }
+}
+
+#[test]
+fn test_batch_proving_reports_errors_per_witness() {
+    let gates = create_circuit(0, PUBLIC);
+    let num_rows = gates.len();
+    let index = new_index_for_test::<Vesta>(gates, PUBLIC);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // the second witness leaves no room for the zero-knowledge rows
+    let mut witnesses = witnesses(3, num_rows);
+    let d1_size = index.cs.domain.d1.size();
+    for col in &mut witnesses[1].witness {
+        col.resize(d1_size - 1, Fp::zero());
+    }
+
+    let proofs = ProverProof::create_batch::<BaseSponge, ScalarSponge>(
+        &group_map,
+        witnesses,
+        &index,
+        usize::MAX,
+        None,
+    );
+
+    let verifier_index = index.verifier_index();
+    assert!(matches!(proofs[1], Err(ProverError::NoRoomForZkInWitness)));
+    for i in [0, 2] {
+        let proof = proofs[i].as_ref().unwrap();
+        verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, proof).unwrap();
+    }
+}
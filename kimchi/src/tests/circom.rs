@@ -0,0 +1,245 @@
+use crate::{
+    circom::{read_wtns, CircomCircuit, R1cs},
+    error::CircomError,
+    proof::ProverProof,
+    prover_index::testing::new_index_for_test,
+    verifier::verify,
+};
+use ark_ff::{One, PrimeField, Zero};
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Fq, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use o1_utils::FieldHelpers;
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+type Lc = Vec<(u32, Fp)>;
+
+//
+// Encoders for the circom binary formats
+//
+
+fn prime_bytes<F: PrimeField>() -> Vec<u8> {
+    let mut prime = F::modulus_biguint().to_bytes_le();
+    prime.resize(32, 0);
+    prime
+}
+
+fn file(magic: &[u8], version: u32, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend(version.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (typ, content) in sections {
+        bytes.extend(typ.to_le_bytes());
+        bytes.extend((content.len() as u64).to_le_bytes());
+        bytes.extend(content);
+    }
+    bytes
+}
+
+fn r1cs_bytes<F: PrimeField>(
+    num_wires: u32,
+    public: (u32, u32, u32),
+    constraints: &[[Lc; 3]],
+) -> Vec<u8> {
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend(prime_bytes::<F>());
+    header.extend(num_wires.to_le_bytes());
+    header.extend(public.0.to_le_bytes());
+    header.extend(public.1.to_le_bytes());
+    header.extend(public.2.to_le_bytes());
+    header.extend(0u64.to_le_bytes());
+    header.extend((constraints.len() as u32).to_le_bytes());
+
+    let mut content = vec![];
+    for lcs in constraints {
+        for lc in lcs {
+            content.extend((lc.len() as u32).to_le_bytes());
+            for (wire, coeff) in lc {
+                content.extend(wire.to_le_bytes());
+                content.extend(coeff.to_bytes());
+            }
+        }
+    }
+
+    // sections can appear in any order
+    file(b"r1cs", 1, &[(2, content), (1, header)])
+}
+
+fn wtns_bytes<F: PrimeField>(values: &[Fp]) -> Vec<u8> {
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend(prime_bytes::<F>());
+    header.extend((values.len() as u32).to_le_bytes());
+
+    let content = values.iter().flat_map(|value| value.to_bytes()).collect();
+    file(b"wtns", 2, &[(1, header), (2, content)])
+}
+
+//
+// The test circuit
+//
+// wires: 0 = 1, 1 = out (public output), 2 = x (public input),
+//        3 = y, 4 = z (private inputs), 5 = t, 6 = w
+//
+
+fn lc(terms: &[(u32, u64)]) -> Lc {
+    terms
+        .iter()
+        .map(|(wire, coeff)| (*wire, Fp::from(*coeff)))
+        .collect()
+}
+
+fn constraints() -> Vec<[Lc; 3]> {
+    vec![
+        // x * y = t
+        [lc(&[(2, 1)]), lc(&[(3, 1)]), lc(&[(5, 1)])],
+        // (t + 2z + 3) * 1 = out
+        [lc(&[(5, 1), (4, 2), (0, 3)]), lc(&[(0, 1)]), lc(&[(1, 1)])],
+        // (x + y) * (z + x - x) = w
+        [
+            lc(&[(2, 1), (3, 1)]),
+            vec![(4, Fp::one()), (2, Fp::one()), (2, -Fp::one())],
+            lc(&[(6, 1)]),
+        ],
+        // 2 * 3 = 6
+        [lc(&[(0, 2)]), lc(&[(0, 3)]), lc(&[(0, 6)])],
+    ]
+}
+
+fn wires() -> Vec<Fp> {
+    // x = 3, y = 5, z = 7
+    [1u64, 32, 3, 5, 7, 15, 56]
+        .into_iter()
+        .map(Fp::from)
+        .collect()
+}
+
+#[test]
+fn test_circom_prove_and_verify() {
+    let r1cs = R1cs::<Fp>::from_bytes(&r1cs_bytes::<Fp>(7, (1, 1, 2), &constraints())).unwrap();
+    assert_eq!(r1cs.public_input_size(), 2);
+    assert_eq!(r1cs.constraints.len(), 4);
+
+    let circuit = CircomCircuit::new(&r1cs).unwrap();
+    let wires = read_wtns::<Fp>(&wtns_bytes::<Fp>(&wires())).unwrap();
+    let witness = circuit.witness(&wires).unwrap();
+    let public = witness[0][..circuit.public_input_size].to_vec();
+    assert_eq!(public, vec![Fp::from(32u64), Fp::from(3u64)]);
+
+    let index = new_index_for_test::<Vesta>(circuit.gates, circuit.public_input_size);
+    index.cs.verify(&witness, &public).unwrap();
+
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proof =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index).unwrap();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index.verifier_index(), &proof).unwrap();
+}
+
+#[test]
+fn test_circom_wrong_witness() {
+    let r1cs = R1cs::<Fp>::from_bytes(&r1cs_bytes::<Fp>(7, (1, 1, 2), &constraints())).unwrap();
+    let circuit = CircomCircuit::new(&r1cs).unwrap();
+
+    let mut wires = wires();
+    wires[5] += Fp::one();
+    let witness = circuit.witness(&wires).unwrap();
+    let public = witness[0][..circuit.public_input_size].to_vec();
+
+    let index = new_index_for_test::<Vesta>(circuit.gates.clone(), circuit.public_input_size);
+    assert!(index.cs.verify(&witness, &public).is_err());
+
+    // the wires must match the circuit
+    assert!(matches!(
+        circuit.witness(&wires[..6]),
+        Err(CircomError::WitnessLength {
+            expected: 7,
+            found: 6
+        })
+    ));
+    wires[0] = Fp::zero();
+    assert!(matches!(
+        circuit.witness(&wires),
+        Err(CircomError::InvalidConstantWire)
+    ));
+}
+
+#[test]
+fn test_circom_field_mismatch() {
+    // files produced for the other Pasta field
+    let r1cs = r1cs_bytes::<Fq>(7, (1, 1, 2), &[]);
+    assert!(matches!(
+        R1cs::<Fp>::from_bytes(&r1cs),
+        Err(CircomError::FieldMismatch { format: "r1cs", .. })
+    ));
+
+    let wtns = wtns_bytes::<Fq>(&wires());
+    assert!(matches!(
+        read_wtns::<Fp>(&wtns),
+        Err(CircomError::FieldMismatch { format: "wtns", .. })
+    ));
+}
+
+#[test]
+fn test_circom_malformed_files() {
+    let r1cs = r1cs_bytes::<Fp>(7, (1, 1, 2), &constraints());
+
+    assert!(matches!(
+        R1cs::<Fp>::from_bytes(&r1cs[..r1cs.len() - 1]),
+        Err(CircomError::Truncated("r1cs"))
+    ));
+
+    assert!(matches!(
+        read_wtns::<Fp>(&r1cs),
+        Err(CircomError::InvalidMagic("wtns"))
+    ));
+
+    let mut unsupported = r1cs.clone();
+    unsupported[4] = 2;
+    assert!(matches!(
+        R1cs::<Fp>::from_bytes(&unsupported),
+        Err(CircomError::UnsupportedVersion("r1cs", 2))
+    ));
+
+    // a constraint using a wire that does not exist
+    let invalid_wire = r1cs_bytes::<Fp>(6, (1, 1, 2), &constraints());
+    assert!(matches!(
+        R1cs::<Fp>::from_bytes(&invalid_wire),
+        Err(CircomError::InvalidWire {
+            constraint: 2,
+            wire: 6,
+            num_wires: 6
+        })
+    ));
+
+    // a constraint that can never hold: 2 * 3 = 7
+    let unsatisfiable = [[lc(&[(0, 2)]), lc(&[(0, 3)]), lc(&[(0, 7)])]];
+    let r1cs = R1cs::<Fp>::from_bytes(&r1cs_bytes::<Fp>(7, (1, 1, 2), &unsatisfiable)).unwrap();
+    assert!(matches!(
+        CircomCircuit::new(&r1cs),
+        Err(CircomError::UnsatisfiableConstraint(0))
+    ));
+}
+
+#[test]
+fn test_circom_too_few_gates() {
+    // no constraints and no public input
+    let r1cs = R1cs::<Fp>::from_bytes(&r1cs_bytes::<Fp>(1, (0, 0, 0), &[])).unwrap();
+    assert!(matches!(
+        CircomCircuit::new(&r1cs),
+        Err(CircomError::TooFewGates(0))
+    ));
+
+    // a single public input, and a constraint that only involves constants
+    let constant = [[lc(&[(0, 2)]), lc(&[(0, 3)]), lc(&[(0, 6)])]];
+    let r1cs = R1cs::<Fp>::from_bytes(&r1cs_bytes::<Fp>(2, (0, 1, 0), &constant)).unwrap();
+    assert!(matches!(
+        CircomCircuit::new(&r1cs),
+        Err(CircomError::TooFewGates(1))
+    ));
+}
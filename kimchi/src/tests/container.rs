@@ -0,0 +1,179 @@
+use super::framework::generic_proof;
+use crate::{
+    container::{encode, read_header, Container, ContainerHeader, ContainerKind, FORMAT_VERSION},
+    error::ContainerError,
+    proof::ProverProof,
+    prover_index::ProverIndex,
+    verifier::verify,
+    verifier_index::VerifierIndex,
+};
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Pallas, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+fn create_proof() -> (ProverIndex<Vesta>, ProverProof<Vesta>) {
+    generic_proof::<Vesta, BaseSponge, ScalarSponge>(5)
+}
+
+#[test]
+fn test_container_roundtrip() {
+    let (index, proof) = create_proof();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // encode and decode the proof
+    let proof_bytes = proof.to_container_bytes().unwrap();
+    let header = read_header(&proof_bytes).unwrap();
+    assert_eq!(header.version, FORMAT_VERSION);
+    assert_eq!(header.kind, ContainerKind::Proof);
+    assert_eq!(header.curve, "vesta");
+    let proof = ProverProof::<Vesta>::from_container_bytes(&proof_bytes).unwrap();
+
+    // encode and decode the verifier index, through a writer and a reader
+    let mut index_bytes = vec![];
+    index
+        .verifier_index()
+        .write_container(&mut index_bytes)
+        .unwrap();
+    let verifier_index = VerifierIndex::<Vesta>::read_container(&mut &index_bytes[..]).unwrap();
+
+    // the decoded verifier index accepts the decoded proof
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+}
+
+#[test]
+fn test_container_errors() {
+    let (index, proof) = create_proof();
+    let bytes = proof.to_container_bytes().unwrap();
+
+    // corrupted content
+    let mut corrupted = bytes.clone();
+    let mid = corrupted.len() / 2;
+    corrupted[mid] ^= 1;
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&corrupted),
+        Err(ContainerError::InvalidChecksum)
+    ));
+
+    // truncated content
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&bytes[..bytes.len() - 1]),
+        Err(ContainerError::Truncated)
+    ));
+
+    // bytes after the checksum
+    let mut trailing = bytes.clone();
+    trailing.extend_from_slice(&[0, 0]);
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&trailing),
+        Err(ContainerError::TrailingBytes(2))
+    ));
+
+    // not a container
+    let mut bad_magic = bytes.clone();
+    bad_magic[0] = b'X';
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&bad_magic),
+        Err(ContainerError::InvalidMagic)
+    ));
+
+    // a version from the future
+    let mut future = bytes.clone();
+    future[4..6].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&future),
+        Err(ContainerError::UnsupportedVersion(v, _)) if v == FORMAT_VERSION + 1
+    ));
+
+    // a proof is not a verifier index
+    assert!(matches!(
+        VerifierIndex::<Vesta>::from_container_bytes(&bytes),
+        Err(ContainerError::UnexpectedKind {
+            expected: ContainerKind::VerifierIndex,
+            found: ContainerKind::Proof,
+        })
+    ));
+
+    // a vesta proof is not a pallas proof
+    assert!(matches!(
+        ProverProof::<Pallas>::from_container_bytes(&bytes),
+        Err(ContainerError::UnexpectedCurve { .. })
+    ));
+
+    // a verifier index is not a proof
+    let index_bytes = index.verifier_index().to_container_bytes().unwrap();
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&index_bytes),
+        Err(ContainerError::UnexpectedKind {
+            expected: ContainerKind::Proof,
+            found: ContainerKind::VerifierIndex,
+        })
+    ));
+}
+
+#[test]
+fn test_container_migration() {
+    let (index, mut proof) = create_proof();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // a proof as written with format version 2, before proofs included the evaluation of ft at zeta
+    proof.ft_eval0 = None;
+    let mut payload = rmp_serde::to_vec(&proof).unwrap();
+    assert_eq!(payload[0], 0x98);
+    assert_eq!(payload.pop(), Some(0xc0));
+    payload[0] = 0x97;
+
+    let mut header = ContainerHeader {
+        version: 2,
+        kind: ContainerKind::Proof,
+        curve: "vesta".to_string(),
+        flags: proof.feature_flags(),
+    };
+    let bytes = encode(&header, &payload).unwrap();
+    let migrated = ProverProof::<Vesta>::from_container_bytes(&bytes).unwrap();
+    assert!(migrated.ft_eval0.is_none());
+    assert!(migrated.verifier_index_digest.is_some());
+
+    let verifier_index = index.verifier_index();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &migrated).unwrap();
+
+    // a proof as written with format version 1, before proofs included the digest of their verifier index
+    proof.verifier_index_digest = None;
+    let mut payload = rmp_serde::to_vec(&proof).unwrap();
+    assert_eq!(payload.pop(), Some(0xc0));
+    assert_eq!(payload.pop(), Some(0xc0));
+    payload[0] = 0x96;
+    header.version = 1;
+    let bytes = encode(&header, &payload).unwrap();
+    let migrated = ProverProof::<Vesta>::from_container_bytes(&bytes).unwrap();
+    assert!(migrated.verifier_index_digest.is_none());
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &migrated).unwrap();
+
+    // a payload that does not have the layout of version 1
+    let bytes = encode(&header, &[0x95]).unwrap();
+    assert!(matches!(
+        ProverProof::<Vesta>::from_container_bytes(&bytes),
+        Err(ContainerError::Migration(1, _))
+    ));
+}
+
+#[test]
+fn test_verifier_index_file_errors() {
+    let (index, _) = create_proof();
+
+    let missing = std::env::temp_dir().join("kimchi-missing-verifier-index");
+    assert!(matches!(
+        VerifierIndex::<Vesta>::from_file(None, &missing, None),
+        Err(ContainerError::Io(_))
+    ));
+    assert!(matches!(
+        index.verifier_index().to_file(&missing, Some(false)),
+        Err(ContainerError::Io(_))
+    ));
+}
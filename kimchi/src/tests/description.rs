@@ -0,0 +1,139 @@
+use super::serde::check_json;
+use crate::{
+    circuits::{
+        constraints::ConstraintSystem,
+        description::{CircuitDescription, RuntimeTableDescription},
+        gate::{CircuitGate, GateType},
+        lookup::{
+            runtime_tables::{RuntimeTableCfg, RuntimeTableSpec},
+            tables::LookupTable,
+        },
+        polynomials::generic::testing::create_circuit,
+        wires::Wire,
+    },
+    error::DescriptionError,
+    json::Json,
+};
+use mina_curves::pasta::Fp;
+
+/// Exports `cs`, goes through JSON, and checks that the description builds the same constraint system.
+fn check_round_trip(cs: &ConstraintSystem<Fp>) -> CircuitDescription<Fp> {
+    let description = cs.description().unwrap();
+
+    let json = description.to_json_pretty().unwrap();
+    check_json(&json, "CircuitDescription");
+    let imported = CircuitDescription::<Fp>::from_json(&json).unwrap();
+    assert_eq!(imported, description);
+
+    let rebuilt = imported.into_builder().build().unwrap();
+    let diff = cs.diff(&rebuilt);
+    assert!(diff.is_empty(), "{diff}");
+    assert_eq!(rebuilt.gates, cs.gates);
+
+    description
+}
+
+#[test]
+fn test_description_generic() {
+    let public = 5;
+    let gates = create_circuit::<Fp>(0, public);
+    let cs = ConstraintSystem::create(gates.clone())
+        .public(public)
+        .prev_challenges(2)
+        .build()
+        .unwrap();
+
+    let description = check_round_trip(&cs);
+    assert_eq!(description.gates, gates);
+    assert_eq!(description.public_input_size, public);
+    assert_eq!(description.prev_challenges, 2);
+    assert!(description.lookup_tables.is_empty());
+    assert!(description.runtime_tables.is_none());
+
+    // a circuit that is not zero-knowledge
+    let cs = ConstraintSystem::create(gates)
+        .public(public)
+        .zero_knowledge(false)
+        .build()
+        .unwrap();
+    assert!(!check_round_trip(&cs).zero_knowledge);
+}
+
+#[test]
+fn test_description_lookups() {
+    // a gate using the XOR table, followed by lookup gates
+    let gates: Vec<_> = (0..300)
+        .map(|row| {
+            let typ = if row == 0 {
+                GateType::Xor16
+            } else {
+                GateType::Lookup
+            };
+            CircuitGate::new(typ, Wire::for_row(row), vec![])
+        })
+        .collect();
+    let table = LookupTable {
+        id: 1,
+        data: vec![
+            (10..20u64).map(Into::into).collect(),
+            (20..30u64).map(Into::into).collect(),
+        ],
+    };
+    let first_column: Vec<Fp> = vec![5u64.into(), 7u64.into(), 9u64.into()];
+    let runtime_tables = vec![
+        RuntimeTableCfg::Custom {
+            id: 2,
+            first_column: first_column.clone(),
+        },
+        RuntimeTableCfg::Indexed(RuntimeTableSpec { id: 3, len: 4 }),
+    ];
+
+    let cs = ConstraintSystem::create(gates)
+        .public(1)
+        .lookup(vec![table.clone()])
+        .runtime(Some(runtime_tables))
+        .build()
+        .unwrap();
+
+    let description = check_round_trip(&cs);
+
+    // the XOR table is not exported, as it comes with the gate
+    assert_eq!(description.lookup_tables.len(), 1);
+    let exported = &description.lookup_tables[0];
+    assert_eq!(exported.id, 1);
+    assert_eq!(exported.data[..2], table.data[..]);
+    assert_eq!(
+        description.runtime_tables,
+        Some(vec![
+            RuntimeTableDescription::Custom {
+                id: 2,
+                first_column
+            },
+            RuntimeTableDescription::Indexed { id: 3, len: 4 },
+        ])
+    );
+
+    // the lookup tables of a constraint system serialized before they were recorded are unknown
+    let mut old = cs;
+    old.lookup_constraint_system.as_mut().unwrap().fixed_tables = vec![];
+    assert!(matches!(
+        old.description(),
+        Err(DescriptionError::MissingLookupTables)
+    ));
+}
+
+#[test]
+fn test_description_errors() {
+    let gates = create_circuit::<Fp>(0, 0);
+    let json = CircuitDescription::new(gates, 0).to_json().unwrap();
+    assert!(CircuitDescription::<Fp>::from_json(&json).is_ok());
+
+    // unsupported versions are rejected
+    let other_version = json.replacen("\"version\":1", "\"version\":2", 1);
+    let err = CircuitDescription::<Fp>::from_json(&other_version).unwrap_err();
+    assert!(err.to_string().contains("version 2"));
+
+    // and so are unknown fields
+    let unknown_field = json.replacen("{", "{\"lookups\":[],", 1);
+    assert!(CircuitDescription::<Fp>::from_json(&unknown_field).is_err());
+}
@@ -0,0 +1,45 @@
+use super::framework::generic_index;
+use crate::{proof::ProverProof, verifier::verify};
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+#[test]
+fn test_deterministic_proofs() {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let verifier_index = index.verifier_index();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let prove = |seed| {
+        let proof = ProverProof::create_deterministic::<BaseSponge, ScalarSponge>(
+            &group_map,
+            witness.clone(),
+            &[],
+            &index,
+            vec![],
+            seed,
+        )
+        .unwrap();
+        let bytes = rmp_serde::to_vec(&proof).unwrap();
+        (proof, bytes)
+    };
+
+    let (proof, bytes1) = prove([7u8; 32]);
+    let (_, bytes2) = prove([7u8; 32]);
+    let (_, bytes3) = prove([8u8; 32]);
+
+    // same seed, same proof
+    assert_eq!(bytes1, bytes2);
+
+    // different seed, different proof
+    assert_ne!(bytes1, bytes3);
+
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+}
@@ -0,0 +1,220 @@
+use crate::{
+    circuits::{
+        constraints::ConstraintSystem,
+        gate::{CircuitGate, GateType},
+        lookup::tables::LookupTable,
+        wires::Wire,
+    },
+    diff::{diff_gates, GateChange, TableChange},
+    prover_index::testing::new_index_for_test,
+};
+use mina_curves::pasta::{Fp, Vesta};
+
+/// A chain of generic gates with distinct coefficients,
+/// where the first cell of the first row and the first cell of the last row are wired together.
+fn circuit(coeffs: &[u64]) -> Vec<CircuitGate<Fp>> {
+    let last = coeffs.len() - 1;
+    let mut gates: Vec<_> = coeffs
+        .iter()
+        .enumerate()
+        .map(|(row, coeff)| {
+            CircuitGate::new(
+                GateType::Generic,
+                Wire::for_row(row),
+                vec![Fp::from(*coeff)],
+            )
+        })
+        .collect();
+    gates[0].wires[0] = Wire { row: last, col: 0 };
+    gates[last].wires[0] = Wire { row: 0, col: 0 };
+    gates
+}
+
+#[test]
+fn test_diff_identical() {
+    let gates = circuit(&[1, 2, 3, 4, 5]);
+    assert!(diff_gates(&gates, &gates).is_empty());
+
+    let cs = ConstraintSystem::fp_for_testing(gates);
+    let diff = cs.diff(&cs);
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "no differences\n");
+}
+
+#[test]
+fn test_diff_shifted_gadget() {
+    let old = circuit(&[1, 2, 3, 4, 5, 6]);
+
+    // a gadget inserted in the middle shifts the end of the circuit,
+    // and the wiring to the last row follows it
+    let new = circuit(&[1, 2, 3, 100, 101, 4, 5, 6]);
+    assert_eq!(
+        diff_gates(&old, &new),
+        vec![
+            GateChange::Inserted {
+                row: 3,
+                typ: GateType::Generic
+            },
+            GateChange::Inserted {
+                row: 4,
+                typ: GateType::Generic
+            },
+        ]
+    );
+
+    // and the other way around
+    assert_eq!(
+        diff_gates(&new, &old),
+        vec![
+            GateChange::Removed {
+                row: 3,
+                typ: GateType::Generic
+            },
+            GateChange::Removed {
+                row: 4,
+                typ: GateType::Generic
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_diff_changed_gates() {
+    let old = circuit(&[1, 2, 3, 4, 5, 6]);
+    let mut new = circuit(&[1, 2, 3, 4, 5, 6]);
+
+    // a changed coefficient
+    new[1].coeffs[0] = Fp::from(42u64);
+    // a changed type
+    new[2].typ = GateType::Poseidon;
+    // a changed wiring
+    new[4].wires[3] = Wire { row: 0, col: 3 };
+    new[0].wires[3] = Wire { row: 4, col: 3 };
+
+    let changes = diff_gates(&old, &new);
+    assert_eq!(
+        changes,
+        vec![
+            GateChange::Changed {
+                old_row: 0,
+                new_row: 0,
+                old_typ: GateType::Generic,
+                new_typ: GateType::Generic,
+                coeffs: vec![],
+                wires: vec![3],
+            },
+            GateChange::Changed {
+                old_row: 1,
+                new_row: 1,
+                old_typ: GateType::Generic,
+                new_typ: GateType::Generic,
+                coeffs: vec![0],
+                wires: vec![],
+            },
+            GateChange::Changed {
+                old_row: 2,
+                new_row: 2,
+                old_typ: GateType::Generic,
+                new_typ: GateType::Poseidon,
+                coeffs: vec![],
+                wires: vec![],
+            },
+            GateChange::Changed {
+                old_row: 4,
+                new_row: 4,
+                old_typ: GateType::Generic,
+                new_typ: GateType::Generic,
+                coeffs: vec![],
+                wires: vec![3],
+            },
+        ]
+    );
+    assert_eq!(
+        changes[2].to_string(),
+        "~ row 2 -> 2: type Generic -> Poseidon"
+    );
+}
+
+#[test]
+fn test_diff_constraint_systems() {
+    let lookup_gates = |n: usize| -> Vec<CircuitGate<Fp>> {
+        (0..n)
+            .map(|row| CircuitGate::new(GateType::Lookup, Wire::for_row(row), vec![]))
+            .collect()
+    };
+    let table = |id: i32, values: &[u64]| LookupTable {
+        id,
+        data: vec![values.iter().map(|v| Fp::from(*v)).collect()],
+    };
+
+    let old = ConstraintSystem::create(lookup_gates(20))
+        .lookup(vec![
+            table(0, &[0, 1, 2]),
+            table(1, &[5, 6]),
+            table(2, &[7]),
+        ])
+        .public(1)
+        .build()
+        .unwrap();
+    let new = ConstraintSystem::create(lookup_gates(21))
+        .lookup(vec![
+            table(0, &[0, 1, 3]),
+            table(1, &[5, 6, 7]),
+            table(3, &[8]),
+        ])
+        .public(2)
+        .build()
+        .unwrap();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.public, Some((1, 2)));
+    assert_eq!(diff.domain_size, None);
+    assert_eq!(
+        diff.gates,
+        vec![GateChange::Inserted {
+            row: 20,
+            typ: GateType::Lookup
+        }]
+    );
+    assert_eq!(
+        diff.lookup_tables,
+        vec![
+            TableChange::Modified { id: 0 },
+            TableChange::Resized {
+                id: 1,
+                old_len: 2,
+                new_len: 3
+            },
+            TableChange::Removed { id: 2, len: 1 },
+            TableChange::Inserted { id: 3, len: 1 },
+        ]
+    );
+    assert!(diff.added_features.is_empty());
+
+    // a circuit without lookups
+    let generic = ConstraintSystem::fp_for_testing(circuit(&[1, 2, 3, 4]));
+    let diff = generic.diff(&old);
+    assert!(diff.added_features.contains(&"lookup_pattern_lookup"));
+    assert_eq!(diff.lookup_tables.len(), 3);
+    assert!(old
+        .diff(&generic)
+        .removed_features
+        .contains(&"lookup_pattern_lookup"));
+}
+
+#[test]
+fn test_diff_verifier_indexes() {
+    let old = circuit(&[1, 2, 3, 4, 5, 6]);
+    let mut new = old.clone();
+    new[1].coeffs[0] = Fp::from(42u64);
+
+    let old_index = new_index_for_test::<Vesta>(old, 0).verifier_index();
+    let new_index = new_index_for_test::<Vesta>(new, 0).verifier_index();
+
+    assert!(old_index.diff(&old_index).is_empty());
+
+    let diff = old_index.diff(&new_index);
+    assert_eq!(diff.commitments, vec!["coefficients_comm[0]".to_string()]);
+    assert_eq!(diff.public, None);
+    assert!(!diff.lookup_info);
+}
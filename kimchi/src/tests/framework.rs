@@ -7,21 +7,25 @@ use crate::{
             runtime_tables::{RuntimeTable, RuntimeTableCfg},
             tables::LookupTable,
         },
+        polynomials::generic::testing::{create_circuit, fill_in_witness},
         wires::COLUMNS,
     },
     curve::KimchiCurve,
     plonk_sponge::FrSponge,
     proof::{ProverProof, RecursionChallenge},
-    prover_index::{testing::new_index_for_test_with_lookups, ProverIndex},
+    prover_index::{
+        testing::{new_index_for_test, new_index_for_test_with_lookups},
+        ProverIndex,
+    },
     verifier::verify,
     verifier_index::VerifierIndex,
 };
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, SquareRootField, Zero};
 use commitment_dlog::commitment::CommitmentCurve;
 use groupmap::GroupMap;
 use mina_poseidon::sponge::FqSponge;
 use num_bigint::BigUint;
-use std::{fmt::Write, mem, time::Instant};
+use std::{array, fmt::Write, mem, time::Instant};
 
 // aliases
 
@@ -188,6 +192,53 @@ where
     }
 }
 
+/// The generic gate circuit of [`create_circuit`] with `public` public inputs, all set to 3,
+/// and its witness.
+pub(crate) fn generic_circuit<F: PrimeField>(
+    public: usize,
+) -> (Vec<CircuitGate<F>>, [Vec<F>; COLUMNS]) {
+    let public = vec![F::from(3u8); public];
+    let gates = create_circuit(0, public.len());
+
+    let mut witness: [Vec<F>; COLUMNS] = array::from_fn(|_| vec![F::zero(); gates.len()]);
+    fill_in_witness(0, &mut witness, &public);
+
+    (gates, witness)
+}
+
+/// Creates the prover index of the [`generic_circuit`] with `public` public inputs,
+/// and returns it along with the witness.
+pub(crate) fn generic_index<G: KimchiCurve>(
+    public: usize,
+) -> (ProverIndex<G>, [Vec<G::ScalarField>; COLUMNS])
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let (gates, witness) = generic_circuit(public);
+    (new_index_for_test(gates, public), witness)
+}
+
+/// Creates the prover index of the [`generic_circuit`] with `public` public inputs,
+/// and a proof for it.
+pub(crate) fn generic_proof<G, EFqSponge, EFrSponge>(
+    public: usize,
+) -> (ProverIndex<G>, ProverProof<G>)
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField + SquareRootField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    let (index, witness) = generic_index(public);
+    let group_map = <G as CommitmentCurve>::Map::setup();
+    let proof =
+        ProverProof::create::<EFqSponge, EFrSponge>(&group_map, witness, &[], &index).unwrap();
+
+    (index, proof)
+}
+
 pub fn print_witness<F>(cols: &[Vec<F>; COLUMNS], start_row: usize, end_row: usize)
 where
     F: PrimeField,
@@ -0,0 +1,83 @@
+use super::framework::{generic_index, generic_proof};
+use crate::{
+    error::VerifyError,
+    proof::ProverProof,
+    prover_index::ProverIndex,
+    verifier::{batch_verify, verify},
+};
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+fn create_proof(public_size: usize) -> (ProverIndex<Vesta>, ProverProof<Vesta>) {
+    generic_proof::<Vesta, BaseSponge, ScalarSponge>(public_size)
+}
+
+#[test]
+fn test_index_mismatch() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (index1, proof1) = create_proof(5);
+    let (index2, proof2) = create_proof(3);
+    let verifier_index1 = index1.verifier_index();
+    let verifier_index2 = index2.verifier_index();
+
+    // proofs include the digest of their verifier index
+    assert_eq!(
+        proof1.verifier_index_digest,
+        Some(verifier_index1.digest::<BaseSponge>())
+    );
+
+    // each proof verifies against its own index
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index1, &proof1).unwrap();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index2, &proof2).unwrap();
+
+    // but not against the other one
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index2, &proof1);
+    assert!(matches!(res, Err(VerifyError::IndexMismatch)));
+
+    // even within a batch
+    let res = batch_verify::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index1, &proof1), (&verifier_index1, &proof2)],
+    );
+    assert!(matches!(
+        res,
+        Err(VerifyError::BatchProofFailed(1, err)) if matches!(*err, VerifyError::IndexMismatch)
+    ));
+}
+
+#[test]
+fn test_proof_without_index_digest() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (index1, mut proof1) = create_proof(5);
+    let (index2, _) = create_proof(3);
+
+    // the digest is optional
+    proof1.verifier_index_digest = None;
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index1.verifier_index(), &proof1)
+        .unwrap();
+
+    // and the prover can leave it out
+    let (mut index3, witness) = generic_index::<Vesta>(5);
+    index3.include_verifier_index_digest = false;
+    let proof3 =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index3).unwrap();
+    assert!(proof3.verifier_index_digest.is_none());
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index3.verifier_index(), &proof3)
+        .unwrap();
+
+    // without it, a mismatch is only detected later on
+    let res =
+        verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index2.verifier_index(), &proof1);
+    assert!(matches!(
+        res,
+        Err(VerifyError::IncorrectPubicInputLength(3))
+    ));
+}
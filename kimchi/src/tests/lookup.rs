@@ -1,9 +1,11 @@
 use super::framework::{print_witness, TestFramework};
 use crate::circuits::{
+    constraints::ConstraintSystem,
     gate::{CircuitGate, GateType},
     lookup::{
+        index::LookupConstraintSystem,
         runtime_tables::{RuntimeTable, RuntimeTableCfg, RuntimeTableSpec},
-        tables::LookupTable,
+        tables::{LookupTable, LookupTableSpec},
     },
     polynomial::COLUMNS,
     wires::Wire,
@@ -206,4 +208,37 @@ fn test_custom_runtime_table() {
     runtime_table(5, false);
 }
 
+#[test]
+fn test_deserialize_without_fixed_tables() {
+    let table = LookupTable {
+        id: 0,
+        data: vec![
+            (0..10u64).map(Into::into).collect(),
+            (0..10u64).map(|i| (i * i).into()).collect(),
+        ],
+    };
+    let gates = (0..10)
+        .map(|row| CircuitGate::new(GateType::Lookup, Wire::for_row(row), vec![]))
+        .collect();
+    let cs = ConstraintSystem::<Fp>::create(gates)
+        .lookup(vec![table])
+        .build()
+        .unwrap();
+    let mut lcs = cs.lookup_constraint_system.unwrap();
+    assert_eq!(lcs.fixed_tables, [LookupTableSpec { id: 0, len: 10 }]);
+
+    // remove the trailing `fixed_tables` from the array of the fields,
+    // as in lookup constraint systems serialized before they were recorded
+    lcs.fixed_tables = vec![];
+    let mut bytes = rmp_serde::to_vec(&lcs).unwrap();
+    assert_eq!(bytes[0], 0x90 + 10);
+    assert_eq!(bytes.pop(), Some(0x90));
+    bytes[0] = 0x90 + 9;
+
+    let old: LookupConstraintSystem<Fp> = rmp_serde::from_slice(&bytes).unwrap();
+    assert!(old.fixed_tables.is_empty());
+    assert_eq!(old.lookup_table, lcs.lookup_table);
+    assert_eq!(old.runtime_table_offset, lcs.runtime_table_offset);
+}
+
 // TODO: add a test with a runtime table with ID 0 (it should panic)
@@ -1,5 +1,12 @@
+mod acir;
 mod and;
+mod batch;
 mod chacha;
+mod circom;
+mod container;
+mod description;
+mod deterministic;
+mod diff;
 mod ec;
 mod endomul;
 mod endomul_scalar;
@@ -7,13 +14,20 @@ mod foreign_field_add;
 mod foreign_field_mul;
 mod framework;
 mod generic;
+mod index_mismatch;
 mod lookup;
+mod non_zk;
 mod not;
+mod pcs;
 mod poseidon;
+mod prover_hook;
 mod range_check;
 mod recursion;
 mod rot;
 mod serde;
+mod stats;
+mod transcript;
 mod turshi;
 mod varbasemul;
+mod verify_errors;
 mod xor;
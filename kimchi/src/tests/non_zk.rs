@@ -0,0 +1,224 @@
+use super::framework::generic_circuit;
+use crate::circuits::{
+    constraints::ConstraintSystem,
+    gate::{CircuitGate, GateType},
+    lookup::tables::LookupTable,
+    polynomials::permutation::ZK_ROWS,
+    wires::{Wire, COLUMNS},
+};
+use crate::{proof::ProverProof, prover_index::ProverIndex, verifier::verify};
+use ark_ff::Zero;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain as D};
+use commitment_dlog::{
+    commitment::{is_sparse, CommitmentCurve},
+    srs::endos,
+    srs::SRS,
+    PolyComm,
+};
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use std::{array, sync::Arc};
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+fn create_index(
+    gates: Vec<CircuitGate<Fp>>,
+    public: usize,
+    lookup_tables: Vec<LookupTable<Fp>>,
+    zero_knowledge: bool,
+) -> ProverIndex<Vesta> {
+    let cs = ConstraintSystem::<Fp>::create(gates)
+        .public(public)
+        .lookup(lookup_tables)
+        .zero_knowledge(zero_knowledge)
+        .build()
+        .unwrap();
+    let mut srs = SRS::<Vesta>::create(cs.domain.d1.size());
+    srs.add_lagrange_basis(cs.domain.d1);
+
+    let (endo_q, _endo_r) = endos::<<Vesta as CommitmentCurve>::OtherCurve>();
+    ProverIndex::<Vesta>::create(cs, endo_q, Arc::new(srs))
+}
+
+fn prove_and_verify(index: &ProverIndex<Vesta>, witness: [Vec<Fp>; COLUMNS]) -> ProverProof<Vesta> {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proof =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], index).unwrap();
+
+    let verifier_index = index.verifier_index();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+
+    proof
+}
+
+/// Commits to evaluations over `domain` with a dense multi-scalar multiplication of its Lagrange basis
+fn dense_commitment(srs: &SRS<Vesta>, domain: D<Fp>, evals: &[Fp]) -> PolyComm<Vesta> {
+    let basis: Vec<_> = srs.lagrange_bases[&domain.size()].iter().collect();
+    PolyComm::multi_scalar_mul(&basis, evals)
+}
+
+#[test]
+fn test_non_zk_generic() {
+    let (mut gates, mut witness) = generic_circuit::<Fp>(5);
+
+    // fill the circuit up to one row less than a power of two,
+    // so that only the non-zero-knowledge circuit fits in the smaller domain
+    let num_rows = (gates.len() + 1).next_power_of_two() - 1;
+    while gates.len() < num_rows {
+        gates.push(CircuitGate::zero(Wire::for_row(gates.len())));
+    }
+    for col in &mut witness {
+        col.resize(num_rows, Fp::zero());
+    }
+
+    let zk_index = create_index(gates.clone(), 5, vec![], true);
+    let index = create_index(gates, 5, vec![], false);
+
+    assert_eq!(zk_index.cs.zk_rows, ZK_ROWS);
+    assert_eq!(index.cs.zk_rows, 1);
+    assert_eq!(index.cs.domain.d1.size() * 2, zk_index.cs.domain.d1.size());
+
+    let verifier_index = index.verifier_index();
+    assert!(!verifier_index.zero_knowledge);
+    assert_eq!(verifier_index.zk_rows, 1);
+
+    prove_and_verify(&zk_index, witness.clone());
+
+    // without zero-knowledge, the witness commitments are deterministic
+    let proof1 = prove_and_verify(&index, witness.clone());
+    let proof2 = prove_and_verify(&index, witness);
+    assert_eq!(proof1.commitments.w_comm, proof2.commitments.w_comm);
+}
+
+#[test]
+fn test_non_zk_lookup() {
+    let num_lookups = 20;
+    let table_values: Vec<Fp> = (0..32u64).map(|i| Fp::from(i * i)).collect();
+    let lookup_table = LookupTable {
+        id: 0,
+        data: vec![(0..32u64).map(Into::into).collect(), table_values.clone()],
+    };
+
+    let gates = (0..num_lookups)
+        .map(|i| CircuitGate::new(GateType::Lookup, Wire::for_row(i), vec![]))
+        .collect();
+
+    let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); num_lookups]);
+    for row in 0..num_lookups {
+        // each lookup gate performs 3 lookups into table 0
+        for i in 0..3 {
+            let index = (row + i) % table_values.len();
+            witness[1 + 2 * i][row] = (index as u64).into();
+            witness[2 + 2 * i][row] = table_values[index];
+        }
+    }
+
+    let index = create_index(gates, 0, vec![lookup_table], false);
+
+    // lookups always reserve `ZK_ROWS` rows
+    assert_eq!(index.cs.zk_rows, ZK_ROWS);
+
+    prove_and_verify(&index, witness);
+}
+
+#[test]
+fn test_non_zk_digest() {
+    // with lookups, both indexes reserve `ZK_ROWS` rows and have the same commitments
+    let lookup_table = LookupTable {
+        id: 0,
+        data: vec![
+            (0..8u64).map(Into::into).collect(),
+            (0..8u64).map(|i| Fp::from(i * i)).collect(),
+        ],
+    };
+    let gates: Vec<_> = (0..4)
+        .map(|i| CircuitGate::new(GateType::Lookup, Wire::for_row(i), vec![]))
+        .collect();
+
+    let zk_index = create_index(gates.clone(), 0, vec![lookup_table.clone()], true);
+    let index = create_index(gates, 0, vec![lookup_table], false);
+    assert_eq!(zk_index.cs.zk_rows, index.cs.zk_rows);
+    assert_eq!(zk_index.cs.domain.d1.size(), index.cs.domain.d1.size());
+
+    let zk_verifier_index = zk_index.verifier_index();
+    let verifier_index = index.verifier_index();
+    assert_eq!(zk_verifier_index.sigma_comm, verifier_index.sigma_comm);
+    assert_ne!(
+        zk_verifier_index.digest::<BaseSponge>(),
+        verifier_index.digest::<BaseSponge>()
+    );
+}
+
+#[test]
+fn test_deserialize_without_zk_fields() {
+    let (gates, _) = generic_circuit::<Fp>(5);
+    let cs = ConstraintSystem::<Fp>::create(gates)
+        .public(5)
+        .build()
+        .unwrap();
+
+    // remove the trailing `zero_knowledge` and `zk_rows` from the array of the fields,
+    // as in constraint systems serialized before they existed
+    let mut bytes = rmp_serde::to_vec(&cs).unwrap();
+    assert_eq!(bytes[0], 0x90 + 12);
+    assert_eq!(bytes[bytes.len() - 2..], [0xc3, ZK_ROWS as u8]);
+    bytes[0] = 0x90 + 10;
+    bytes.truncate(bytes.len() - 2);
+
+    let old: ConstraintSystem<Fp> = rmp_serde::from_slice(&bytes).unwrap();
+    assert!(old.zero_knowledge);
+    assert_eq!(old.zk_rows, ZK_ROWS);
+    assert_eq!(old.gates, cs.gates);
+}
+
+#[test]
+fn test_non_zk_sparse_commitments() {
+    // the generic circuit, padded so that most of its columns are sparse
+    let (mut gates, mut witness) = generic_circuit::<Fp>(5);
+    while gates.len() < 63 {
+        gates.push(CircuitGate::zero(Wire::for_row(gates.len())));
+    }
+    for col in &mut witness {
+        col.resize(gates.len(), Fp::zero());
+    }
+    let index = create_index(gates, 5, vec![], false);
+    let d1 = index.cs.domain.d1;
+    let n = d1.size();
+    assert_eq!(n, 64);
+
+    // the commitments to the sparse coefficient columns of the index are the dense ones
+    let verifier_index = index.verifier_index();
+    let coefficients: Vec<Vec<Fp>> = index
+        .column_evaluations
+        .coefficients8
+        .iter()
+        .map(|evals8| (0..n).map(|i| evals8.evals[8 * i]).collect())
+        .collect();
+    assert!(coefficients.iter().any(|c| is_sparse(c)));
+    for (c, comm) in coefficients.iter().zip(&verifier_index.coefficients_comm) {
+        assert_eq!(*comm, dense_commitment(&index.srs, d1, c));
+    }
+
+    // so are the unblinded commitments to the sparse witness columns, padded with zeros
+    let proof = prove_and_verify(&index, witness.clone());
+    let padded: Vec<Vec<Fp>> = witness
+        .into_iter()
+        .map(|mut w| {
+            w.resize(n, Fp::zero());
+            w
+        })
+        .collect();
+    assert!(padded
+        .iter()
+        .any(|w| is_sparse(w) && w.iter().any(|x| !x.is_zero())));
+    assert!(padded.iter().any(|w| !is_sparse(w)));
+    for (w, comm) in padded.iter().zip(&proof.commitments.w_comm) {
+        assert_eq!(*comm, dense_commitment(&index.srs, d1, w));
+    }
+}
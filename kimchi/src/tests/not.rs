@@ -440,7 +440,7 @@ fn test_bad_not_gnrc() {
     let index =
         new_index_for_test_with_lookups(cs.gates, 1, 0, vec![xor::lookup_table()], None, false);
     assert_eq!(
-        index.cs.gates[1].verify::<Vesta>(1, &witness, &index, &[]),
+        index.cs.gates[1].verify::<Vesta, _>(1, &witness, &index, &[]),
         Err(("generic: incorrect gate").to_string())
     );
 }
@@ -0,0 +1,197 @@
+use super::framework::generic_circuit;
+use crate::circuits::constraints::ConstraintSystem;
+use crate::{
+    error::VerifyError,
+    proof::ProverProof,
+    prover_index::ProverIndex,
+    verifier::{batch_verify_with_fallback_with_pcs, batch_verify_with_pcs, verify_with_pcs},
+};
+use ark_ec::msm::VariableBaseMSM;
+use ark_ff::{One, PrimeField, Zero};
+use ark_poly::{univariate::DensePolynomial, EvaluationDomain, Polynomial};
+use commitment_dlog::{
+    commitment::{BatchEvaluationProof, CommitmentCurve},
+    error::CommitmentError,
+    evaluation_proof::{DensePolynomialOrEvaluations, OpeningProof},
+    pcs::{combine_evaluations, combine_polys, CommitmentKey, OpenProof},
+    srs::{endos, SRS},
+    PolyComm,
+};
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Fq, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+    FqSponge,
+};
+use rand_core::{CryptoRng, RngCore};
+use std::sync::Arc;
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+/// A transparent polynomial commitment scheme over the Pedersen commitments of the [`SRS`]:
+/// the opening reveals the combination of the opened polynomials and of their blinders.
+/// It is neither succinct nor zero-knowledge,
+/// and only serves to check that kimchi works with another scheme than the inner-product argument.
+#[derive(Clone, Debug)]
+struct TransparentOpening {
+    combined: DensePolynomial<Fp>,
+    blinding: Fp,
+}
+
+impl OpenProof<Vesta> for TransparentOpening {
+    type SRS = SRS<Vesta>;
+
+    fn open<EFqSponge, RNG, D: EvaluationDomain<Fp>>(
+        srs: &Self::SRS,
+        _group_map: &<Vesta as CommitmentCurve>::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<Fp, D>,
+            Option<usize>,
+            PolyComm<Fp>,
+        )],
+        _elm: &[Fp],
+        polyscale: Fp,
+        _evalscale: Fp,
+        _sponge: EFqSponge,
+        _rng: &mut RNG,
+    ) -> Result<Self, CommitmentError>
+    where
+        EFqSponge: Clone + FqSponge<Fq, Vesta, Fp>,
+        RNG: RngCore + CryptoRng,
+    {
+        let (combined, blinding) = combine_polys(plnms, polyscale, srs.max_poly_size());
+        Ok(TransparentOpening { combined, blinding })
+    }
+
+    fn verify<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        _group_map: &<Vesta as CommitmentCurve>::Map,
+        batch: &mut [BatchEvaluationProof<Vesta, EFqSponge, Self>],
+        _rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<Fq, Vesta, Fp>,
+        RNG: RngCore + CryptoRng,
+    {
+        batch.iter().all(|proof| {
+            let (commitments, evals) = match combine_evaluations(
+                &proof.evaluations,
+                proof.polyscale,
+                proof.evaluation_points.len(),
+            ) {
+                Some(combined) => combined,
+                None => return false,
+            };
+            let opening = proof.opening;
+
+            // the combined commitment is the commitment to the revealed polynomial
+            let (mut points, mut scalars): (Vec<_>, Vec<_>) = commitments.into_iter().unzip();
+            points.push(srs.h);
+            scalars.push(-opening.blinding);
+            let commitment = srs.commit_non_hiding(&opening.combined, None);
+            for chunk in commitment.unshifted {
+                points.push(chunk);
+                scalars.push(-Fp::one());
+            }
+            let scalars: Vec<_> = scalars.iter().map(PrimeField::into_repr).collect();
+            let opens = VariableBaseMSM::multi_scalar_mul(&points, &scalars).is_zero();
+
+            // and it has the combined evaluations
+            opens
+                && proof
+                    .evaluation_points
+                    .iter()
+                    .zip(&evals)
+                    .all(|(point, eval)| opening.combined.evaluate(point) == *eval)
+        })
+    }
+}
+
+/// Proves and verifies a circuit with public inputs,
+/// only relying on the polynomial commitment scheme traits.
+fn prove_and_verify<PCS: OpenProof<Vesta>>() -> (ProverIndex<Vesta, PCS>, ProverProof<Vesta, PCS>) {
+    let (gates, witness) = generic_circuit::<Fp>(5);
+
+    let cs = ConstraintSystem::<Fp>::create(gates)
+        .public(5)
+        .build()
+        .unwrap();
+    let mut srs = <PCS::SRS as CommitmentKey<Vesta>>::create(cs.domain.d1.size());
+    srs.add_lagrange_basis(cs.domain.d1);
+    assert_eq!(
+        srs.lagrange_basis(cs.domain.d1.size()).map(<[_]>::len),
+        Some(cs.domain.d1.size())
+    );
+
+    let (endo_q, _endo_r) = endos::<<Vesta as CommitmentCurve>::OtherCurve>();
+    let index = ProverIndex::<Vesta, PCS>::create(cs, endo_q, Arc::new(srs));
+
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proof =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index).unwrap();
+    verify_with_pcs::<Vesta, BaseSponge, ScalarSponge, PCS>(
+        &group_map,
+        &index.verifier_index(),
+        &proof,
+    )
+    .unwrap();
+
+    (index, proof)
+}
+
+#[test]
+fn test_pcs_inner_product_argument() {
+    let (_, proof) = prove_and_verify::<OpeningProof<Vesta>>();
+
+    // the inner-product argument is the default scheme
+    let _: ProverProof<Vesta> = proof;
+}
+
+#[test]
+fn test_pcs_transparent() {
+    let (index, proof) = prove_and_verify::<TransparentOpening>();
+    let verifier_index = index.verifier_index();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // proofs of the scheme can be batched
+    batch_verify_with_pcs::<Vesta, BaseSponge, ScalarSponge, TransparentOpening>(
+        &group_map,
+        &[(&verifier_index, &proof), (&verifier_index, &proof)],
+    )
+    .unwrap();
+
+    // and an incorrect opening is detected by the scheme
+    let mut wrong = proof.clone();
+    wrong.proof.combined.coeffs[0] += Fp::one();
+    let res = verify_with_pcs::<Vesta, BaseSponge, ScalarSponge, TransparentOpening>(
+        &group_map,
+        &verifier_index,
+        &wrong,
+    );
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results =
+        batch_verify_with_fallback_with_pcs::<Vesta, BaseSponge, ScalarSponge, TransparentOpening>(
+            &group_map,
+            &[(&verifier_index, &wrong)],
+        );
+    assert!(matches!(results[0], Err(VerifyError::CombinedInnerProduct)));
+
+    // as well as an incorrect evaluation
+    let mut wrong = proof;
+    wrong.ft_eval1 += Fp::one();
+    let res = verify_with_pcs::<Vesta, BaseSponge, ScalarSponge, TransparentOpening>(
+        &group_map,
+        &verifier_index,
+        &wrong,
+    );
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results =
+        batch_verify_with_fallback_with_pcs::<Vesta, BaseSponge, ScalarSponge, TransparentOpening>(
+            &group_map,
+            &[(&verifier_index, &wrong)],
+        );
+    assert!(matches!(results[0], Err(VerifyError::CombinedInnerProduct)));
+}
@@ -0,0 +1,156 @@
+use super::framework::generic_index;
+use crate::circuits::{argument::ArgumentType, wires::COLUMNS};
+use crate::{
+    error::ProverError,
+    proof::ProverProof,
+    prover_hook::ProverStep,
+    verifier::{batch_verify, verify},
+};
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+#[test]
+fn test_hook_reports_steps() {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let steps = RefCell::new(vec![]);
+    let hook = |step: ProverStep| -> Result<(), ProverError> {
+        steps.borrow_mut().push(step);
+        Ok(())
+    };
+
+    let proof = ProverProof::create_recursive_with_hook::<BaseSponge, ScalarSponge>(
+        &group_map,
+        witness,
+        &[],
+        &index,
+        vec![],
+        None,
+        &hook,
+    )
+    .unwrap();
+
+    let steps = steps.into_inner();
+    assert_eq!(steps[0], ProverStep::WitnessCommitment(0));
+    assert_eq!(
+        steps[COLUMNS - 1],
+        ProverStep::WitnessCommitment(COLUMNS - 1)
+    );
+    assert!(!steps.contains(&ProverStep::Lookup));
+    assert!(steps.contains(&ProverStep::Quotient(ArgumentType::Permutation)));
+    assert!(steps.contains(&ProverStep::QuotientCommitment(0)));
+
+    // each round of the opening proof is reported
+    let opening = steps
+        .iter()
+        .position(|s| *s == ProverStep::Opening)
+        .unwrap();
+    assert_eq!(opening + 1 + proof.proof.lr.len(), steps.len());
+    for (round, step) in steps[opening + 1..].iter().enumerate() {
+        assert_eq!(*step, ProverStep::OpeningRound(round));
+    }
+
+    let verifier_index = index.verifier_index();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+}
+
+#[test]
+fn test_hook_cancels_proof() {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let steps = RefCell::new(vec![]);
+    let hook = |step: ProverStep| -> Result<(), ProverError> {
+        if step == ProverStep::Permutation {
+            return Err(ProverError::Cancelled);
+        }
+        steps.borrow_mut().push(step);
+        Ok(())
+    };
+
+    let res = ProverProof::create_recursive_with_hook::<BaseSponge, ScalarSponge>(
+        &group_map,
+        witness,
+        &[],
+        &index,
+        vec![],
+        None,
+        &hook,
+    );
+    assert!(matches!(res, Err(ProverError::Cancelled)));
+
+    // the prover stopped at the permutation step
+    assert_eq!(
+        steps.into_inner().last(),
+        Some(&ProverStep::WitnessCommitment(COLUMNS - 1))
+    );
+}
+
+#[test]
+fn test_hook_cancels_opening() {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // the opening proof can be cancelled between its rounds
+    let hook = |step: ProverStep| -> Result<(), ProverError> {
+        if step == ProverStep::OpeningRound(1) {
+            return Err(ProverError::Cancelled);
+        }
+        Ok(())
+    };
+
+    let res = ProverProof::create_recursive_with_rng::<BaseSponge, ScalarSponge, _>(
+        &group_map,
+        witness,
+        &[],
+        &index,
+        vec![],
+        None,
+        &mut StdRng::from_seed([0u8; 32]),
+        Some(&hook),
+    );
+    assert!(matches!(res, Err(ProverError::Cancelled)));
+}
+
+#[test]
+fn test_hook_in_batch() {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let openings = AtomicUsize::new(0);
+    let hook = |step: ProverStep| -> Result<(), ProverError> {
+        if step == ProverStep::Opening {
+            openings.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(())
+    };
+
+    let proofs = ProverProof::create_batch::<BaseSponge, ScalarSponge>(
+        &group_map,
+        vec![witness.clone().into(), witness.into()],
+        &index,
+        usize::MAX,
+        Some(&hook),
+    );
+    assert_eq!(openings.into_inner(), 2);
+
+    let verifier_index = index.verifier_index();
+    let proofs: Vec<_> = proofs.into_iter().map(Result::unwrap).collect();
+    let batch: Vec<_> = proofs.iter().map(|p| (&verifier_index, p)).collect();
+    batch_verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch).unwrap();
+}
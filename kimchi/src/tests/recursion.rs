@@ -1,11 +1,16 @@
 use super::framework::TestFramework;
 use crate::circuits::polynomials::generic::testing::{create_circuit, fill_in_witness};
 use crate::circuits::wires::COLUMNS;
-use crate::proof::RecursionChallenge;
+use crate::{
+    proof::{ProverProof, RecursionChallenge},
+    prover_index::testing::{new_index_for_test, new_index_for_test_with_lookups},
+    verifier::{succinct_verify, verify},
+};
 use ark_ff::{UniformRand, Zero};
 use ark_poly::univariate::DensePolynomial;
 use ark_poly::UVPolynomial;
-use commitment_dlog::commitment::b_poly_coefficients;
+use commitment_dlog::commitment::{b_poly_coefficients, CommitmentCurve};
+use groupmap::GroupMap;
 use mina_curves::pasta::{Fp, Vesta, VestaParameters};
 use mina_poseidon::{
     constants::PlonkSpongeConstantsKimchi,
@@ -53,3 +58,43 @@ fn test_recursion() {
         .prove_and_verify::<BaseSponge, ScalarSponge>()
         .is_ok());
 }
+
+#[test]
+fn test_recursion_with_accumulator() {
+    let gates = create_circuit(0, 0);
+    let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); gates.len()]);
+    fill_in_witness(0, &mut witness, &[]);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // a first proof, whose opening is only checked succinctly
+    let index = new_index_for_test::<Vesta>(gates.clone(), 0);
+    let proof =
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness.clone(), &[], &index)
+            .unwrap();
+    let verifier_index = index.verifier_index();
+    let accumulators = succinct_verify::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index, &proof)],
+    )
+    .unwrap();
+    assert_eq!(accumulators.len(), 1);
+    assert!(index.srs.decide(&accumulators, &mut thread_rng()));
+
+    // its accumulator is passed to the next proof, whose verification decides it
+    let index = new_index_for_test_with_lookups::<Vesta>(gates, 0, 1, vec![], None, false);
+    let prev_challenges = accumulators
+        .accumulators
+        .into_iter()
+        .map(RecursionChallenge::from)
+        .collect();
+    let proof = ProverProof::create_recursive::<BaseSponge, ScalarSponge>(
+        &group_map,
+        witness,
+        &[],
+        &index,
+        prev_challenges,
+        None,
+    )
+    .unwrap();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index.verifier_index(), &proof).unwrap();
+}
@@ -1,9 +1,11 @@
 use crate::{
     bench::BenchmarkCtx,
     circuits::{
+        gate::CircuitGate,
         polynomials::generic::testing::{create_circuit, fill_in_witness},
         wires::COLUMNS,
     },
+    json::{Json, JSON_SCHEMA},
     proof::ProverProof,
     prover_index::testing::new_index_for_test,
     verifier::verify,
@@ -18,6 +20,7 @@ use mina_poseidon::{
     constants::PlonkSpongeConstantsKimchi,
     sponge::{DefaultFqSponge, DefaultFrSponge},
 };
+use serde_json::Value;
 use std::array;
 use std::time::Instant;
 
@@ -25,6 +28,102 @@ type SpongeParams = PlonkSpongeConstantsKimchi;
 type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
 type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
 
+/// Checks that `value` follows the definition `def` of [JSON_SCHEMA].
+/// This only supports the subset of JSON schema used by [JSON_SCHEMA].
+fn check_schema(schema: &Value, def: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let def = def.as_object().unwrap();
+    let ensure = |ok: bool, msg: &str| {
+        if ok {
+            Ok(())
+        } else {
+            Err(format!("{path}: {msg}"))
+        }
+    };
+
+    if let Some(Value::String(reference)) = def.get("$ref") {
+        let name = reference.strip_prefix("#/$defs/").unwrap();
+        check_schema(schema, &schema["$defs"][name], value, path)?;
+    }
+
+    if let Some(Value::Array(variants)) = def.get("oneOf") {
+        let matching = variants
+            .iter()
+            .filter(|variant| check_schema(schema, variant, value, path).is_ok())
+            .count();
+        ensure(matching == 1, "expected exactly one matching variant")?;
+    }
+
+    if let Some(Value::Array(variants)) = def.get("enum") {
+        ensure(variants.contains(value), "unexpected value")?;
+    }
+
+    if let Some(Value::String(typ)) = def.get("type") {
+        let ok = match typ.as_str() {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_u64(),
+            "string" => value.is_string(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => panic!("unsupported type {typ}"),
+        };
+        ensure(ok, &format!("expected {typ}"))?;
+    }
+
+    if def.contains_key("pattern") {
+        let hex = value.as_str().unwrap_or_default();
+        let is_hex = hex.len() % 2 == 0 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f'));
+        ensure(is_hex, "expected a hex string")?;
+    }
+
+    if let Value::Array(items) = value {
+        let len = items.len() as u64;
+        if let Some(min) = def.get("minItems").and_then(Value::as_u64) {
+            ensure(len >= min, "too few items")?;
+        }
+        if let Some(max) = def.get("maxItems").and_then(Value::as_u64) {
+            ensure(len <= max, "too many items")?;
+        }
+        let prefix = def.get("prefixItems").and_then(Value::as_array);
+        for (i, item) in items.iter().enumerate() {
+            let item_def = prefix
+                .and_then(|prefix| prefix.get(i))
+                .or_else(|| def.get("items"));
+            if let Some(item_def) = item_def {
+                check_schema(schema, item_def, item, &format!("{path}/{i}"))?;
+            }
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(Value::Array(required)) = def.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                ensure(fields.contains_key(name), &format!("missing field {name}"))?;
+            }
+        }
+        if let Some(properties) = def.get("properties").and_then(Value::as_object) {
+            let closed = def.get("additionalProperties") == Some(&Value::Bool(false));
+            for (name, field) in fields {
+                match properties.get(name) {
+                    Some(field_def) => {
+                        check_schema(schema, field_def, field, &format!("{path}/{name}"))?
+                    }
+                    None => ensure(!closed, &format!("unexpected field {name}"))?,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that `json` follows the definition `name` of [JSON_SCHEMA].
+pub(super) fn check_json(json: &str, name: &str) {
+    let schema: Value = serde_json::from_str(JSON_SCHEMA).unwrap();
+    let value: Value = serde_json::from_str(json).unwrap();
+    check_schema(&schema, &schema["$defs"][name], &value, "#").unwrap();
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -88,4 +187,65 @@ mod tests {
             .unwrap();
         println!("- time to verify: {}ms", start.elapsed().as_millis());
     }
+
+    #[test]
+    fn test_json_proof() {
+        let ctx = BenchmarkCtx::new(1 << 4);
+        let proof = ctx.create_proof();
+
+        let json = proof.to_json().unwrap();
+        check_json(&json, "Proof");
+
+        // field elements and points are hex strings
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert!(value["ft_eval1"].is_string());
+        assert!(value["proof"]["delta"].is_string());
+
+        // the hex encoding is specific to the canonical JSON:
+        // other human-readable serializers keep the byte arrays of the arkworks encodings
+        let plain = serde_json::to_value(&proof).unwrap();
+        assert!(plain["ft_eval1"].is_array());
+        assert!(plain["proof"]["delta"].is_array());
+
+        // export, import and export again gives the same JSON
+        let de_pf = ProverProof::<Vesta>::from_json(&json).unwrap();
+        assert_eq!(de_pf.to_json().unwrap(), json);
+
+        // the imported proof verifies
+        ctx.batch_verification(vec![de_pf]);
+    }
+
+    #[test]
+    fn test_json_verifier_index_gates_and_witness() {
+        let public = vec![Fp::from(3u8); 5];
+        let gates = create_circuit(0, public.len());
+
+        // gates round-trip
+        let gates_json = gates.to_json().unwrap();
+        check_json(&gates_json, "Gates");
+        let gates: Vec<CircuitGate<Fp>> = Json::from_json(&gates_json).unwrap();
+        assert_eq!(gates.to_json().unwrap(), gates_json);
+
+        // witness round-trip
+        let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); gates.len()]);
+        fill_in_witness(0, &mut witness, &public);
+        let witness_json = witness.to_json_pretty().unwrap();
+        check_json(&witness_json, "Witness");
+        let de_witness: [Vec<Fp>; COLUMNS] = Json::from_json(&witness_json).unwrap();
+        assert_eq!(de_witness, witness);
+
+        // verifier index round-trip
+        let index = new_index_for_test(gates, public.len());
+        let verifier_index_json = index.verifier_index().to_json().unwrap();
+        check_json(&verifier_index_json, "VerifierIndex");
+        let verifier_index = VerifierIndex::<Vesta>::from_json(&verifier_index_json).unwrap();
+        assert_eq!(verifier_index.to_json().unwrap(), verifier_index_json);
+
+        // the imported verifier index, gates and witness can be used to create and verify a proof
+        let group_map = <Vesta as CommitmentCurve>::Map::setup();
+        let proof =
+            ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, de_witness, &[], &index)
+                .unwrap();
+        verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+    }
 }
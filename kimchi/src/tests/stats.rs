@@ -0,0 +1,205 @@
+use crate::{
+    circuits::{
+        gate::{CircuitGate, GateType},
+        lookup::{
+            runtime_tables::{RuntimeTable, RuntimeTableCfg, RuntimeTableSpec},
+            tables::LookupTable,
+        },
+        polynomial::COLUMNS,
+        polynomials::generic::GenericGateSpec,
+        wires::Wire,
+    },
+    proof::ProverProof,
+    prover_index::testing::{new_index_for_test, new_index_for_test_with_lookups},
+    verifier::verify,
+};
+use ark_ff::Zero;
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+use std::{array, cell::Cell};
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+/// Counts the curve points and the field elements of a proof.
+fn proof_size(proof: &ProverProof<Vesta>) -> (usize, usize) {
+    let commitments = &proof.commitments;
+    let mut points: usize = commitments
+        .w_comm
+        .iter()
+        .chain([&commitments.z_comm, &commitments.t_comm])
+        .map(|comm| comm.unshifted.len())
+        .sum();
+    if let Some(lookup) = &commitments.lookup {
+        points += lookup
+            .sorted
+            .iter()
+            .chain([&lookup.aggreg])
+            .chain(&lookup.runtime)
+            .map(|comm| comm.unshifted.len())
+            .sum::<usize>();
+    }
+    points += 2 * proof.proof.lr.len() + 2;
+
+    let evals = Cell::new(0);
+    proof
+        .evals
+        .map_ref(&|e| evals.set(evals.get() + e.zeta.len() + e.zeta_omega.len()));
+    let optional =
+        usize::from(proof.verifier_index_digest.is_some()) + usize::from(proof.ft_eval0.is_some());
+    let scalars = evals.get() + 1 + proof.public.len() + 2 + optional;
+
+    (points, scalars)
+}
+
+#[test]
+fn test_stats_generic() {
+    let public = 3;
+    let mut gates: Vec<_> = (0..public)
+        .map(|row| {
+            CircuitGate::create_generic_gadget(Wire::for_row(row), GenericGateSpec::Pub, None)
+        })
+        .collect();
+    gates.push(CircuitGate::create_generic_gadget(
+        Wire::for_row(public),
+        GenericGateSpec::Const(Fp::zero()),
+        Some(GenericGateSpec::Const(Fp::zero())),
+    ));
+    gates.push(CircuitGate::new(
+        GateType::Zero,
+        Wire::for_row(public + 1),
+        vec![],
+    ));
+
+    let num_rows = gates.len();
+    let mut index = new_index_for_test::<Vesta>(gates, public);
+    let stats = index.cs.stats();
+
+    assert_eq!(stats.public_inputs, 3);
+    assert_eq!(stats.gates.get(&GateType::Generic), Some(&4));
+    // an unwired zero gate at the end of the circuit cannot be told apart from padding
+    assert_eq!(stats.gates.get(&GateType::Zero), None);
+    assert_eq!(stats.rows, 4);
+    assert_eq!(stats.domain_size, 8);
+    assert_eq!(stats.padding, 4);
+    assert_eq!(stats.zk_rows, 3);
+    assert!(stats.lookup.is_none());
+    assert!(stats.features().is_empty());
+
+    // the report of the prover index knows the size of the elements
+    let stats = index.stats();
+    assert!(stats.proof_size.bytes.is_some());
+
+    let report = stats.to_string();
+    assert!(report.contains("Generic"));
+    assert!(!report.contains("lookup tables"));
+
+    // the estimated proof size follows the settings of the index
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    for (include_verifier_index_digest, include_ft_eval0) in [(true, false), (false, true)] {
+        index.include_verifier_index_digest = include_verifier_index_digest;
+        index.include_ft_eval0 = include_ft_eval0;
+        let stats = index.stats();
+
+        let witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); num_rows]);
+        let proof =
+            ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index)
+                .unwrap();
+        assert_eq!(
+            proof_size(&proof),
+            (
+                stats.proof_size.group_elements,
+                stats.proof_size.field_elements
+            )
+        );
+    }
+}
+
+#[test]
+fn test_stats_lookups() {
+    // a fixed table that is not used, and a runtime table
+    let fixed_table = LookupTable {
+        id: 0,
+        data: vec![(0..10u64).map(Into::into).collect(); 2],
+    };
+    let runtime_table_cfg = RuntimeTableCfg::Indexed(RuntimeTableSpec { id: 1, len: 5 });
+    let runtime_table = RuntimeTable {
+        id: 1,
+        data: (10..15u64).map(Into::into).collect(),
+    };
+
+    let num_lookups = 20;
+    let gates = (0..num_lookups)
+        .map(|row| CircuitGate::new(GateType::Lookup, Wire::for_row(row), vec![]))
+        .collect();
+
+    // each row looks up the entry 3 of the runtime table three times
+    let mut witness: [_; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); num_lookups]);
+    for row in 0..num_lookups {
+        witness[0][row] = 1u64.into();
+        for lookup in 0..3 {
+            witness[2 * lookup + 1][row] = 3u64.into();
+            witness[2 * lookup + 2][row] = 13u64.into();
+        }
+    }
+
+    let index = new_index_for_test_with_lookups::<Vesta>(
+        gates,
+        0,
+        0,
+        vec![fixed_table],
+        Some(vec![runtime_table_cfg]),
+        false,
+    );
+    let stats = index.stats();
+
+    assert_eq!(stats.gates.get(&GateType::Lookup), Some(&num_lookups));
+    assert_eq!(stats.rows, num_lookups);
+    assert_eq!(stats.domain_size, 32);
+    assert_eq!(stats.padding, 12);
+
+    let lookup = stats.lookup.as_ref().unwrap();
+    assert_eq!(lookup.tables.len(), 1);
+    assert_eq!(lookup.tables[0].id, 0);
+    assert_eq!(lookup.tables[0].len, 10);
+    assert_eq!(lookup.tables[0].lookups, 0);
+    assert_eq!(lookup.witness_table_lookups, 3 * num_lookups);
+    assert_eq!(lookup.runtime_tables.len(), 1);
+    assert_eq!(lookup.runtime_tables[0].len, 5);
+    assert_eq!(lookup.max_per_row, 3);
+    assert_eq!(lookup.max_joint_size, 2);
+    assert_eq!(lookup.table_entries, 15);
+    assert_eq!(lookup.table_capacity, 27);
+
+    let features = stats.features();
+    assert!(features.contains(&"lookup_pattern_lookup"));
+    assert!(features.contains(&"runtime_tables"));
+
+    let report = stats.to_string();
+    assert!(report.contains("1 (runtime)"));
+
+    // the estimated proof size matches the proof
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proof = ProverProof::create::<BaseSponge, ScalarSponge>(
+        &group_map,
+        witness,
+        &[runtime_table],
+        &index,
+    )
+    .unwrap();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &index.verifier_index(), &proof).unwrap();
+
+    assert_eq!(
+        proof_size(&proof),
+        (
+            stats.proof_size.group_elements,
+            stats.proof_size.field_elements
+        )
+    );
+}
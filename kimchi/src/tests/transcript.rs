@@ -0,0 +1,85 @@
+use super::framework::generic_index;
+use crate::{
+    proof::ProverProof,
+    transcript::{self, TranscriptSponge},
+    verifier::verify,
+};
+use ark_ff::One;
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = TranscriptSponge<DefaultFqSponge<VestaParameters, SpongeParams>>;
+type ScalarSponge = TranscriptSponge<DefaultFrSponge<Fp, SpongeParams>>;
+
+fn create_proof() -> (
+    ProverProof<Vesta>,
+    transcript::Transcript,
+    crate::verifier_index::VerifierIndex<Vesta>,
+) {
+    let (index, witness) = generic_index::<Vesta>(5);
+    let verifier_index = index.verifier_index();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let (proof, prover_transcript) = transcript::record(|| {
+        ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index)
+    });
+
+    (proof.unwrap(), prover_transcript, verifier_index)
+}
+
+#[test]
+fn test_transcripts_match() {
+    let (proof, prover_transcript, verifier_index) = create_proof();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let (res, verifier_transcript) = transcript::record(|| {
+        verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof)
+    });
+    assert!(res.is_ok());
+
+    assert!(!prover_transcript.entries.is_empty());
+    assert_eq!(
+        transcript::diff(&prover_transcript, &verifier_transcript),
+        None
+    );
+}
+
+#[test]
+fn test_transcripts_diverge() {
+    let (mut proof, prover_transcript, verifier_index) = create_proof();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    // tamper with the evaluation of ft, which is absorbed by the Fr-Sponge
+    proof.ft_eval1 += Fp::one();
+
+    let (res, verifier_transcript) = transcript::record(|| {
+        verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof)
+    });
+    assert!(res.is_err());
+
+    let divergence = transcript::diff(&prover_transcript, &verifier_transcript)
+        .expect("transcripts should diverge");
+    assert!(divergence.to_string().starts_with(&format!(
+        "transcripts diverge at entry #{}",
+        divergence.index
+    )));
+    let prover_entry = divergence.prover.expect("missing prover entry");
+    assert_eq!(prover_entry.phase, "evaluations");
+    assert_eq!(prover_entry.sponge, transcript::SpongeKind::Fr);
+}
+
+#[test]
+fn test_nothing_recorded_outside_of_record() {
+    let (_, recorded) = transcript::record(|| ());
+    assert!(recorded.entries.is_empty());
+
+    // sponges keep working when nothing is being recorded
+    let (proof, _, verifier_index) = create_proof();
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof).unwrap();
+}
@@ -0,0 +1,142 @@
+use super::framework::generic_index;
+use crate::{
+    error::VerifyError,
+    proof::ProverProof,
+    verifier::{batch_verify, batch_verify_with_fallback, verify},
+    verifier_index::VerifierIndex,
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::One;
+use commitment_dlog::commitment::CommitmentCurve;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+};
+type SpongeParams = PlonkSpongeConstantsKimchi;
+type BaseSponge = DefaultFqSponge<VestaParameters, SpongeParams>;
+type ScalarSponge = DefaultFrSponge<Fp, SpongeParams>;
+
+fn create_proofs(count: usize) -> (VerifierIndex<Vesta>, Vec<ProverProof<Vesta>>) {
+    let (mut index, witness) = generic_index::<Vesta>(5);
+    index.include_ft_eval0 = true;
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let proofs = (0..count)
+        .map(|_| {
+            ProverProof::create::<BaseSponge, ScalarSponge>(
+                &group_map,
+                witness.clone(),
+                &[],
+                &index,
+            )
+            .unwrap()
+        })
+        .collect();
+
+    (index.verifier_index(), proofs)
+}
+
+#[test]
+fn test_incorrect_evaluation() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (verifier_index, mut proofs) = create_proofs(1);
+
+    proofs[0].ft_eval1 += Fp::one();
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proofs[0]);
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index, &proofs[0])],
+    );
+    assert!(matches!(results[0], Err(VerifyError::CombinedInnerProduct)));
+}
+
+#[test]
+fn test_incorrect_opening_equation() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (verifier_index, mut proofs) = create_proofs(1);
+
+    proofs[0].proof.z1 += Fp::one();
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proofs[0]);
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index, &proofs[0])],
+    );
+    assert!(matches!(results[0], Err(VerifyError::CombinedInnerProduct)));
+}
+
+#[test]
+fn test_incorrect_challenge_polynomial() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (verifier_index, mut proofs) = create_proofs(1);
+
+    // sg + h with z2 - z1 still satisfies the opening equation,
+    // but is not the commitment to the challenge polynomial anymore
+    let opening = &mut proofs[0].proof;
+    opening.sg =
+        (opening.sg.into_projective() + verifier_index.srs().h.into_projective()).into_affine();
+    opening.z2 -= opening.z1;
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proofs[0]);
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index, &proofs[0])],
+    );
+    assert!(matches!(results[0], Err(VerifyError::ChallengePolynomial)));
+}
+
+#[test]
+fn test_ft_eval0_mismatch() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (verifier_index, mut proofs) = create_proofs(1);
+
+    // the evaluations do not satisfy the constraints for another public input
+    proofs[0].public[0] += Fp::one();
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proofs[0]);
+    assert!(matches!(res, Err(VerifyError::FtEval0Mismatch)));
+
+    // without the evaluation of the prover, the mismatch is only detected by the opening
+    proofs[0].ft_eval0 = None;
+    let res = verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proofs[0]);
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(
+        &group_map,
+        &[(&verifier_index, &proofs[0])],
+    );
+    assert!(matches!(results[0], Err(VerifyError::CombinedInnerProduct)));
+}
+
+#[test]
+fn test_batch_fallback() {
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let (verifier_index, mut proofs) = create_proofs(3);
+
+    // all valid: the batch verifies
+    let batch: Vec<_> = proofs.iter().map(|p| (&verifier_index, p)).collect();
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch);
+    assert!(results.iter().all(Result::is_ok));
+
+    // one invalid proof makes the whole batch fail
+    proofs[1].proof.z2 += Fp::one();
+    let batch: Vec<_> = proofs.iter().map(|p| (&verifier_index, p)).collect();
+    let res = batch_verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch);
+    assert!(matches!(res, Err(VerifyError::OpenProof)));
+
+    // the fallback gives the result of each proof
+    let results = batch_verify_with_fallback::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(VerifyError::CombinedInnerProduct)));
+    assert!(results[2].is_ok());
+
+    // errors found before the opening are attributed to their proof too
+    proofs[1].proof.z2 -= Fp::one();
+    proofs[2].public[0] += Fp::one();
+    let batch: Vec<_> = proofs.iter().map(|p| (&verifier_index, p)).collect();
+    let res = batch_verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &batch);
+    assert!(matches!(
+        res,
+        Err(VerifyError::BatchProofFailed(2, err)) if matches!(*err, VerifyError::FtEval0Mismatch)
+    ));
+}
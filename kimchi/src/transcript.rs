@@ -0,0 +1,397 @@
+//! This module implements a transcript-recording wrapper for the Fq-Sponge and the Fr-Sponge.
+//!
+//! Wrapping the sponges given to [`ProverProof::create`](crate::proof::ProverProof) and
+//! [`verify`](crate::verifier::verify) in a [`TranscriptSponge`] records every element
+//! absorbed and every challenge squeezed, labelled with the phase of the protocol it belongs to.
+//! A prover transcript and a verifier transcript can then be compared with [`diff`],
+//! which points at the first place where the two sides of the Fiat-Shamir argument disagree.
+//!
+//! ```ignore
+//! type BaseSponge = TranscriptSponge<DefaultFqSponge<VestaParameters, SC>>;
+//! type ScalarSponge = TranscriptSponge<DefaultFrSponge<Fp, SC>>;
+//!
+//! let (proof, prover) = transcript::record(|| {
+//!     ProverProof::create::<BaseSponge, ScalarSponge>(&group_map, witness, &[], &index)
+//! });
+//! let (res, verifier) = transcript::record(|| {
+//!     verify::<Vesta, BaseSponge, ScalarSponge>(&group_map, &verifier_index, &proof.unwrap())
+//! });
+//! if let Some(divergence) = transcript::diff(&prover, &verifier) {
+//!     println!("{divergence}");
+//! }
+//! ```
+//!
+//! Recording is done per thread: only the sponges used on the thread calling [`record`] are logged.
+//! When nothing is being recorded, the wrapper simply forwards to the inner sponge.
+
+use crate::{
+    plonk_sponge::FrSponge,
+    proof::{LookupEvaluations, PointEvaluations, ProofEvaluations},
+};
+use ark_ff::Field;
+use commitment_dlog::commitment::CommitmentCurve;
+use mina_poseidon::{poseidon::ArithmeticSpongeParams, sponge::ScalarChallenge, FqSponge};
+use o1_utils::FieldHelpers;
+use serde::{Deserialize, Serialize};
+use std::{cell::RefCell, fmt};
+
+/// The sponge an entry of the transcript was recorded from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpongeKind {
+    /// The sponge acting on the base field of the curve
+    Fq,
+    /// The sponge acting on the scalar field of the curve
+    Fr,
+}
+
+/// An operation performed on a sponge.
+/// Field elements are stored as (little-endian) hex strings,
+/// and curve points as the hex strings of their two coordinates.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TranscriptOp {
+    /// Elements absorbed by the sponge
+    Absorb(Vec<String>),
+    /// A challenge (or a digest) squeezed out of the sponge
+    Squeeze(String),
+}
+
+/// A single entry of a [`Transcript`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptEntry {
+    /// The phase of the protocol during which the operation happened
+    pub phase: String,
+    /// The sponge the operation was performed on
+    pub sponge: SpongeKind,
+    /// The operation itself
+    pub op: TranscriptOp,
+}
+
+/// The ordered list of sponge operations performed while creating or verifying a proof
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript {
+    pub entries: Vec<TranscriptEntry>,
+}
+
+/// The first point at which two transcripts disagree, as returned by [`diff`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The index of the first entry that differs
+    pub index: usize,
+    /// The entry of the prover transcript (if the transcript is long enough)
+    pub prover: Option<TranscriptEntry>,
+    /// The entry of the verifier transcript (if the transcript is long enough)
+    pub verifier: Option<TranscriptEntry>,
+}
+
+//
+// Recording
+//
+
+struct Recorder {
+    phase: &'static str,
+    paused: usize,
+    transcript: Transcript,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = RefCell::new(None);
+}
+
+/// Restores the previously installed recorder (if any) when dropped,
+/// so that a panic inside [`record`] does not leave the thread recording.
+struct RecordGuard(Option<Option<Recorder>>);
+
+impl RecordGuard {
+    fn finish(mut self) -> Transcript {
+        let previous = self.0.take().expect("guard is only finished once");
+        RECORDER
+            .with(|r| r.replace(previous))
+            .map(|recorder| recorder.transcript)
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for RecordGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            RECORDER.with(|r| r.replace(previous));
+        }
+    }
+}
+
+/// Runs `f` and returns its result along with the transcript of all the
+/// [`TranscriptSponge`] operations performed by the current thread while running it.
+pub fn record<T>(f: impl FnOnce() -> T) -> (T, Transcript) {
+    let previous = RECORDER.with(|r| {
+        r.replace(Some(Recorder {
+            phase: "",
+            paused: 0,
+            transcript: Transcript::default(),
+        }))
+    });
+    let guard = RecordGuard(Some(previous));
+    let res = f();
+    (res, guard.finish())
+}
+
+/// Sets the label attached to the next entries of the transcript being recorded, if any.
+pub fn phase(label: &'static str) {
+    RECORDER.with(|r| {
+        if let Some(recorder) = r.borrow_mut().as_mut() {
+            recorder.phase = label;
+        }
+    });
+}
+
+/// Stops recording until the returned guard is dropped.
+/// This is used for sponges that are not part of the proof transcript,
+/// like the one computing the digest of the verifier index
+/// (which the prover might have cached).
+pub(crate) fn pause() -> PauseGuard {
+    RECORDER.with(|r| {
+        if let Some(recorder) = r.borrow_mut().as_mut() {
+            recorder.paused += 1;
+        }
+    });
+    PauseGuard
+}
+
+/// See [`pause`].
+pub(crate) struct PauseGuard;
+
+impl Drop for PauseGuard {
+    fn drop(&mut self) {
+        RECORDER.with(|r| {
+            if let Some(recorder) = r.borrow_mut().as_mut() {
+                recorder.paused = recorder.paused.saturating_sub(1);
+            }
+        });
+    }
+}
+
+fn log(sponge: SpongeKind, op: impl FnOnce() -> TranscriptOp) {
+    RECORDER.with(|r| {
+        if let Some(recorder) = r.borrow_mut().as_mut() {
+            if recorder.paused == 0 {
+                recorder.transcript.entries.push(TranscriptEntry {
+                    phase: recorder.phase.to_string(),
+                    sponge,
+                    op: op(),
+                });
+            }
+        }
+    });
+}
+
+fn point_to_hex<G: CommitmentCurve>(g: &G) -> String {
+    match g.to_coordinates() {
+        None => "infinity".to_string(),
+        Some((x, y)) => format!("({}, {})", x.to_hex(), y.to_hex()),
+    }
+}
+
+fn evaluations_to_hex<F: Field>(e: &ProofEvaluations<PointEvaluations<Vec<F>>>) -> Vec<String> {
+    let ProofEvaluations {
+        w,
+        z,
+        s,
+        coefficients,
+        lookup,
+        generic_selector,
+        poseidon_selector,
+    } = e;
+
+    // same order as the one used by the Fr-Sponge
+    let mut points = vec![z, generic_selector, poseidon_selector];
+    points.extend(w.iter());
+    points.extend(coefficients.iter());
+    points.extend(s.iter());
+
+    if let Some(LookupEvaluations {
+        sorted,
+        aggreg,
+        table,
+        runtime,
+    }) = lookup
+    {
+        points.push(aggreg);
+        points.push(table);
+        points.extend(sorted.iter());
+        points.extend(runtime.iter());
+    }
+
+    points
+        .into_iter()
+        .flat_map(|p| p.zeta.iter().chain(p.zeta_omega.iter()))
+        .map(|x| x.to_hex())
+        .collect()
+}
+
+//
+// The sponge wrapper
+//
+
+/// A sponge that forwards every call to the wrapped sponge `S`,
+/// and logs it in the transcript being [`record`]ed (if any).
+/// It can wrap both an Fq-Sponge and an Fr-Sponge.
+#[derive(Clone, Debug)]
+pub struct TranscriptSponge<S> {
+    pub inner: S,
+}
+
+impl<G, S> FqSponge<G::BaseField, G, G::ScalarField> for TranscriptSponge<S>
+where
+    G: CommitmentCurve,
+    S: FqSponge<G::BaseField, G, G::ScalarField>,
+{
+    fn new(p: &'static ArithmeticSpongeParams<G::BaseField>) -> Self {
+        TranscriptSponge { inner: S::new(p) }
+    }
+
+    fn absorb_g(&mut self, g: &[G]) {
+        log(SpongeKind::Fq, || {
+            TranscriptOp::Absorb(g.iter().map(point_to_hex).collect())
+        });
+        self.inner.absorb_g(g);
+    }
+
+    fn absorb_fq(&mut self, x: &[G::BaseField]) {
+        log(SpongeKind::Fq, || {
+            TranscriptOp::Absorb(x.iter().map(|x| x.to_hex()).collect())
+        });
+        self.inner.absorb_fq(x);
+    }
+
+    fn absorb_fr(&mut self, x: &[G::ScalarField]) {
+        log(SpongeKind::Fq, || {
+            TranscriptOp::Absorb(x.iter().map(|x| x.to_hex()).collect())
+        });
+        self.inner.absorb_fr(x);
+    }
+
+    fn challenge(&mut self) -> G::ScalarField {
+        let chal = self.inner.challenge();
+        log(SpongeKind::Fq, || TranscriptOp::Squeeze(chal.to_hex()));
+        chal
+    }
+
+    fn challenge_fq(&mut self) -> G::BaseField {
+        let chal = self.inner.challenge_fq();
+        log(SpongeKind::Fq, || TranscriptOp::Squeeze(chal.to_hex()));
+        chal
+    }
+
+    fn digest(self) -> G::ScalarField {
+        let digest = self.inner.digest();
+        log(SpongeKind::Fq, || TranscriptOp::Squeeze(digest.to_hex()));
+        digest
+    }
+
+    fn digest_fq(self) -> G::BaseField {
+        let digest = self.inner.digest_fq();
+        log(SpongeKind::Fq, || TranscriptOp::Squeeze(digest.to_hex()));
+        digest
+    }
+}
+
+impl<Fr, S> FrSponge<Fr> for TranscriptSponge<S>
+where
+    Fr: Field,
+    S: FrSponge<Fr>,
+{
+    fn new(p: &'static ArithmeticSpongeParams<Fr>) -> Self {
+        TranscriptSponge { inner: S::new(p) }
+    }
+
+    fn absorb(&mut self, x: &Fr) {
+        log(SpongeKind::Fr, || TranscriptOp::Absorb(vec![x.to_hex()]));
+        self.inner.absorb(x);
+    }
+
+    fn absorb_multiple(&mut self, x: &[Fr]) {
+        log(SpongeKind::Fr, || {
+            TranscriptOp::Absorb(x.iter().map(|x| x.to_hex()).collect())
+        });
+        self.inner.absorb_multiple(x);
+    }
+
+    fn challenge(&mut self) -> ScalarChallenge<Fr> {
+        let chal = self.inner.challenge();
+        log(SpongeKind::Fr, || TranscriptOp::Squeeze(chal.0.to_hex()));
+        chal
+    }
+
+    fn digest(self) -> Fr {
+        let digest = self.inner.digest();
+        log(SpongeKind::Fr, || TranscriptOp::Squeeze(digest.to_hex()));
+        digest
+    }
+
+    fn absorb_evaluations(&mut self, e: &ProofEvaluations<PointEvaluations<Vec<Fr>>>) {
+        log(SpongeKind::Fr, || {
+            TranscriptOp::Absorb(evaluations_to_hex(e))
+        });
+        self.inner.absorb_evaluations(e);
+    }
+}
+
+//
+// Diffing
+//
+
+/// Compares a prover transcript with a verifier transcript,
+/// and returns the first entry at which they diverge (if any).
+pub fn diff(prover: &Transcript, verifier: &Transcript) -> Option<Divergence> {
+    let len = std::cmp::max(prover.entries.len(), verifier.entries.len());
+    (0..len).find_map(|index| {
+        let p = prover.entries.get(index);
+        let v = verifier.entries.get(index);
+        if p == v {
+            None
+        } else {
+            Some(Divergence {
+                index,
+                prover: p.cloned(),
+                verifier: v.cloned(),
+            })
+        }
+    })
+}
+
+impl fmt::Display for SpongeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpongeKind::Fq => write!(f, "fq"),
+            SpongeKind::Fr => write!(f, "fr"),
+        }
+    }
+}
+
+impl fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.op {
+            TranscriptOp::Absorb(xs) => {
+                write!(
+                    f,
+                    "[{}] {} absorb {}",
+                    self.phase,
+                    self.sponge,
+                    xs.join(", ")
+                )
+            }
+            TranscriptOp::Squeeze(x) => write!(f, "[{}] {} squeeze {}", self.phase, self.sponge, x),
+        }
+    }
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entry = |e: &Option<TranscriptEntry>| match e {
+            Some(e) => e.to_string(),
+            None => "<end of transcript>".to_string(),
+        };
+        writeln!(f, "transcripts diverge at entry #{}", self.index)?;
+        writeln!(f, "  prover:   {}", entry(&self.prover))?;
+        write!(f, "  verifier: {}", entry(&self.verifier))
+    }
+}
@@ -16,12 +16,19 @@ use crate::{
     oracles::OraclesResult,
     plonk_sponge::FrSponge,
     proof::{PointEvaluations, ProverProof, RecursionChallenge},
+    transcript,
     verifier_index::VerifierIndex,
 };
 use ark_ff::{Field, One, PrimeField, Zero};
 use ark_poly::{EvaluationDomain, Polynomial};
-use commitment_dlog::commitment::{
-    absorb_commitment, combined_inner_product, BatchEvaluationProof, Evaluation, PolyComm,
+use commitment_dlog::{
+    accumulator::Accumulators,
+    commitment::{
+        absorb_commitment, combined_inner_product, BatchEvaluationProof, Evaluation, OpeningCheck,
+        PolyComm,
+    },
+    evaluation_proof::OpeningProof as DlogOpeningProof,
+    pcs::{CommitmentKey, OpenProof},
 };
 use mina_poseidon::{sponge::ScalarChallenge, FqSponge};
 use rand::thread_rng;
@@ -29,12 +36,12 @@ use rand::thread_rng;
 /// The result of a proof verification.
 pub type Result<T> = std::result::Result<T, VerifyError>;
 
-pub struct Context<'a, G: KimchiCurve> {
-    proof: &'a ProverProof<G>,
-    index: &'a VerifierIndex<G>,
+pub struct Context<'a, G: KimchiCurve, OpeningProof: OpenProof<G>> {
+    proof: &'a ProverProof<G, OpeningProof>,
+    index: &'a VerifierIndex<G, OpeningProof>,
 }
 
-impl<'a, G: KimchiCurve> Context<'a, G> {
+impl<'a, G: KimchiCurve, OpeningProof: OpenProof<G>> Context<'a, G, OpeningProof> {
     pub fn get_column(&self, col: Column) -> Option<&'a PolyComm<G>> {
         use Column::*;
         match col {
@@ -84,7 +91,7 @@ impl<'a, G: KimchiCurve> Context<'a, G> {
     }
 }
 
-impl<G: KimchiCurve> ProverProof<G>
+impl<G: KimchiCurve, OpeningProof: OpenProof<G>> ProverProof<G, OpeningProof>
 where
     G::BaseField: PrimeField,
 {
@@ -102,8 +109,34 @@ where
         EFrSponge: FrSponge<G::ScalarField>,
     >(
         &self,
-        index: &VerifierIndex<G>,
+        index: &VerifierIndex<G, OpeningProof>,
+        public_comm: &PolyComm<G>,
+    ) -> Result<OraclesResult<G, EFqSponge>> {
+        self.oracles_with_digest::<EFqSponge, EFrSponge>(
+            index,
+            public_comm,
+            index.digest::<EFqSponge>(),
+        )
+    }
+
+    /// Same as [`ProverProof::oracles`], with the digest of `index` computed by the caller
+    /// (see [`VerifierIndex::digest`]), so that it is computed once for several proofs.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `commitment(s)` are invalid(missing or wrong length), or `proof` is verified as invalid.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `PolishToken` evaluation is invalid.
+    pub fn oracles_with_digest<
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        EFrSponge: FrSponge<G::ScalarField>,
+    >(
+        &self,
+        index: &VerifierIndex<G, OpeningProof>,
         public_comm: &PolyComm<G>,
+        verifier_index_digest: G::BaseField,
     ) -> Result<OraclesResult<G, EFqSponge>> {
         //~
         //~ #### Fiat-Shamir argument
@@ -114,10 +147,10 @@ where
         let (_, endo_r) = G::endos();
 
         //~ 1. Setup the Fq-Sponge.
+        transcript::phase("setup");
         let mut fq_sponge = EFqSponge::new(G::OtherCurve::sponge_params());
 
         //~ 1. Absorb the digest of the VerifierIndex.
-        let verifier_index_digest = index.digest::<EFqSponge>();
         fq_sponge.absorb_fq(&[verifier_index_digest]);
 
         //~ 1. Absorb the commitments of the previous challenges with the Fq-sponge.
@@ -126,9 +159,11 @@ where
         }
 
         //~ 1. Absorb the commitment of the public input polynomial with the Fq-Sponge.
+        transcript::phase("public input");
         absorb_commitment(&mut fq_sponge, public_comm);
 
         //~ 1. Absorb the commitments to the registers / witness columns with the Fq-Sponge.
+        transcript::phase("witness");
         self.commitments
             .w_comm
             .iter()
@@ -136,6 +171,8 @@ where
 
         //~ 1. If lookup is used:
         if let Some(l) = &index.lookup_index {
+            transcript::phase("lookup");
+
             let lookup_commits = self
                 .commitments
                 .lookup
@@ -187,6 +224,7 @@ where
         }
 
         //~ 1. Sample $\beta$ with the Fq-Sponge.
+        transcript::phase("permutation");
         let beta = fq_sponge.challenge();
 
         //~ 1. Sample $\gamma$ with the Fq-Sponge.
@@ -212,6 +250,7 @@ where
         }
 
         //~ 1. Absorb the commitment to the quotient polynomial $t$ into the argument.
+        transcript::phase("quotient");
         absorb_commitment(&mut fq_sponge, &self.commitments.t_comm);
 
         //~ 1. Sample $\zeta'$ with the Fq-Sponge.
@@ -221,6 +260,7 @@ where
         let zeta = zeta_chal.to_field(endo_r);
 
         //~ 1. Setup the Fr-Sponge.
+        transcript::phase("evaluations");
         let digest = fq_sponge.clone().digest();
         let mut fr_sponge = EFrSponge::new(G::sponge_params());
 
@@ -442,7 +482,7 @@ where
                 ))
             }
 
-            combined_inner_product(&evaluation_points, &v, &u, &es, index.srs().g.len())
+            combined_inner_product(&evaluation_points, &v, &u, &es, index.srs().max_poly_size())
         };
 
         let oracles = RandomOracles {
@@ -474,12 +514,14 @@ where
     }
 }
 
-fn to_batch<'a, G, EFqSponge, EFrSponge>(
-    index: &VerifierIndex<G>,
-    proof: &'a ProverProof<G>,
-) -> Result<BatchEvaluationProof<'a, G, EFqSponge>>
+fn to_batch<'a, G, EFqSponge, EFrSponge, OpeningProof>(
+    index: &VerifierIndex<G, OpeningProof>,
+    proof: &'a ProverProof<G, OpeningProof>,
+    verifier_index_digest: G::BaseField,
+) -> Result<BatchEvaluationProof<'a, G, EFqSponge, OpeningProof>>
 where
     G: KimchiCurve,
+    OpeningProof: OpenProof<G>,
     G::BaseField: PrimeField,
     EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
     EFrSponge: FrSponge<G::ScalarField>,
@@ -509,8 +551,7 @@ where
         }
         let lgr_comm = index
             .srs()
-            .lagrange_bases
-            .get(&index.domain.size())
+            .lagrange_basis(index.domain.size())
             .expect("pre-computed committed lagrange bases not found");
         let com: Vec<_> = lgr_comm.iter().take(index.public).collect();
         let elm: Vec<_> = proof.public.iter().map(|s| -*s).collect();
@@ -539,7 +580,17 @@ where
         zeta1: zeta_to_domain_size,
         ft_eval0,
         ..
-    } = proof.oracles::<EFqSponge, EFrSponge>(index, &public_comm)?;
+    } = proof.oracles_with_digest::<EFqSponge, EFrSponge>(
+        index,
+        &public_comm,
+        verifier_index_digest,
+    )?;
+
+    //~ 1. If the proof includes the evaluation of $ft$ at $\zeta$ computed by the prover,
+    //~    ensure that it is the one recomputed by the verifier.
+    if matches!(proof.ft_eval0, Some(claimed) if claimed != ft_eval0) {
+        return Err(VerifyError::FtEval0Mismatch);
+    }
 
     //~ 1. Combine the chunked polynomials' evaluations
     //~    (TODO: most likely only the quotient polynomial is chunked)
@@ -768,9 +819,33 @@ where
     G::BaseField: PrimeField,
     EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
     EFrSponge: FrSponge<G::ScalarField>,
+{
+    verify_with_pcs::<G, EFqSponge, EFrSponge, DlogOpeningProof<G>>(
+        group_map,
+        verifier_index,
+        proof,
+    )
+}
+
+/// Verifies a proof like [`verify`], with any polynomial commitment scheme (see [`OpenProof`]).
+///
+/// # Errors
+///
+/// Will give error if `proof(s)` are not verified as valid.
+pub fn verify_with_pcs<G, EFqSponge, EFrSponge, OpeningProof>(
+    group_map: &G::Map,
+    verifier_index: &VerifierIndex<G, OpeningProof>,
+    proof: &ProverProof<G, OpeningProof>,
+) -> Result<()>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+    OpeningProof: OpenProof<G>,
 {
     let proofs = vec![(verifier_index, proof)];
-    batch_verify::<G, EFqSponge, EFrSponge>(group_map, &proofs)
+    batch_verify_with_pcs::<G, EFqSponge, EFrSponge, OpeningProof>(group_map, &proofs)
 }
 
 /// This function verifies the batch of zk-proofs
@@ -781,6 +856,11 @@ where
 /// # Errors
 ///
 /// Will give error if `srs` of `proof` is invalid or `verify` process fails.
+/// In a batch of several proofs, the error of a proof is reported as
+/// [`VerifyError::BatchProofFailed`] with the position of the proof,
+/// except for a failure of the opening proofs, which is checked for the whole batch at once
+/// and reported as [`VerifyError::OpenProof`]
+/// (see [`batch_verify_with_fallback`] to find out which proof failed, and why).
 pub fn batch_verify<G, EFqSponge, EFrSponge>(
     group_map: &G::Map,
     proofs: &[(&VerifierIndex<G>, &ProverProof<G>)],
@@ -790,6 +870,29 @@ where
     G::BaseField: PrimeField,
     EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
     EFrSponge: FrSponge<G::ScalarField>,
+{
+    batch_verify_with_pcs::<G, EFqSponge, EFrSponge, DlogOpeningProof<G>>(group_map, proofs)
+}
+
+/// Verifies a batch of proofs like [`batch_verify`],
+/// with any polynomial commitment scheme (see [`OpenProof`]).
+///
+/// # Errors
+///
+/// Will give error if `srs` of `proof` is invalid or `verify` process fails.
+pub fn batch_verify_with_pcs<G, EFqSponge, EFrSponge, OpeningProof>(
+    group_map: &G::Map,
+    proofs: &[(
+        &VerifierIndex<G, OpeningProof>,
+        &ProverProof<G, OpeningProof>,
+    )],
+) -> Result<()>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+    OpeningProof: OpenProof<G>,
 {
     //~ #### Batch verification of proofs
     //~
@@ -803,30 +906,209 @@ where
         return Ok(());
     }
 
+    //~ 1. Ensure that the proofs that include the digest of their verifier index
+    //~    were created for the verifier index they are verified against.
     //~ 1. Ensure that all the proof's verifier index have a URS of the same length. (TODO: do they have to be the same URS though? should we check for that?)
+    let (srs, digests) = check_batch::<G, EFqSponge, OpeningProof>(proofs)?;
+
+    //~ 1. Validate each proof separately following the [partial verification](#partial-verification) steps.
+    let mut batch = vec![];
+    for (i, ((index, proof), digest)) in proofs.iter().zip(digests).enumerate() {
+        batch.push(
+            to_batch::<G, EFqSponge, EFrSponge, OpeningProof>(index, proof, digest)
+                .map_err(|e| proof_failed(proofs.len(), i, e))?,
+        );
+    }
+
+    //~ 1. Use the [`PolyCom.verify`](#polynomial-commitments) to verify the partially evaluated proofs.
+    transcript::phase("opening");
+    if OpeningProof::verify(srs, group_map, &mut batch, &mut thread_rng()) {
+        Ok(())
+    } else {
+        Err(VerifyError::OpenProof)
+    }
+}
+
+/// Attributes the error `err` to the proof `i` of a batch of `len` proofs
+/// (a batch of a single proof returns the error as is).
+fn proof_failed(len: usize, i: usize, err: VerifyError) -> VerifyError {
+    if len > 1 {
+        VerifyError::BatchProofFailed(i, Box::new(err))
+    } else {
+        err
+    }
+}
+
+/// Checks that the proofs were created for their verifier index, and can be batched.
+/// Returns the SRS of the batch, and the digest of the verifier index of each proof.
+fn check_batch<'a, G, EFqSponge, OpeningProof>(
+    proofs: &'a [(
+        &VerifierIndex<G, OpeningProof>,
+        &ProverProof<G, OpeningProof>,
+    )],
+) -> Result<(&'a OpeningProof::SRS, Vec<G::BaseField>)>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    OpeningProof: OpenProof<G>,
+{
+    // the proofs of a batch usually share a few verifier indexes,
+    // so the digest is only computed once per distinct index
+    let mut cache: Vec<(&VerifierIndex<G, OpeningProof>, G::BaseField)> = vec![];
+    let mut digests = Vec::with_capacity(proofs.len());
+    for (i, (index, proof)) in proofs.iter().enumerate() {
+        let digest = match cache.iter().find(|(i, _)| std::ptr::eq(*i, *index)) {
+            Some((_, digest)) => *digest,
+            None => {
+                let digest = index.digest::<EFqSponge>();
+                cache.push((index, digest));
+                digest
+            }
+        };
+        if let Some(proof_digest) = proof.verifier_index_digest {
+            if proof_digest != digest {
+                return Err(proof_failed(proofs.len(), i, VerifyError::IndexMismatch));
+            }
+        }
+        digests.push(digest);
+    }
+
     // TODO: Account for the different SRS lengths
-    let srs = &proofs[0].0.srs();
+    let srs: &OpeningProof::SRS = proofs[0].0.srs();
     for (index, _) in proofs.iter() {
-        if index.srs().g.len() != srs.g.len() {
+        if index.srs().max_poly_size() != srs.max_poly_size() {
             return Err(VerifyError::DifferentSRS);
         }
 
         // also make sure that the SRS is not smaller than the domain size
-        if index.srs().max_degree() < index.domain.size() {
+        if index.srs().max_poly_size() < index.domain.size() {
             return Err(VerifyError::SRSTooSmall);
         }
     }
 
-    //~ 1. Validate each proof separately following the [partial verification](#partial-verification) steps.
+    Ok((srs, digests))
+}
+
+/// Performs the succinct part of the verification of a batch of proofs,
+/// that is [`batch_verify`] without the check of the commitments to the challenge polynomials
+/// of the opening proofs, which is a multi-scalar multiplication of the size of the SRS.
+/// The returned accumulators can be decided later with [`SRS::decide`](commitment_dlog::srs::SRS::decide),
+/// for example together with the accumulators of other batches,
+/// or passed to the next proof of a recursion as [`RecursionChallenge`]s.
+///
+/// # Errors
+///
+/// Will give error if the proofs cannot be batched, or if the succinct verification fails.
+pub fn succinct_verify<G, EFqSponge, EFrSponge>(
+    group_map: &G::Map,
+    proofs: &[(&VerifierIndex<G>, &ProverProof<G>)],
+) -> Result<Accumulators<G>>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    if proofs.is_empty() {
+        return Ok(Accumulators::default());
+    }
+
+    let (srs, digests) = check_batch::<G, EFqSponge, DlogOpeningProof<G>>(proofs)?;
+
     let mut batch = vec![];
-    for (index, proof) in proofs {
-        batch.push(to_batch::<G, EFqSponge, EFrSponge>(index, proof)?);
+    for ((index, proof), digest) in proofs.iter().zip(digests) {
+        batch.push(to_batch::<G, EFqSponge, EFrSponge, DlogOpeningProof<G>>(
+            index, proof, digest,
+        )?);
     }
 
-    //~ 1. Use the [`PolyCom.verify`](#polynomial-commitments) to verify the partially evaluated proofs.
-    if srs.verify::<EFqSponge, _>(group_map, &mut batch, &mut thread_rng()) {
-        Ok(())
+    transcript::phase("opening");
+    srs.succinct_verify(group_map, &mut batch, &mut thread_rng())
+        .ok_or(VerifyError::OpenProof)
+}
+
+/// Verifies a batch of proofs like [`batch_verify`], but if the batch fails to verify,
+/// falls back to verifying each proof separately to find out which ones are invalid, and why.
+/// This is as fast as [`batch_verify`] when all the proofs are valid,
+/// and much slower otherwise.
+///
+/// Returns the result of the verification of each proof, in the order of `proofs`.
+pub fn batch_verify_with_fallback<G, EFqSponge, EFrSponge>(
+    group_map: &G::Map,
+    proofs: &[(&VerifierIndex<G>, &ProverProof<G>)],
+) -> Vec<Result<()>>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    batch_verify_with_fallback_with_pcs::<G, EFqSponge, EFrSponge, DlogOpeningProof<G>>(
+        group_map, proofs,
+    )
+}
+
+/// Verifies a batch of proofs like [`batch_verify_with_fallback`],
+/// with any polynomial commitment scheme (see [`OpenProof`]).
+pub fn batch_verify_with_fallback_with_pcs<G, EFqSponge, EFrSponge, OpeningProof>(
+    group_map: &G::Map,
+    proofs: &[(
+        &VerifierIndex<G, OpeningProof>,
+        &ProverProof<G, OpeningProof>,
+    )],
+) -> Vec<Result<()>>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+    OpeningProof: OpenProof<G>,
+{
+    if batch_verify_with_pcs::<G, EFqSponge, EFrSponge, OpeningProof>(group_map, proofs).is_ok() {
+        return vec![Ok(()); proofs.len()];
+    }
+
+    let _untracked = transcript::pause();
+    proofs
+        .iter()
+        .map(|(index, proof)| {
+            diagnose::<G, EFqSponge, EFrSponge, OpeningProof>(group_map, index, proof)
+        })
+        .collect()
+}
+
+/// Verifies a single proof, and finds out which check of its opening fails, if any.
+fn diagnose<G, EFqSponge, EFrSponge, OpeningProof>(
+    group_map: &G::Map,
+    index: &VerifierIndex<G, OpeningProof>,
+    proof: &ProverProof<G, OpeningProof>,
+) -> Result<()>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+    OpeningProof: OpenProof<G>,
+{
+    let proofs = [(index, proof)];
+    let (srs, digests) = check_batch::<G, EFqSponge, OpeningProof>(&proofs)?;
+    let mut batch = [to_batch::<G, EFqSponge, EFrSponge, OpeningProof>(
+        index, proof, digests[0],
+    )?];
+
+    let sponge = batch[0].sponge.clone();
+    let mut fails = |check| {
+        batch[0].sponge = sponge.clone();
+        !OpeningProof::verify_checks(srs, group_map, &mut batch, &mut thread_rng(), &[check])
+    };
+    // an incorrect evaluation also changes the challenges of the opening,
+    // and thus makes the check of the challenge polynomial fail too
+    if fails(OpeningCheck::CombinedInnerProduct) {
+        Err(VerifyError::CombinedInnerProduct)
+    } else if fails(OpeningCheck::ChallengePolynomial) {
+        Err(VerifyError::ChallengePolynomial)
     } else {
-        Err(VerifyError::OpenProof)
+        Ok(())
     }
 }
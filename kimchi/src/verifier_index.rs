@@ -4,20 +4,24 @@
 use crate::{
     alphas::Alphas,
     circuits::{
+        constraints::FeatureFlags,
         expr::{Linearization, PolishToken},
         lookup::{index::LookupSelectors, lookups::LookupInfo},
-        polynomials::permutation::{zk_polynomial, zk_w3},
+        polynomials::permutation::{permutation_vanishing_polynomial, zk_w},
         wires::{COLUMNS, PERMUTS},
     },
     curve::KimchiCurve,
-    error::VerifierIndexError,
+    error::{ContainerError, VerifierIndexError},
+    linearization::expr_linearization,
     prover_index::ProverIndex,
+    transcript,
 };
 use ark_ff::{One, PrimeField};
 use ark_poly::{univariate::DensePolynomial, Radix2EvaluationDomain as D};
 use commitment_dlog::{
     commitment::{CommitmentCurve, PolyComm},
-    srs::SRS,
+    evaluation_proof::OpeningProof as DlogOpeningProof,
+    pcs::{CommitmentKey, OpenProof},
 };
 use mina_poseidon::FqSponge;
 use once_cell::sync::OnceCell;
@@ -56,7 +60,7 @@ pub struct LookupVerifierIndex<G: CommitmentCurve> {
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct VerifierIndex<G: KimchiCurve> {
+pub struct VerifierIndex<G: KimchiCurve, OpeningProof: OpenProof<G> = DlogOpeningProof<G>> {
     /// evaluation domain
     #[serde_as(as = "o1_utils::serialization::SerdeAs")]
     pub domain: D<G::ScalarField>,
@@ -64,7 +68,7 @@ pub struct VerifierIndex<G: KimchiCurve> {
     pub max_poly_size: usize,
     /// polynomial commitment keys
     #[serde(skip)]
-    pub srs: OnceCell<Arc<SRS<G>>>,
+    pub srs: OnceCell<Arc<OpeningProof::SRS>>,
     /// number of public inputs
     pub public: usize,
     /// number of previous evaluation challenges, for recursive proving
@@ -150,16 +154,25 @@ pub struct VerifierIndex<G: KimchiCurve> {
     /// The mapping between powers of alpha and constraints
     #[serde(skip)]
     pub powers_of_alpha: Alphas<G::ScalarField>,
+
+    // the fields below come last and have defaults,
+    // so that verifier indexes serialized before they existed can still be deserialized
+    /// whether the proofs are zero-knowledge
+    #[serde(default = "crate::circuits::constraints::default_zero_knowledge")]
+    pub zero_knowledge: bool,
+    /// number of rows at the end of the domain that are not covered by the permutation argument
+    #[serde(default = "crate::circuits::constraints::default_zk_rows")]
+    pub zk_rows: u64,
 }
 //~spec:endcode
 
-impl<G: KimchiCurve> ProverIndex<G> {
+impl<G: KimchiCurve, OpeningProof: OpenProof<G>> ProverIndex<G, OpeningProof> {
     /// Produces the [`VerifierIndex`] from the prover's [`ProverIndex`].
     ///
     /// # Panics
     ///
     /// Will panic if `srs` cannot be in `cell`.
-    pub fn verifier_index(&self) -> VerifierIndex<G> {
+    pub fn verifier_index(&self) -> VerifierIndex<G, OpeningProof> {
         if let Some(verifier_index) = &self.verifier_index {
             return verifier_index.clone();
         }
@@ -207,6 +220,8 @@ impl<G: KimchiCurve> ProverIndex<G> {
             powers_of_alpha: self.powers_of_alpha.clone(),
             public: self.cs.public,
             prev_challenges: self.cs.prev_challenges,
+            zero_knowledge: self.cs.zero_knowledge,
+            zk_rows: self.cs.zk_rows,
             srs: {
                 let cell = OnceCell::new();
                 cell.set(Arc::clone(&self.srs)).unwrap();
@@ -301,7 +316,7 @@ impl<G: KimchiCurve> ProverIndex<G> {
             },
             w: {
                 let cell = OnceCell::new();
-                cell.set(zk_w3(self.cs.domain.d1)).unwrap();
+                cell.set(zk_w(self.cs.domain.d1, self.cs.zk_rows)).unwrap();
                 cell
             },
             endo: self.cs.endo,
@@ -311,27 +326,57 @@ impl<G: KimchiCurve> ProverIndex<G> {
     }
 }
 
-impl<G: KimchiCurve> VerifierIndex<G> {
+impl<G: KimchiCurve, OpeningProof: OpenProof<G>> VerifierIndex<G, OpeningProof> {
     /// Gets srs from [`VerifierIndex`] lazily
-    pub fn srs(&self) -> &Arc<SRS<G>>
+    pub fn srs(&self) -> &Arc<OpeningProof::SRS>
     where
         G::BaseField: PrimeField,
     {
         self.srs.get_or_init(|| {
-            let mut srs = SRS::<G>::create(self.max_poly_size);
+            let mut srs = <OpeningProof::SRS as CommitmentKey<G>>::create(self.max_poly_size);
             srs.add_lagrange_basis(self.domain);
             Arc::new(srs)
         })
     }
 
+    /// Recovers the [`FeatureFlags`] of the circuit from the commitments present in the [`VerifierIndex`]
+    pub fn feature_flags(&self) -> FeatureFlags {
+        FeatureFlags {
+            chacha: self.chacha_comm.is_some(),
+            range_check0: self.range_check0_comm.is_some(),
+            range_check1: self.range_check1_comm.is_some(),
+            foreign_field_add: self.foreign_field_add_comm.is_some(),
+            foreign_field_mul: self.foreign_field_mul_comm.is_some(),
+            xor: self.xor_comm.is_some(),
+            rot: self.rot_comm.is_some(),
+            lookup_features: self
+                .lookup_index
+                .as_ref()
+                .map(|lookup_index| lookup_index.lookup_info.features)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Recomputes the fields of the [`VerifierIndex`] that are not serialized:
+    /// the linearization, the powers of alpha and the endoscalar coefficient.
+    /// The SRS is created lazily by [`VerifierIndex::srs`], or can be set directly.
+    pub fn restore_skipped_fields(&mut self) {
+        let (linearization, powers_of_alpha) =
+            expr_linearization(Some(&self.feature_flags()), true);
+        self.linearization = linearization;
+        self.powers_of_alpha = powers_of_alpha;
+        self.endo = G::OtherCurve::endos().0;
+    }
+
     /// Gets zkpm from [`VerifierIndex`] lazily
     pub fn zkpm(&self) -> &DensePolynomial<G::ScalarField> {
-        self.zkpm.get_or_init(|| zk_polynomial(self.domain))
+        self.zkpm
+            .get_or_init(|| permutation_vanishing_polynomial(self.domain, self.zk_rows))
     }
 
     /// Gets w from [`VerifierIndex`] lazily
     pub fn w(&self) -> &G::ScalarField {
-        self.w.get_or_init(|| zk_w3(self.domain))
+        self.w.get_or_init(|| zk_w(self.domain, self.zk_rows))
     }
 
     /// Deserializes a [`VerifierIndex`] from a file, given a pointer to an SRS and an optional offset in the file.
@@ -340,34 +385,32 @@ impl<G: KimchiCurve> VerifierIndex<G> {
     ///
     /// Will give error if it fails to deserialize from file or unable to set `srs` in `verifier_index`.
     pub fn from_file(
-        srs: Option<Arc<SRS<G>>>,
+        srs: Option<Arc<OpeningProof::SRS>>,
         path: &Path,
         offset: Option<u64>,
-        // TODO: we shouldn't have to pass these
-        endo: G::ScalarField,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, ContainerError> {
         // open file
-        let file = File::open(path).map_err(|e| e.to_string())?;
+        let file = File::open(path)?;
 
         // offset
         let mut reader = BufReader::new(file);
         if let Some(offset) = offset {
-            reader.seek(Start(offset)).map_err(|e| e.to_string())?;
+            reader.seek(Start(offset))?;
         }
 
         // deserialize
         let mut verifier_index = Self::deserialize(&mut rmp_serde::Deserializer::new(reader))
-            .map_err(|e| e.to_string())?;
+            .map_err(|e| ContainerError::Deserialization(e.to_string()))?;
 
         // fill in the rest
         if let Some(srs) = srs {
             verifier_index
                 .srs
                 .set(srs)
-                .map_err(|_| VerifierIndexError::SRSHasBeenSet.to_string())?;
+                .map_err(|_| VerifierIndexError::SRSHasBeenSet)?;
         };
 
-        verifier_index.endo = endo;
+        verifier_index.restore_skipped_fields();
 
         Ok(verifier_index)
     }
@@ -381,17 +424,14 @@ impl<G: KimchiCurve> VerifierIndex<G> {
     /// # Panics
     ///
     /// Will panic if `path` is invalid or `file serialization` has issue.
-    pub fn to_file(&self, path: &Path, append: Option<bool>) -> Result<(), String> {
+    pub fn to_file(&self, path: &Path, append: Option<bool>) -> Result<(), ContainerError> {
         let append = append.unwrap_or(true);
-        let file = OpenOptions::new()
-            .append(append)
-            .open(path)
-            .map_err(|e| e.to_string())?;
+        let file = OpenOptions::new().append(append).open(path)?;
 
         let writer = BufWriter::new(file);
 
         self.serialize(&mut rmp_serde::Serializer::new(writer))
-            .map_err(|e| e.to_string())
+            .map_err(|e| ContainerError::Serialization(e.to_string()))
     }
 
     /// Compute the digest of the [`VerifierIndex`], which can be used for the Fiat-Shamir
@@ -399,6 +439,8 @@ impl<G: KimchiCurve> VerifierIndex<G> {
     pub fn digest<EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>>(
         &self,
     ) -> G::BaseField {
+        // this sponge is not part of the proof transcript
+        let _untracked = transcript::pause();
         let mut fq_sponge = EFqSponge::new(G::OtherCurve::sponge_params());
         // We fully expand this to make the compiler check that we aren't missing any commitments
         let VerifierIndex {
@@ -407,6 +449,8 @@ impl<G: KimchiCurve> VerifierIndex<G> {
             srs: _,
             public: _,
             prev_challenges: _,
+            zero_knowledge,
+            zk_rows,
 
             // Always present
             sigma_comm,
@@ -531,6 +575,13 @@ impl<G: KimchiCurve> VerifierIndex<G> {
                 fq_sponge.absorb_g(&ffmul.unshifted);
             }
         }
+
+        // Zero-knowledge
+        // (only absorbed for indexes without zero-knowledge, so that the digests of the others are unchanged)
+
+        if !zero_knowledge {
+            fq_sponge.absorb_fq(&[G::BaseField::from(*zk_rows)]);
+        }
         fq_sponge.digest_fq()
     }
 }
@@ -0,0 +1,34 @@
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, ProjectiveCurve};
+use ark_ff::{PrimeField, UniformRand};
+use commitment_dlog::msm::Glv;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use rand::{rngs::StdRng, SeedableRng};
+
+pub fn bench_msm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("MSM");
+    group.sample_size(10);
+
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let glv = Glv::<VestaParameters>::new();
+
+    for log_size in [10, 12, 14, 16] {
+        let size = 1 << log_size;
+        let bases: Vec<_> = (0..size)
+            .map(|_| Vesta::prime_subgroup_generator().mul(Fp::rand(rng)))
+            .collect();
+        let bases = ProjectiveCurve::batch_normalization_into_affine(&bases);
+        let scalars: Vec<Fp> = (0..size).map(|_| Fp::rand(rng)).collect();
+        let scalars_repr: Vec<_> = scalars.iter().map(|k| k.into_repr()).collect();
+
+        group.bench_with_input(BenchmarkId::new("arkworks", size), &size, |b, _| {
+            b.iter(|| VariableBaseMSM::multi_scalar_mul(black_box(&bases), &scalars_repr))
+        });
+        group.bench_with_input(BenchmarkId::new("GLV batch-affine", size), &size, |b, _| {
+            b.iter(|| glv.msm(black_box(&bases), &scalars))
+        });
+    }
+}
+
+criterion_group!(benches, bench_msm);
+criterion_main!(benches);
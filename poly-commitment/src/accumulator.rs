@@ -0,0 +1,192 @@
+//! This module implements accumulators, to defer the expensive part of the verification
+//! of opening proofs of the inner-product argument.
+//!
+//! Verifying an opening proof amounts to:
+//!
+//! 1. a succinct check, logarithmic in the size of the SRS,
+//!    that the proof is valid if its commitment `sg` to the challenge polynomial is correct,
+//! 2. a check that `sg` is the commitment to the challenge polynomial,
+//!    which is a multi-scalar multiplication of the size of the SRS.
+//!
+//! [`SRS::succinct_verify`] performs the first check on a batch of proofs,
+//! and returns their [`Accumulator`]s: the challenges of each proof and its claimed `sg`.
+//! Accumulators can be carried around (for example, in the next proof of a recursion,
+//! or to an aggregation service), merged, and decided later with [`SRS::decide`],
+//! which performs the second check for all of them with a single multi-scalar multiplication.
+//!
+//! [`SRS::verify`] is the same as a succinct check followed by the decision of the resulting accumulators.
+
+use crate::{
+    commitment::{b_poly, b_poly_coefficients, BatchEvaluationProof, CommitmentCurve},
+    srs::{endos, SRS},
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use mina_poseidon::FqSponge;
+use o1_utils::math;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// The deferred part of the verification of an opening proof:
+/// the challenges of the proof and its claimed commitment to the challenge polynomial.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "G: CanonicalDeserialize + CanonicalSerialize")]
+pub struct Accumulator<G: AffineCurve> {
+    /// The challenges of the inner-product argument
+    #[serde_as(as = "Vec<o1_utils::serialization::SerdeAs>")]
+    pub chals: Vec<G::ScalarField>,
+    /// The claimed commitment to the challenge polynomial
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub sg: G,
+}
+
+/// A set of accumulators, decided together.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "G: CanonicalDeserialize + CanonicalSerialize")]
+pub struct Accumulators<G: AffineCurve> {
+    pub accumulators: Vec<Accumulator<G>>,
+}
+
+impl<G: AffineCurve> Accumulator<G> {
+    pub fn new(chals: Vec<G::ScalarField>, sg: G) -> Self {
+        Accumulator { chals, sg }
+    }
+
+    /// Evaluates the challenge polynomial at `x`, in time logarithmic in its degree.
+    pub fn evaluate(&self, x: G::ScalarField) -> G::ScalarField {
+        b_poly(&self.chals, x)
+    }
+
+    /// Returns the challenge polynomial, committed to by `sg`.
+    pub fn challenge_polynomial(&self) -> DensePolynomial<G::ScalarField> {
+        DensePolynomial::from_coefficients_vec(b_poly_coefficients(&self.chals))
+    }
+}
+
+impl<G: AffineCurve> Default for Accumulators<G> {
+    fn default() -> Self {
+        Accumulators {
+            accumulators: vec![],
+        }
+    }
+}
+
+impl<G: AffineCurve> From<Vec<Accumulator<G>>> for Accumulators<G> {
+    fn from(accumulators: Vec<Accumulator<G>>) -> Self {
+        Accumulators { accumulators }
+    }
+}
+
+impl<G: AffineCurve> Accumulators<G> {
+    pub fn len(&self) -> usize {
+        self.accumulators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accumulators.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Accumulator<G>> {
+        self.accumulators.iter()
+    }
+
+    pub fn push(&mut self, accumulator: Accumulator<G>) {
+        self.accumulators.push(accumulator)
+    }
+
+    /// Adds the accumulators of `other`, to decide them together with these ones.
+    pub fn merge(&mut self, other: Accumulators<G>) {
+        self.accumulators.extend(other.accumulators)
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G> {
+    /// Performs the succinct part of [`SRS::verify`] on a batch of opening proofs:
+    /// checks each proof assuming that its commitment to the challenge polynomial is correct,
+    /// without any multi-scalar multiplication of the size of the SRS.
+    /// Note that the sponges of the batch are updated, as in [`SRS::verify`].
+    ///
+    /// Returns the accumulators of the proofs, in the order of the batch, to be decided with [`SRS::decide`],
+    /// or `None` if the batch fails to verify.
+    pub fn succinct_verify<EFqSponge, RNG>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge>],
+        rng: &mut RNG,
+    ) -> Option<Accumulators<G>>
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        let (_, endo_r) = endos::<G>();
+
+        let mut points = vec![self.h];
+        let mut scalars = vec![G::ScalarField::zero()];
+
+        // sample randomiser to scale the proofs with
+        let rand_base = G::ScalarField::rand(rng);
+        let mut rand_base_i = G::ScalarField::one();
+
+        let mut accumulators = Accumulators::default();
+        for proof in batch.iter_mut() {
+            let chals = self.push_opening_terms(
+                group_map,
+                &endo_r,
+                proof,
+                rand_base_i,
+                &mut points,
+                &mut scalars,
+            );
+            accumulators.push(Accumulator::new(chals, proof.opening.sg));
+
+            rand_base_i *= &rand_base;
+        }
+
+        if G::msm(&points, &scalars) == G::Projective::zero() {
+            Some(accumulators)
+        } else {
+            None
+        }
+    }
+
+    /// Decides accumulators: checks that the `sg` of each accumulator is the commitment
+    /// to its challenge polynomial, with a single multi-scalar multiplication of the size of the SRS.
+    /// The accumulators must come from proofs opened with an SRS of the same size.
+    pub fn decide<RNG>(&self, accumulators: &Accumulators<G>, rng: &mut RNG) -> bool
+    where
+        RNG: RngCore + CryptoRng,
+    {
+        let n = self.g.len();
+        let rounds = math::ceil_log2(n);
+        if accumulators.iter().any(|acc| acc.chals.len() != rounds) {
+            return false;
+        }
+
+        let mut points = self.g.clone();
+        let mut scalars = vec![G::ScalarField::zero(); n];
+
+        // sample randomiser to scale the accumulators with
+        let rand_base = G::ScalarField::rand(rng);
+        let mut rand_base_i = G::ScalarField::one();
+
+        for acc in accumulators.iter() {
+            // rand_base_i < s, self.g > - rand_base_i sg
+            // (the coefficients of s beyond the size of the SRS multiply the zero padding of self.g)
+            let s = b_poly_coefficients(&acc.chals);
+            for (scalar, s_i) in scalars[..n].iter_mut().zip(&s) {
+                *scalar += rand_base_i * s_i;
+            }
+            points.push(acc.sg);
+            scalars.push(-rand_base_i);
+
+            rand_base_i *= &rand_base;
+        }
+
+        G::msm(&points, &scalars) == G::Projective::zero()
+    }
+}
@@ -382,8 +382,18 @@ pub trait CommitmentCurve: AffineCurve {
     ) -> Vec<Self> {
         crate::combine::window_combine(g1, g2, x1, x2)
     }
+
+    /// Computes `sum_i scalars[i] bases[i]`
+    fn msm(bases: &[Self], scalars: &[Self::ScalarField]) -> Self::Projective {
+        let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+        VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+    }
 }
 
+/// The minimum number of points of a multi-scalar multiplication accelerated with the GLV endomorphism,
+/// below which the setup of the decomposition of the scalars is not worth it
+const GLV_MSM_MIN_POINTS: usize = 64;
+
 impl<P: SWModelParameters + Clone> CommitmentCurve for SWJAffine<P>
 where
     P::BaseField: PrimeField,
@@ -425,6 +435,25 @@ where
     ) -> Vec<Self> {
         crate::combine::affine_window_combine(g1, g2, x1, x2)
     }
+
+    /// Uses the GLV endomorphism of curves of the form `y^2 = x^3 + b` such as Pallas and Vesta,
+    /// see [`crate::msm`]
+    fn msm(bases: &[Self], scalars: &[Self::ScalarField]) -> Self::Projective {
+        if P::COEFF_A.is_zero() && bases.len() >= GLV_MSM_MIN_POINTS {
+            crate::msm::glv::<P>().msm(bases, scalars)
+        } else {
+            let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+            VariableBaseMSM::multi_scalar_mul(bases, &scalars)
+        }
+    }
+}
+
+/// Returns whether `p`, deserialized without checks, is on the curve and in its prime-order subgroup,
+/// by deserializing its compressed encoding with checks.
+pub(crate) fn is_valid_point<G: AffineCurve>(p: &G) -> bool {
+    let mut compressed = vec![];
+    p.serialize(&mut compressed).is_ok()
+        && G::deserialize(&compressed[..]).map_or(false, |q| q == *p)
 }
 
 pub fn to_group<G: CommitmentCurve>(m: &G::Map, t: <G as AffineCurve>::BaseField) -> G {
@@ -500,8 +529,12 @@ where
 
 /// Contains the batch evaluation
 // TODO: I think we should really change this name to something more correct
-pub struct BatchEvaluationProof<'a, G, EFqSponge>
-where
+pub struct BatchEvaluationProof<
+    'a,
+    G,
+    EFqSponge,
+    OpeningProof = crate::evaluation_proof::OpeningProof<G>,
+> where
     G: AffineCurve,
     EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
 {
@@ -514,7 +547,7 @@ where
     /// scaling factor for polynomials
     pub evalscale: G::ScalarField,
     /// batched opening proof
-    pub opening: &'a OpeningProof<G>,
+    pub opening: &'a OpeningProof,
 }
 
 impl<G: CommitmentCurve> SRS<G> {
@@ -564,17 +597,40 @@ impl<G: CommitmentCurve> SRS<G> {
     /// The function returns an unbounded commitment vector (which splits the commitment into several commitments of size at most `n`),
     /// as well as an optional bounded commitment (if `max` is set).
     /// Note that a maximum degree cannot (and doesn't need to) be enforced via a shift if `max` is a multiple of `n`.
+    /// Sparse polynomials (see [`is_sparse`]) are committed to with [SRS::commit_sparse_non_hiding].
     pub fn commit_non_hiding(
         &self,
         plnm: &DensePolynomial<G::ScalarField>,
         max: Option<usize>,
+    ) -> PolyComm<G> {
+        if is_sparse(&plnm.coeffs) {
+            return self.commit_sparse_non_hiding(plnm, max);
+        }
+        self.commit_non_hiding_with(plnm, max, G::msm)
+    }
+
+    /// Same as [SRS::commit_non_hiding], but skips the zero coefficients of the polynomial
+    /// and adds the points of the coefficients equal to one, whatever the density of the polynomial.
+    pub fn commit_sparse_non_hiding(
+        &self,
+        plnm: &DensePolynomial<G::ScalarField>,
+        max: Option<usize>,
+    ) -> PolyComm<G> {
+        self.commit_non_hiding_with(plnm, max, sparse_msm)
+    }
+
+    fn commit_non_hiding_with(
+        &self,
+        plnm: &DensePolynomial<G::ScalarField>,
+        max: Option<usize>,
+        msm: impl Fn(&[G], &[G::ScalarField]) -> G::Projective,
     ) -> PolyComm<G> {
         let is_zero = plnm.is_zero();
 
         let basis_len = self.g.len();
         let coeffs_len = plnm.coeffs.len();
 
-        let coeffs: Vec<_> = plnm.iter().map(|c| c.into_repr()).collect();
+        let coeffs = &plnm.coeffs;
 
         // chunk while commiting
         let mut unshifted = vec![];
@@ -582,7 +638,7 @@ impl<G: CommitmentCurve> SRS<G> {
             unshifted.push(G::zero());
         } else {
             coeffs.chunks(self.g.len()).for_each(|coeffs_chunk| {
-                let chunk = VariableBaseMSM::multi_scalar_mul(&self.g, coeffs_chunk);
+                let chunk = msm(&self.g, coeffs_chunk);
                 unshifted.push(chunk.into_affine());
             });
         }
@@ -600,10 +656,7 @@ impl<G: CommitmentCurve> SRS<G> {
                     None
                 } else {
                     // we shift the last chunk to the right as proof of the degree bound
-                    let shifted = VariableBaseMSM::multi_scalar_mul(
-                        &self.g[basis_len - (max % basis_len)..],
-                        &coeffs[start..],
-                    );
+                    let shifted = msm(&self.g[basis_len - (max % basis_len)..], &coeffs[start..]);
                     Some(shifted.into_affine())
                 }
             }
@@ -612,17 +665,48 @@ impl<G: CommitmentCurve> SRS<G> {
         PolyComm::<G> { unshifted, shifted }
     }
 
+    /// Commits to a polynomial given by its evaluations over `domain`, with the Lagrange basis of `domain`.
+    /// If the evaluations are over a larger domain, only the evaluations over `domain` are used.
+    /// Sparse evaluations (see [`is_sparse`]) are committed to with [SRS::commit_sparse_evaluations_non_hiding].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the Lagrange basis of `domain` has not been computed,
+    /// or if the evaluations are over a smaller domain.
     pub fn commit_evaluations_non_hiding(
         &self,
         domain: D<G::ScalarField>,
         plnm: &Evaluations<G::ScalarField, D<G::ScalarField>>,
     ) -> PolyComm<G> {
-        let basis = self
-            .lagrange_bases
-            .get(&domain.size())
-            .unwrap_or_else(|| panic!("lagrange bases for size {} not found", domain.size()));
+        let basis = self.lagrange_basis_of(domain);
         let commit_evaluations = |evals: &Vec<G::ScalarField>, basis: &Vec<PolyComm<G>>| {
-            PolyComm::<G>::multi_scalar_mul(&basis.iter().collect::<Vec<_>>()[..], &evals[..])
+            if is_sparse(evals) {
+                let sparse: Vec<_> = evals
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, e)| !e.is_zero())
+                    .map(|(i, e)| (i, *e))
+                    .collect();
+                self.commit_sparse_evaluations_non_hiding(domain, &sparse)
+            } else {
+                // the commitments to the Lagrange basis all have the same number of chunks
+                let num_chunks = basis.first().map_or(1, |comm| comm.unshifted.len());
+                let unshifted = (0..num_chunks)
+                    .map(|chunk| {
+                        let bases: Vec<_> =
+                            basis.iter().map(|comm| comm.unshifted[chunk]).collect();
+                        G::msm(&bases, evals).into_affine()
+                    })
+                    .collect();
+                // as in `PolyComm::multi_scalar_mul`, only the bases with a shifted part are combined
+                let (points, scalars): (Vec<_>, Vec<_>) = basis
+                    .iter()
+                    .zip(evals)
+                    .filter_map(|(comm, e)| comm.shifted.map(|s| (s, *e)))
+                    .unzip();
+                let shifted = (!points.is_empty()).then(|| G::msm(&points, &scalars).into_affine());
+                PolyComm::new(unshifted, shifted)
+            }
         };
         match domain.size.cmp(&plnm.domain().size) {
             std::cmp::Ordering::Less => {
@@ -637,6 +721,44 @@ impl<G: CommitmentCurve> SRS<G> {
         }
     }
 
+    /// Commits to the polynomial whose evaluations over `domain` are zero,
+    /// except at the indices of `evals` where they are the given values,
+    /// by adding up the points of the Lagrange basis of `domain` at these indices.
+    /// The points of the evaluations equal to one are added without any scalar multiplication.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the Lagrange basis of `domain` has not been computed,
+    /// or if an index is not smaller than the size of `domain`.
+    pub fn commit_sparse_evaluations_non_hiding(
+        &self,
+        domain: D<G::ScalarField>,
+        evals: &[(usize, G::ScalarField)],
+    ) -> PolyComm<G> {
+        let basis = self.lagrange_basis_of(domain);
+        let unshifted = (0..basis[0].unshifted.len())
+            .map(|chunk| {
+                let (points, scalars): (Vec<_>, Vec<_>) = evals
+                    .iter()
+                    .map(|(i, e)| (basis[*i].unshifted[chunk], *e))
+                    .unzip();
+                sparse_msm(&points, &scalars).into_affine()
+            })
+            .collect();
+        let (points, scalars): (Vec<_>, Vec<_>) = evals
+            .iter()
+            .filter_map(|(i, e)| basis[*i].shifted.map(|s| (s, *e)))
+            .unzip();
+        let shifted = (!points.is_empty()).then(|| sparse_msm(&points, &scalars).into_affine());
+        PolyComm::new(unshifted, shifted)
+    }
+
+    fn lagrange_basis_of(&self, domain: D<G::ScalarField>) -> &Vec<PolyComm<G>> {
+        self.lagrange_bases
+            .get(&domain.size())
+            .unwrap_or_else(|| panic!("lagrange bases for size {} not found", domain.size()))
+    }
+
     pub fn commit_evaluations(
         &self,
         domain: D<G::ScalarField>,
@@ -662,6 +784,32 @@ impl<G: CommitmentCurve> SRS<G> {
         batch: &mut [BatchEvaluationProof<G, EFqSponge>],
         rng: &mut RNG,
     ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        self.verify_checks(
+            group_map,
+            batch,
+            rng,
+            &[
+                OpeningCheck::ChallengePolynomial,
+                OpeningCheck::CombinedInnerProduct,
+            ],
+        )
+    }
+
+    /// Same as [SRS::verify], but only performs the given `checks`.
+    /// This is useful to find out which check makes a batch fail.
+    /// Note that the sponges of the batch are updated, as in [SRS::verify].
+    pub fn verify_checks<EFqSponge, RNG>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge>],
+        rng: &mut RNG,
+        checks: &[OpeningCheck],
+    ) -> bool
     where
         EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
         RNG: RngCore + CryptoRng,
@@ -708,86 +856,40 @@ impl<G: CommitmentCurve> SRS<G> {
         let rand_base = G::ScalarField::rand(rng);
         let sg_rand_base = G::ScalarField::rand(rng);
 
-        let mut rand_base_i = G::ScalarField::one();
-        let mut sg_rand_base_i = G::ScalarField::one();
-
-        for BatchEvaluationProof {
-            sponge,
-            evaluation_points,
-            polyscale,
-            evalscale,
-            evaluations,
-            opening,
-        } in batch.iter_mut()
-        {
-            // TODO: This computation is repeated in ProverProof::oracles
-            let combined_inner_product0 = {
-                let es: Vec<_> = evaluations
-                    .iter()
-                    .map(
-                        |Evaluation {
-                             commitment,
-                             evaluations,
-                             degree_bound,
-                         }| {
-                            let bound: Option<usize> = (|| {
-                                let b = (*degree_bound)?;
-                                let x = commitment.shifted?;
-                                if x.is_zero() {
-                                    None
-                                } else {
-                                    Some(b)
-                                }
-                            })();
-                            (evaluations.clone(), bound)
-                        },
-                    )
-                    .collect();
-                combined_inner_product(evaluation_points, polyscale, evalscale, &es, self.g.len())
-            };
-
-            sponge.absorb_fr(&[shift_scalar::<G>(combined_inner_product0)]);
-
-            let t = sponge.challenge_fq();
-            let u: G = to_group(group_map, t);
-
-            let Challenges { chal, chal_inv } = opening.challenges::<EFqSponge>(&endo_r, sponge);
-
-            sponge.absorb_g(&[opening.delta]);
-            let c = ScalarChallenge(sponge.challenge()).to_field(&endo_r);
-
-            // < s, sum_i evalscale^i pows(evaluation_point[i]) >
-            // ==
-            // sum_i evalscale^i < s, pows(evaluation_point[i]) >
-            let b0 = {
-                let mut scale = G::ScalarField::one();
-                let mut res = G::ScalarField::zero();
-                for &e in evaluation_points.iter() {
-                    let term = b_poly(&chal, e);
-                    res += &(scale * term);
-                    scale *= *evalscale;
-                }
-                res
-            };
-
-            let s = b_poly_coefficients(&chal);
-
-            let neg_rand_base_i = -rand_base_i;
+        // a check is left out by giving it a zero weight
+        let weight = |check| {
+            if checks.contains(&check) {
+                G::ScalarField::one()
+            } else {
+                G::ScalarField::zero()
+            }
+        };
+        let mut rand_base_i = weight(OpeningCheck::CombinedInnerProduct);
+        let mut sg_rand_base_i = weight(OpeningCheck::ChallengePolynomial);
+
+        for proof in batch.iter_mut() {
+            let chal = self.push_opening_terms(
+                group_map,
+                &endo_r,
+                proof,
+                rand_base_i,
+                &mut points,
+                &mut scalars,
+            );
 
             // TERM
-            // - rand_base_i z1 G
+            // - sg_rand_base_i sg
             //
-            // we also add -sg_rand_base_i * G to check correctness of sg.
-            points.push(opening.sg);
-            scalars.push(neg_rand_base_i * opening.z1 - sg_rand_base_i);
-
-            // Here we add
+            // Here we also add
             // sg_rand_base_i * ( < s, self.g > )
             // =
             // < sg_rand_base_i s, self.g >
             //
             // to check correctness of the sg component.
+            points.push(proof.opening.sg);
+            scalars.push(-sg_rand_base_i);
             {
+                let s = b_poly_coefficients(&chal);
                 let terms: Vec<_> = s.par_iter().map(|s| sg_rand_base_i * s).collect();
 
                 for (i, term) in terms.iter().enumerate() {
@@ -795,80 +897,201 @@ impl<G: CommitmentCurve> SRS<G> {
                 }
             }
 
-            // TERM
-            // - rand_base_i * z2 * H
-            scalars[0] -= &(rand_base_i * opening.z2);
+            rand_base_i *= &rand_base;
+            sg_rand_base_i *= &sg_rand_base;
+        }
 
-            // TERM
-            // -rand_base_i * (z1 * b0 * U)
-            scalars.push(neg_rand_base_i * (opening.z1 * b0));
-            points.push(u);
+        // verify the equation
+        G::msm(&points, &scalars).is_zero()
+    }
 
-            // TERM
-            // rand_base_i c_i Q_i
-            // = rand_base_i c_i
-            //   (sum_j (chal_invs[j] L_j + chals[j] R_j) + P_prime)
-            // where P_prime = combined commitment + combined_inner_product * U
-            let rand_base_i_c_i = c * rand_base_i;
-            for ((l, r), (u_inv, u)) in opening.lr.iter().zip(chal_inv.iter().zip(chal.iter())) {
-                points.push(*l);
-                scalars.push(rand_base_i_c_i * u_inv);
-
-                points.push(*r);
-                scalars.push(rand_base_i_c_i * u);
+    /// Adds the terms of the opening equation of `proof`, scaled by `rand_base_i`,
+    /// to the multi-scalar multiplication of `points` and `scalars`, whose first point must be `self.h`.
+    /// The commitment `sg` of the proof to its challenge polynomial is used as is,
+    /// so that the terms do not involve `self.g`.
+    /// Returns the challenges of the proof.
+    pub(crate) fn push_opening_terms<EFqSponge>(
+        &self,
+        group_map: &G::Map,
+        endo_r: &G::ScalarField,
+        proof: &mut BatchEvaluationProof<G, EFqSponge>,
+        rand_base_i: G::ScalarField,
+        points: &mut Vec<G>,
+        scalars: &mut Vec<G::ScalarField>,
+    ) -> Vec<G::ScalarField>
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        G::BaseField: PrimeField,
+    {
+        let BatchEvaluationProof {
+            sponge,
+            evaluation_points,
+            polyscale,
+            evalscale,
+            evaluations,
+            opening,
+        } = proof;
+
+        // TODO: This computation is repeated in ProverProof::oracles
+        let combined_inner_product0 = {
+            let es: Vec<_> = evaluations
+                .iter()
+                .map(
+                    |Evaluation {
+                         commitment,
+                         evaluations,
+                         degree_bound,
+                     }| {
+                        let bound: Option<usize> = (|| {
+                            let b = (*degree_bound)?;
+                            let x = commitment.shifted?;
+                            if x.is_zero() {
+                                None
+                            } else {
+                                Some(b)
+                            }
+                        })();
+                        (evaluations.clone(), bound)
+                    },
+                )
+                .collect();
+            combined_inner_product(evaluation_points, polyscale, evalscale, &es, self.g.len())
+        };
+
+        sponge.absorb_fr(&[shift_scalar::<G>(combined_inner_product0)]);
+
+        let t = sponge.challenge_fq();
+        let u: G = to_group(group_map, t);
+
+        let Challenges { chal, chal_inv } = opening.challenges::<EFqSponge>(endo_r, sponge);
+
+        sponge.absorb_g(&[opening.delta]);
+        let c = ScalarChallenge(sponge.challenge()).to_field(endo_r);
+
+        // < s, sum_i evalscale^i pows(evaluation_point[i]) >
+        // ==
+        // sum_i evalscale^i < s, pows(evaluation_point[i]) >
+        let b0 = {
+            let mut scale = G::ScalarField::one();
+            let mut res = G::ScalarField::zero();
+            for &e in evaluation_points.iter() {
+                let term = b_poly(&chal, e);
+                res += &(scale * term);
+                scale *= *evalscale;
             }
+            res
+        };
 
-            // TERM
-            // sum_j evalscale^j (sum_i polyscale^i f_i) (elm_j)
-            // == sum_j sum_i evalscale^j polyscale^i f_i(elm_j)
-            // == sum_i polyscale^i sum_j evalscale^j f_i(elm_j)
+        let neg_rand_base_i = -rand_base_i;
+
+        // TERM
+        // - rand_base_i z1 G
+        points.push(opening.sg);
+        scalars.push(neg_rand_base_i * opening.z1);
+
+        // TERM
+        // - rand_base_i * z2 * H
+        scalars[0] -= &(rand_base_i * opening.z2);
+
+        // TERM
+        // -rand_base_i * (z1 * b0 * U)
+        scalars.push(neg_rand_base_i * (opening.z1 * b0));
+        points.push(u);
+
+        // TERM
+        // rand_base_i c_i Q_i
+        // = rand_base_i c_i
+        //   (sum_j (chal_invs[j] L_j + chals[j] R_j) + P_prime)
+        // where P_prime = combined commitment + combined_inner_product * U
+        let rand_base_i_c_i = c * rand_base_i;
+        for ((l, r), (u_inv, u)) in opening.lr.iter().zip(chal_inv.iter().zip(chal.iter())) {
+            points.push(*l);
+            scalars.push(rand_base_i_c_i * u_inv);
+
+            points.push(*r);
+            scalars.push(rand_base_i_c_i * u);
+        }
+
+        // TERM
+        // sum_j evalscale^j (sum_i polyscale^i f_i) (elm_j)
+        // == sum_j sum_i evalscale^j polyscale^i f_i(elm_j)
+        // == sum_i polyscale^i sum_j evalscale^j f_i(elm_j)
+        {
+            let mut xi_i = G::ScalarField::one();
+
+            for Evaluation {
+                commitment,
+                degree_bound,
+                ..
+            } in evaluations
+                .iter()
+                .filter(|x| !x.commitment.unshifted.is_empty())
             {
-                let mut xi_i = G::ScalarField::one();
+                // iterating over the polynomial segments
+                for comm_ch in &commitment.unshifted {
+                    scalars.push(rand_base_i_c_i * xi_i);
+                    points.push(*comm_ch);
 
-                for Evaluation {
-                    commitment,
-                    degree_bound,
-                    ..
-                } in evaluations
-                    .iter()
-                    .filter(|x| !x.commitment.unshifted.is_empty())
-                {
-                    // iterating over the polynomial segments
-                    for comm_ch in &commitment.unshifted {
-                        scalars.push(rand_base_i_c_i * xi_i);
-                        points.push(*comm_ch);
-
-                        xi_i *= *polyscale;
-                    }
+                    xi_i *= *polyscale;
+                }
 
-                    if let Some(_m) = degree_bound {
-                        if let Some(comm_ch) = commitment.shifted {
-                            if !comm_ch.is_zero() {
-                                // polyscale^i sum_j evalscale^j elm_j^{N - m} f(elm_j)
-                                scalars.push(rand_base_i_c_i * xi_i);
-                                points.push(comm_ch);
+                if let Some(_m) = degree_bound {
+                    if let Some(comm_ch) = commitment.shifted {
+                        if !comm_ch.is_zero() {
+                            // polyscale^i sum_j evalscale^j elm_j^{N - m} f(elm_j)
+                            scalars.push(rand_base_i_c_i * xi_i);
+                            points.push(comm_ch);
 
-                                xi_i *= *polyscale;
-                            }
+                            xi_i *= *polyscale;
                         }
                     }
                 }
-            };
+            }
+        };
 
-            scalars.push(rand_base_i_c_i * combined_inner_product0);
-            points.push(u);
+        scalars.push(rand_base_i_c_i * combined_inner_product0);
+        points.push(u);
 
-            scalars.push(rand_base_i);
-            points.push(opening.delta);
+        scalars.push(rand_base_i);
+        points.push(opening.delta);
 
-            rand_base_i *= &rand_base;
-            sg_rand_base_i *= &sg_rand_base;
-        }
+        chal
+    }
+}
 
-        // verify the equation
-        let scalars: Vec<_> = scalars.iter().map(|x| x.into_repr()).collect();
-        VariableBaseMSM::multi_scalar_mul(&points, &scalars) == G::Projective::zero()
+/// A check performed when verifying a batch of opening proofs (see [SRS::verify_checks])
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpeningCheck {
+    /// The commitment `sg` to the challenge polynomial is correct
+    ChallengePolynomial,
+    /// The opening proof opens the combined commitment to the combined inner product of the evaluations
+    CombinedInnerProduct,
+}
+
+/// Scalars are committed to by skipping their zeros if at most one in `SPARSE_THRESHOLD` of them is non-zero,
+/// as for most selector polynomials and many witness columns.
+pub const SPARSE_THRESHOLD: usize = 4;
+
+/// Returns true if at most one in [`SPARSE_THRESHOLD`] of the scalars is non-zero.
+pub fn is_sparse<F: Zero>(xs: &[F]) -> bool {
+    xs.iter().filter(|x| !x.is_zero()).count() * SPARSE_THRESHOLD <= xs.len()
+}
+
+/// Computes `<scalars, bases>` with a multi-scalar multiplication of the non-zero scalars only,
+/// except for the scalars equal to one whose bases are simply added.
+fn sparse_msm<G: AffineCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G::Projective {
+    let mut ones = G::Projective::zero();
+    let mut points = vec![];
+    let mut reprs = vec![];
+    for (g, s) in bases.iter().zip(scalars) {
+        if s.is_one() {
+            ones.add_assign_mixed(g);
+        } else if !s.is_zero() {
+            points.push(*g);
+            reprs.push(s.into_repr());
+        }
     }
+    ones + VariableBaseMSM::multi_scalar_mul(&points, &reprs)
 }
 
 pub fn inner_prod<F: Field>(xs: &[F], ys: &[F]) -> F {
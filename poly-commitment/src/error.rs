@@ -6,4 +6,76 @@ pub enum CommitmentError {
         "the length of the given blinders ({0}) don't match the length of the commitment ({1})"
     )]
     BlindersDontMatch(usize, usize),
+    #[error("the polynomial commitment scheme cannot open hiding commitments")]
+    HidingNotSupported,
+}
+
+/// Errors that can occur when loading the powers of tau of a `.ptau` file
+#[derive(Error, Debug)]
+pub enum PtauError {
+    #[error("cannot read the .ptau file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a .ptau file")]
+    InvalidMagic,
+    #[error("unsupported .ptau version {0}")]
+    UnsupportedVersion(u32),
+    #[error("the .ptau file has no section {0}")]
+    MissingSection(u32),
+    #[error("the .ptau file is for another curve")]
+    WrongCurve,
+    #[error("the .ptau file contains {available} powers of tau, but {requested} were requested")]
+    NotEnoughPowers { requested: usize, available: usize },
+    #[error("the .ptau file contains an invalid point")]
+    InvalidPoint,
+    #[error("the powers of tau of the .ptau file are inconsistent")]
+    InconsistentPowers,
+}
+
+/// Errors that can occur when loading or saving Lagrange bases of a [`crate::lagrange_cache::LagrangeCache`]
+#[derive(Error, Debug)]
+pub enum LagrangeCacheError {
+    #[error("cannot access the Lagrange basis cache: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a Lagrange basis cache file")]
+    InvalidMagic,
+    #[error("unsupported Lagrange basis cache version {0}")]
+    UnsupportedVersion(u32),
+    #[error("the cache file contains the Lagrange basis of a domain of size {found}, but {expected} was expected")]
+    WrongDomainSize { expected: usize, found: usize },
+    #[error("the cache file was computed from another SRS")]
+    WrongSrs,
+    #[error("the cache file contains an invalid point")]
+    InvalidPoint,
+    #[error("the cache file is corrupted")]
+    Corrupted,
+}
+
+/// Errors that can occur when reading or writing an SRS in the compressed format
+#[derive(Error, Debug)]
+pub enum SrsFileError {
+    #[error("cannot access the SRS file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not a compressed SRS file")]
+    InvalidMagic,
+    #[error("unsupported SRS file version {0}")]
+    UnsupportedVersion(u32),
+    #[error("the SRS file contains an invalid point")]
+    InvalidPoint,
+    #[error("the SRS file is corrupted")]
+    Corrupted,
+}
+
+/// Errors that can occur when creating a range proof
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeProofError {
+    #[error("the number of bits of a range proof must be a power of two of at most 64, not {0}")]
+    InvalidBits(usize),
+    #[error("the number of values of a range proof must be a non-zero power of two, not {0}")]
+    InvalidNumberOfValues(usize),
+    #[error("the number of blinders ({blinders}) does not match the number of values ({values})")]
+    BlindersDontMatch { values: usize, blinders: usize },
+    #[error("the value {0} is out of the range of the proof")]
+    ValueOutOfRange(u64),
+    #[error("the SRS is too small for a range proof of {0} bits in total")]
+    SrsTooSmall(usize),
 }
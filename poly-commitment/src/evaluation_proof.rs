@@ -1,3 +1,5 @@
+use crate::error::CommitmentError;
+use crate::pcs::OpenProof;
 use crate::srs::SRS;
 use crate::{commitment::*, srs::endos};
 use ark_ec::{msm::VariableBaseMSM, AffineCurve, ProjectiveCurve};
@@ -10,7 +12,7 @@ use rand_core::{CryptoRng, RngCore};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::iter::Iterator;
+use std::{convert::Infallible, iter::Iterator};
 
 enum OptShiftedPolynomial<P> {
     Unshifted(P),
@@ -94,9 +96,55 @@ impl<G: CommitmentCurve> SRS<G> {
         elm: &[G::ScalarField],    // vector of evaluation points
         polyscale: G::ScalarField, // scaling factor for polynoms
         evalscale: G::ScalarField, // scaling factor for evaluation point powers
-        mut sponge: EFqSponge,     // sponge
+        sponge: EFqSponge,         // sponge
         rng: &mut RNG,
     ) -> OpeningProof<G>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        let no_progress = &mut |_| Ok::<(), Infallible>(());
+        match self.open_with_progress(
+            group_map,
+            plnms,
+            elm,
+            polyscale,
+            evalscale,
+            sponge,
+            rng,
+            no_progress,
+        ) {
+            Ok(proof) => proof,
+            Err(e) => match e {},
+        }
+    }
+
+    /// Same as [`SRS::open`], except that `on_round` is called with the index of each round
+    /// of the argument before computing it.
+    /// If `on_round` returns an error, the opening stops there and returns that error.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `on_round` does.
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    #[allow(clippy::many_single_char_names)]
+    pub fn open_with_progress<EFqSponge, RNG, D: EvaluationDomain<G::ScalarField>, E>(
+        &self,
+        group_map: &G::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<G::ScalarField, D>,
+            Option<usize>,
+            PolyComm<G::ScalarField>,
+        )],
+        elm: &[G::ScalarField],
+        polyscale: G::ScalarField,
+        evalscale: G::ScalarField,
+        mut sponge: EFqSponge,
+        rng: &mut RNG,
+        on_round: &mut dyn FnMut(usize) -> Result<(), E>,
+    ) -> Result<OpeningProof<G>, E>
     where
         EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
         RNG: RngCore + CryptoRng,
@@ -244,7 +292,9 @@ impl<G: CommitmentCurve> SRS<G> {
         let mut chals = vec![];
         let mut chal_invs = vec![];
 
-        for _ in 0..rounds {
+        for round in 0..rounds {
+            on_round(round)?;
+
             let n = g.len() / 2;
             let (g_lo, g_hi) = (g[0..n].to_vec(), g[n..].to_vec());
             let (a_lo, a_hi) = (&a[0..n], &a[n..]);
@@ -337,13 +387,13 @@ impl<G: CommitmentCurve> SRS<G> {
         let z1 = a0 * c + d;
         let z2 = c * r_prime + r_delta;
 
-        OpeningProof {
+        Ok(OpeningProof {
             delta,
             lr,
             z1,
             z2,
             sg: g0,
-        }
+        })
     }
 }
 
@@ -409,3 +459,84 @@ impl<G: AffineCurve> OpeningProof<G> {
         Challenges { chal, chal_inv }
     }
 }
+
+impl<G: CommitmentCurve> OpenProof<G> for OpeningProof<G> {
+    type SRS = SRS<G>;
+
+    fn open<EFqSponge, RNG, D: EvaluationDomain<G::ScalarField>>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<G::ScalarField, D>,
+            Option<usize>,
+            PolyComm<G::ScalarField>,
+        )],
+        elm: &[G::ScalarField],
+        polyscale: G::ScalarField,
+        evalscale: G::ScalarField,
+        sponge: EFqSponge,
+        rng: &mut RNG,
+    ) -> Result<Self, CommitmentError>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        Ok(srs.open(group_map, plnms, elm, polyscale, evalscale, sponge, rng))
+    }
+
+    fn open_with_progress<EFqSponge, RNG, D: EvaluationDomain<G::ScalarField>, E>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<G::ScalarField, D>,
+            Option<usize>,
+            PolyComm<G::ScalarField>,
+        )],
+        elm: &[G::ScalarField],
+        polyscale: G::ScalarField,
+        evalscale: G::ScalarField,
+        sponge: EFqSponge,
+        rng: &mut RNG,
+        on_round: &mut dyn FnMut(usize) -> Result<(), E>,
+    ) -> Result<Self, E>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+        E: From<CommitmentError>,
+    {
+        srs.open_with_progress(
+            group_map, plnms, elm, polyscale, evalscale, sponge, rng, on_round,
+        )
+    }
+
+    fn verify<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge, Self>],
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        srs.verify(group_map, batch, rng)
+    }
+
+    fn verify_checks<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge, Self>],
+        rng: &mut RNG,
+        checks: &[OpeningCheck],
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        srs.verify_checks(group_map, batch, rng, checks)
+    }
+}
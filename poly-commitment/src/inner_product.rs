@@ -0,0 +1,293 @@
+//! This module implements a standalone inner-product argument [BCCGP16, BBBPWM18] over arbitrary vectors,
+//! with Pedersen vector commitments under the points of an [`SRS`].
+//!
+//! The opening proofs of polynomials ([`SRS::open`]) run an inner-product argument
+//! between the coefficients of a polynomial and the powers of the evaluation points, which are public.
+//! Here, both vectors `a` and `b` of length `n` are committed to, in `P = <a, G> + <b, H>`,
+//! where the bases `G` and `H` are the first `2n` points of the SRS (see [`SRS::inner_product_bases`]).
+//! An [`InnerProductProof`] proves the knowledge of `a` and `b` such that `P = <a, G> + <b, H>` and `c = <a, b>`,
+//! with `2 log(n)` points and two scalars.
+//! The points of `H` can be scaled by public factors, as needed by range proofs.
+//!
+//! As in the opening proofs, the commitment `P` must have been absorbed by the sponge before the argument,
+//! which absorbs `c` itself.
+//! The argument is not zero-knowledge: the protocols built on it, such as range proofs,
+//! blind the vectors before running it.
+//!
+//! [BCCGP16]: https://eprint.iacr.org/2016/263
+//! [BBBPWM18]: https://eprint.iacr.org/2017/1066
+
+use crate::{
+    commitment::{
+        b_poly_coefficients, inner_prod, shift_scalar, squeeze_challenge, to_group, CommitmentCurve,
+    },
+    srs::{endos, SRS},
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{batch_inversion, Field, One, PrimeField, Zero};
+use mina_poseidon::FqSponge;
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// A proof of knowledge of two vectors, given a commitment to them and their inner product.
+/// See the [module documentation](self).
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "G: ark_serialize::CanonicalDeserialize + ark_serialize::CanonicalSerialize")]
+pub struct InnerProductProof<G: AffineCurve> {
+    /// vector of rounds of L & R commitments
+    #[serde_as(as = "Vec<(o1_utils::serialization::SerdeAs, o1_utils::serialization::SerdeAs)>")]
+    pub lr: Vec<(G, G)>,
+    /// The vector `a` folded to a single scalar
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub a: G::ScalarField,
+    /// The vector `b` folded to a single scalar
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub b: G::ScalarField,
+}
+
+impl<G: CommitmentCurve> SRS<G> {
+    /// Computes the Pedersen commitment `<a, g> + blinder h` to the vector `a`,
+    /// with the first points `g` of the SRS.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `a` is longer than the SRS.
+    pub fn commit_vector(&self, a: &[G::ScalarField], blinder: G::ScalarField) -> G {
+        assert!(a.len() <= self.g.len());
+        let bases: Vec<_> = self.g[..a.len()].iter().chain([&self.h]).copied().collect();
+        let scalars: Vec<_> = a.iter().chain([&blinder]).copied().collect();
+        G::msm(&bases, &scalars).into_affine()
+    }
+
+    /// Computes the Pedersen commitment `<a, G> + <b, H> + blinder h` to the vectors `a` and `b`,
+    /// with the [bases](SRS::inner_product_bases) of the inner-product argument.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `a` and `b` do not have the same length, or if there are no bases for this length.
+    pub fn commit_vectors(
+        &self,
+        a: &[G::ScalarField],
+        b: &[G::ScalarField],
+        blinder: G::ScalarField,
+    ) -> G {
+        assert_eq!(a.len(), b.len());
+        let (g, h) = self
+            .inner_product_bases(a.len())
+            .expect("no bases for vectors of this length");
+        let bases: Vec<_> = g.iter().chain(h).chain([&self.h]).copied().collect();
+        let scalars: Vec<_> = a.iter().chain(b).chain([&blinder]).copied().collect();
+        G::msm(&bases, &scalars).into_affine()
+    }
+
+    /// Returns the bases `G` and `H` of the inner-product argument for vectors of length `n`,
+    /// which are the first `n` points of the SRS and the `n` following ones,
+    /// or `None` if `n` is not a power of two or the SRS has less than `2n` points.
+    pub fn inner_product_bases(&self, n: usize) -> Option<(&[G], &[G])> {
+        if !n.is_power_of_two() || 2 * n > self.g.len() {
+            return None;
+        }
+        Some((&self.g[..n], &self.g[n..2 * n]))
+    }
+}
+
+impl<G: CommitmentCurve> InnerProductProof<G>
+where
+    G::BaseField: PrimeField,
+{
+    /// Proves the knowledge of `a` and `b` such that `P = <a, g> + <b, h'>`,
+    /// where `h'` are the points `h` scaled by `h_factors` if given.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the vectors, the bases and the factors do not have the same length,
+    /// or if this length is not a power of two.
+    pub fn create<EFqSponge>(
+        group_map: &G::Map,
+        sponge: &mut EFqSponge,
+        g: &[G],
+        h: &[G],
+        h_factors: Option<&[G::ScalarField]>,
+        mut a: Vec<G::ScalarField>,
+        mut b: Vec<G::ScalarField>,
+    ) -> Self
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+    {
+        let n = a.len();
+        assert!(n.is_power_of_two());
+        assert!(b.len() == n && g.len() == n && h.len() == n);
+        let (_, endo_r) = endos::<G>();
+
+        let mut g = g.to_vec();
+        let mut h = match h_factors {
+            Some(factors) => scale_points(h, factors),
+            None => h.to_vec(),
+        };
+
+        let c = inner_prod(&a, &b);
+        sponge.absorb_fr(&[shift_scalar::<G>(c)]);
+        let u: G = to_group(group_map, sponge.challenge_fq());
+
+        let mut lr = vec![];
+        while a.len() > 1 {
+            let n = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(n);
+            let (b_lo, b_hi) = b.split_at(n);
+
+            // L = <a_hi, g_lo> + <b_lo, h_hi> + <a_hi, b_lo> u
+            let l = msm(
+                &[&g[..n], &h[n..], &[u]].concat(),
+                &[a_hi, b_lo, &[inner_prod(a_hi, b_lo)]].concat(),
+            );
+            // R = <a_lo, g_hi> + <b_hi, h_lo> + <a_lo, b_hi> u
+            let r = msm(
+                &[&g[n..], &h[..n], &[u]].concat(),
+                &[a_lo, b_hi, &[inner_prod(a_lo, b_hi)]].concat(),
+            );
+            lr.push((l, r));
+
+            sponge.absorb_g(&[l]);
+            sponge.absorb_g(&[r]);
+            let chal = squeeze_challenge(&endo_r, sponge);
+            let chal_inv = chal.inverse().unwrap();
+
+            // the folded vectors satisfy P' = P + chal_inv L + chal R
+            a = fold(a_lo, a_hi, chal_inv);
+            b = fold(b_lo, b_hi, chal);
+            g = G::combine_one(&g[..n], &g[n..], chal);
+            h = G::combine_one(&h[..n], &h[n..], chal_inv);
+        }
+
+        InnerProductProof {
+            lr,
+            a: a[0],
+            b: b[0],
+        }
+    }
+
+    /// Verifies this proof of knowledge of `a` and `b` such that `p = <a, g> + <b, h'>` and `c = <a, b>`,
+    /// where `h'` are the points `h` scaled by `h_factors` if given.
+    /// The bases are only used in a single multi-scalar multiplication.
+    #[allow(clippy::too_many_arguments)]
+    pub fn verify<EFqSponge>(
+        &self,
+        group_map: &G::Map,
+        sponge: &mut EFqSponge,
+        g: &[G],
+        h: &[G],
+        h_factors: Option<&[G::ScalarField]>,
+        p: G,
+        c: G::ScalarField,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+    {
+        let mut points = vec![p];
+        let mut scalars = vec![G::ScalarField::one()];
+        if !self.push_verification_terms(
+            group_map,
+            sponge,
+            g,
+            h,
+            h_factors,
+            c,
+            G::ScalarField::one(),
+            &mut points,
+            &mut scalars,
+        ) {
+            return false;
+        }
+
+        G::msm(&points, &scalars).is_zero()
+    }
+
+    /// Pushes the points and scalars of the verification equation of this proof, multiplied by `scale`,
+    /// to `points` and `scalars`: the proof is valid if the multi-scalar multiplication of
+    /// `scale P` and the pushed terms is zero, where `P` is the commitment to the vectors.
+    /// The bases `g`, `h` and `h_factors` are the ones of [`InnerProductProof::verify`].
+    ///
+    /// Returns `false`, without pushing anything, if the proof does not have the size of the bases.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn push_verification_terms<EFqSponge>(
+        &self,
+        group_map: &G::Map,
+        sponge: &mut EFqSponge,
+        g: &[G],
+        h: &[G],
+        h_factors: Option<&[G::ScalarField]>,
+        c: G::ScalarField,
+        scale: G::ScalarField,
+        points: &mut Vec<G>,
+        scalars: &mut Vec<G::ScalarField>,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+    {
+        let n = g.len();
+        if !n.is_power_of_two()
+            || h.len() != n
+            || h_factors.map_or(false, |factors| factors.len() != n)
+            || self.lr.len() != n.trailing_zeros() as usize
+        {
+            return false;
+        }
+        let (_, endo_r) = endos::<G>();
+
+        sponge.absorb_fr(&[shift_scalar::<G>(c)]);
+        let u: G = to_group(group_map, sponge.challenge_fq());
+
+        let chals: Vec<_> = self
+            .lr
+            .iter()
+            .map(|(l, r)| {
+                sponge.absorb_g(&[*l]);
+                sponge.absorb_g(&[*r]);
+                squeeze_challenge(&endo_r, sponge)
+            })
+            .collect();
+        let mut chal_invs = chals.clone();
+        batch_inversion(&mut chal_invs);
+
+        // the folded bases are <s, g> and <s_inv, h'>
+        let s = b_poly_coefficients(&chals);
+        let s_inv = b_poly_coefficients(&chal_invs);
+
+        // P + c u + sum_i (chal_inv_i L_i + chal_i R_i) = a <s, g> + b <s_inv, h'> + a b u
+        points.push(u);
+        scalars.push(scale * (c - self.a * self.b));
+        for ((l, r), (chal, chal_inv)) in self.lr.iter().zip(chals.iter().zip(&chal_invs)) {
+            points.extend([*l, *r]);
+            scalars.extend([scale * chal_inv, scale * chal]);
+        }
+        let scaled_a = -scale * self.a;
+        points.extend(g);
+        scalars.extend(s.iter().map(|s| scaled_a * s));
+        let scaled_b = -scale * self.b;
+        points.extend(h);
+        match h_factors {
+            Some(factors) => {
+                scalars.extend(s_inv.iter().zip(factors).map(|(s, f)| scaled_b * s * f))
+            }
+            None => scalars.extend(s_inv.iter().map(|s| scaled_b * s)),
+        }
+        true
+    }
+}
+
+fn msm<G: CommitmentCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G {
+    G::msm(bases, scalars).into_affine()
+}
+
+/// Returns `lo + x hi`
+fn fold<F: Field>(lo: &[F], hi: &[F], x: F) -> Vec<F> {
+    lo.iter().zip(hi).map(|(lo, hi)| *lo + x * hi).collect()
+}
+
+fn scale_points<G: AffineCurve>(points: &[G], factors: &[G::ScalarField]) -> Vec<G> {
+    assert_eq!(points.len(), factors.len());
+    let mut scaled: Vec<_> = points.iter().zip(factors).map(|(p, f)| p.mul(*f)).collect();
+    G::Projective::batch_normalization(&mut scaled);
+    scaled.iter().map(|p| p.into_affine()).collect()
+}
@@ -0,0 +1,603 @@
+//! This module implements the KZG polynomial commitment scheme [KZG10] over pairing-friendly curves,
+//! whose openings are made of a constant number of group elements and can be verified on the EVM.
+//!
+//! - [`PairingSRS`] contains powers of a secret `tau` in both groups of a pairing.
+//!   It commits to polynomials exactly like the [`SRS`] of the inner-product argument
+//!   (with the same chunking and the same blinding generator),
+//!   as its powers of `tau` in the first group form an [`SRS`].
+//!   It can be generated locally for tests with [`PairingSRS::create_trusted_setup`],
+//!   or loaded from the `.ptau` file of a powers-of-tau ceremony with [`PairingSRS::load_ptau`].
+//! - [`KZGProof`] opens a batch of commitments at a set of points.
+//!   As in Shplonk [BDFG20], the polynomials are combined with the powers of `polyscale`,
+//!   and the prover commits to the quotient of the combined polynomial
+//!   by the vanishing polynomial of the points.
+//!   All the polynomials of a batch being opened at the same points, a single quotient suffices
+//!   (polynomials can be opened at different points with [`crate::multi_point`]).
+//!   A batch of proofs is verified with a single product of pairings.
+//!
+//! Both implement the traits of [`crate::pcs`].
+//! The openings are not zero-knowledge: only non-hiding commitments (with zero blinders) can be opened,
+//! and degree bounds (shifted commitments) are not supported.
+//!
+//! [KZG10]: https://www.iacr.org/archive/asiacrypt2010/6477178/6477178.pdf
+//! [BDFG20]: https://eprint.iacr.org/2020/081
+
+use crate::{
+    commitment::{
+        is_valid_point, BatchEvaluationProof, BlindedCommitment, CommitmentCurve, Evaluation,
+    },
+    error::{CommitmentError, PtauError},
+    evaluation_proof::DensePolynomialOrEvaluations,
+    pcs::{combine_evaluations, combine_polys, CommitmentKey, OpenProof},
+    srs::{misc_points, SRS},
+    PolyComm,
+};
+use ark_ec::{
+    msm::{FixedBaseMSM, VariableBaseMSM},
+    AffineCurve, PairingEngine, ProjectiveCurve,
+};
+use ark_ff::{BigInteger, Field, FpParameters, One, PrimeField, UniformRand, Zero};
+use ark_poly::{
+    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    EvaluationDomain, Evaluations, Radix2EvaluationDomain, UVPolynomial,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use groupmap::GroupMap;
+use mina_poseidon::FqSponge;
+use o1_utils::dense_polynomial::{interpolate, vanishing_polynomial};
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, SeekFrom},
+};
+
+/// The maximum number of points at which a batch can be opened,
+/// with the SRS created by [`PairingSRS::create_trusted_setup`] or loaded by [`PairingSRS::load_ptau`].
+pub const MAX_EVALUATION_POINTS: usize = 8;
+
+/// The powers of a secret `tau` in both groups of a pairing, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct PairingSRS<E: PairingEngine>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    /// The powers of `tau` in the first group, and the blinding generator,
+    /// used to commit to polynomials and to open commitments
+    pub full_srs: SRS<E::G1Affine>,
+    /// The first powers of `tau` in the second group,
+    /// used to verify openings at up to `verifier_srs.len() - 1` points
+    pub verifier_srs: Vec<E::G2Affine>,
+}
+
+/// The opening of a batch of KZG commitments at a set of points.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    bound = "E::G1Affine: ark_serialize::CanonicalDeserialize + ark_serialize::CanonicalSerialize"
+)]
+pub struct KZGProof<E: PairingEngine> {
+    /// The commitment to the quotient of the combined polynomial by the vanishing polynomial of the points
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub quotient: E::G1Affine,
+}
+
+/// The KZG commitment key over BN254
+pub type BN254SRS = PairingSRS<ark_bn254::Bn254>;
+
+/// The KZG opening proof over BN254
+pub type BN254Proof = KZGProof<ark_bn254::Bn254>;
+
+impl<E: PairingEngine> Default for PairingSRS<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    fn default() -> Self {
+        PairingSRS {
+            full_srs: SRS::default(),
+            verifier_srs: vec![],
+        }
+    }
+}
+
+impl<E: PairingEngine> PairingSRS<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    /// Creates an SRS with `depth` powers of `tau` in the first group,
+    /// and [`MAX_EVALUATION_POINTS`] + 1 powers of `tau` in the second group.
+    /// Anyone knowing `tau` can open commitments to any value:
+    /// this is only suitable for testing.
+    pub fn create_trusted_setup(tau: E::Fr, depth: usize) -> Self {
+        let [h] = misc_points(&<E::G1Affine as CommitmentCurve>::Map::setup());
+        PairingSRS {
+            full_srs: SRS {
+                g: powers_of_generator::<E::G1Projective>(tau, depth),
+                h,
+                lagrange_bases: HashMap::new(),
+            },
+            verifier_srs: powers_of_generator::<E::G2Projective>(tau, MAX_EVALUATION_POINTS + 1),
+        }
+    }
+
+    /// Loads the first `depth` powers of `tau` in the first group (and up to [`MAX_EVALUATION_POINTS`] + 1
+    /// powers of `tau` in the second group) from a `.ptau` file, as produced by snarkjs.
+    /// The blinding generator is derived as in [`SRS::create`].
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the file cannot be read, is malformed, is for another curve,
+    /// does not contain enough powers, or if its powers are not consistent (see [`PairingSRS::check_powers`]).
+    pub fn load_ptau<R: Read + Seek>(mut reader: R, depth: usize) -> Result<Self, PtauError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != *b"ptau" {
+            return Err(PtauError::InvalidMagic);
+        }
+        let version = read_u32(&mut reader)?;
+        if version != 1 {
+            return Err(PtauError::UnsupportedVersion(version));
+        }
+
+        // the position of each section
+        let mut sections = HashMap::new();
+        for _ in 0..read_u32(&mut reader)? {
+            let typ = read_u32(&mut reader)?;
+            let size = read_u64(&mut reader)?;
+            sections.insert(typ, reader.stream_position()?);
+            reader.seek(SeekFrom::Current(size as i64))?;
+        }
+        let mut seek_section = |reader: &mut R, typ| -> Result<(), PtauError> {
+            let position = sections
+                .remove(&typ)
+                .ok_or(PtauError::MissingSection(typ))?;
+            reader.seek(SeekFrom::Start(position))?;
+            Ok(())
+        };
+
+        // the header: the size of the field elements, the modulus and the number of powers
+        seek_section(&mut reader, HEADER_SECTION)?;
+        let n8 = read_u32(&mut reader)? as usize;
+        let mut modulus = vec![0u8; n8];
+        reader.read_exact(&mut modulus)?;
+        let mut expected_modulus = <E::Fq as PrimeField>::Params::MODULUS.to_bytes_le();
+        expected_modulus.resize(n8, 0);
+        if modulus != expected_modulus {
+            return Err(PtauError::WrongCurve);
+        }
+        let power = read_u32(&mut reader)?;
+        let num_g1 = (1usize << power) * 2 - 1;
+        let num_g2 = 1usize << power;
+        if depth > num_g1 {
+            return Err(PtauError::NotEnoughPowers {
+                requested: depth,
+                available: num_g1,
+            });
+        }
+
+        seek_section(&mut reader, TAU_G1_SECTION)?;
+        let g = (0..depth)
+            .map(|_| read_point::<E::G1Affine, _>(&mut reader, n8))
+            .collect::<Result<_, _>>()?;
+
+        seek_section(&mut reader, TAU_G2_SECTION)?;
+        let verifier_srs = (0..std::cmp::min(num_g2, MAX_EVALUATION_POINTS + 1))
+            .map(|_| read_point::<E::G2Affine, _>(&mut reader, n8))
+            .collect::<Result<_, _>>()?;
+
+        let [h] = misc_points(&<E::G1Affine as CommitmentCurve>::Map::setup());
+        let srs = PairingSRS {
+            full_srs: SRS {
+                g,
+                h,
+                lagrange_bases: HashMap::new(),
+            },
+            verifier_srs,
+        };
+        if !srs.check_powers(&mut rand::thread_rng()) {
+            return Err(PtauError::InconsistentPowers);
+        }
+        Ok(srs)
+    }
+
+    /// Checks that the SRS contains consecutive powers of the same `tau`, starting from 1, in both groups,
+    /// up to a negligible probability over `rng`.
+    /// This needs at least two powers in each group.
+    pub fn check_powers(&self, rng: &mut (impl RngCore + CryptoRng)) -> bool {
+        let (g, h) = (&self.full_srs.g, &self.verifier_srs);
+        if g.len() < 2 || h.len() < 2 || g[0].is_zero() || h[0].is_zero() {
+            return false;
+        }
+
+        // with random linear combinations of the powers,
+        // checks that e(tau^{i+1}, 1) = e(tau^i, tau) in the first group,
+        // and that e(1, tau^{i+1}) = e(tau, tau^i) in the second group
+        let rho = E::Fr::rand(rng);
+        let (g_powers, g_next_powers) = shifted_combinations(g, rho);
+        let (h_powers, h_next_powers) = shifted_combinations(h, rho);
+        let g1_check = E::product_of_pairings(&[
+            (
+                E::G1Prepared::from(g_next_powers),
+                E::G2Prepared::from(h[0]),
+            ),
+            (E::G1Prepared::from(-g_powers), E::G2Prepared::from(h[1])),
+        ]);
+        let g2_check = E::product_of_pairings(&[
+            (
+                E::G1Prepared::from(g[0]),
+                E::G2Prepared::from(h_next_powers),
+            ),
+            (E::G1Prepared::from(-g[1]), E::G2Prepared::from(h_powers)),
+        ]);
+        g1_check.is_one() && g2_check.is_one()
+    }
+
+    /// Verifies a batch of openings, see [`OpenProof::verify`].
+    /// The sponges of the batch are not used.
+    pub fn verify<EFqSponge>(
+        &self,
+        batch: &[BatchEvaluationProof<E::G1Affine, EFqSponge, KZGProof<E>>],
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> bool
+    where
+        EFqSponge: FqSponge<E::Fq, E::G1Affine, E::Fr>,
+    {
+        verify_openings(
+            self,
+            batch.iter().map(|b| {
+                (
+                    &b.evaluations[..],
+                    &b.evaluation_points[..],
+                    b.polyscale,
+                    b.opening,
+                )
+            }),
+            E::Fr::rand(rng),
+        )
+    }
+}
+
+impl<E: PairingEngine> KZGProof<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    /// Opens the polynomials `plnms` at the points `elm`.
+    /// Each polynomial comes with the blinders of its commitment, and without degree bound.
+    /// The polynomials are combined with the powers of `polyscale`.
+    ///
+    /// # Errors
+    ///
+    /// Will give [`CommitmentError::HidingNotSupported`] if a commitment is hiding (has non-zero blinders),
+    /// as opening it would reveal its blinders.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a polynomial has a degree bound,
+    /// or is given by its evaluations over a domain larger than the SRS.
+    #[allow(clippy::type_complexity)]
+    pub fn create<D: EvaluationDomain<E::Fr>>(
+        srs: &PairingSRS<E>,
+        plnms: &[(
+            DensePolynomialOrEvaluations<E::Fr, D>,
+            Option<usize>,
+            PolyComm<E::Fr>,
+        )],
+        elm: &[E::Fr],
+        polyscale: E::Fr,
+    ) -> Result<Self, CommitmentError> {
+        // opening a hiding commitment would require revealing its blinders
+        if !plnms
+            .iter()
+            .all(|(_, _, blinders)| blinders.unshifted.iter().all(Zero::is_zero))
+        {
+            return Err(CommitmentError::HidingNotSupported);
+        }
+        let (plnm, _blinding) = combine_polys(plnms, polyscale, srs.full_srs.g.len());
+
+        // the remainder of the division is the polynomial interpolating the evaluations
+        let divisor = vanishing_polynomial(elm);
+        let (quotient, _remainder) = DenseOrSparsePolynomial::from(&plnm)
+            .divide_with_q_and_r(&DenseOrSparsePolynomial::from(&divisor))
+            .unwrap();
+
+        Ok(KZGProof {
+            quotient: srs.full_srs.commit_non_hiding(&quotient, None).unshifted[0],
+        })
+    }
+
+    /// Verifies this opening of the commitments of `evaluations` at the points `evaluation_points`.
+    pub fn verify(
+        &self,
+        srs: &PairingSRS<E>,
+        evaluations: &[Evaluation<E::G1Affine>],
+        evaluation_points: &[E::Fr],
+        polyscale: E::Fr,
+    ) -> bool {
+        verify_openings(
+            srs,
+            std::iter::once((evaluations, evaluation_points, polyscale, self)),
+            E::Fr::one(),
+        )
+    }
+}
+
+impl<E: PairingEngine> CommitmentKey<E::G1Affine> for PairingSRS<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    fn create(_depth: usize) -> Self {
+        panic!("a pairing SRS cannot be created without a trusted setup, load it with PairingSRS::load_ptau, or use PairingSRS::create_trusted_setup for testing")
+    }
+
+    fn max_poly_size(&self) -> usize {
+        self.full_srs.g.len()
+    }
+
+    fn blinding_commitment(&self) -> E::G1Affine {
+        self.full_srs.h
+    }
+
+    fn add_lagrange_basis(&mut self, domain: Radix2EvaluationDomain<E::Fr>) {
+        self.full_srs.add_lagrange_basis(domain)
+    }
+
+    fn lagrange_basis(&self, domain_size: usize) -> Option<&[PolyComm<E::G1Affine>]> {
+        self.full_srs.lagrange_basis(domain_size)
+    }
+
+    fn commit_non_hiding(
+        &self,
+        plnm: &DensePolynomial<E::Fr>,
+        max: Option<usize>,
+    ) -> PolyComm<E::G1Affine> {
+        self.full_srs.commit_non_hiding(plnm, max)
+    }
+
+    fn commit_evaluations_non_hiding(
+        &self,
+        domain: Radix2EvaluationDomain<E::Fr>,
+        plnm: &Evaluations<E::Fr, Radix2EvaluationDomain<E::Fr>>,
+    ) -> PolyComm<E::G1Affine> {
+        self.full_srs.commit_evaluations_non_hiding(domain, plnm)
+    }
+
+    fn mask_custom(
+        &self,
+        com: PolyComm<E::G1Affine>,
+        blinders: &PolyComm<E::Fr>,
+    ) -> Result<BlindedCommitment<E::G1Affine>, CommitmentError> {
+        self.full_srs.mask_custom(com, blinders)
+    }
+}
+
+/// Only non-hiding commitments can be opened, see [`KZGProof::create`].
+impl<E: PairingEngine> OpenProof<E::G1Affine> for KZGProof<E>
+where
+    E::G1Affine: CommitmentCurve,
+{
+    type SRS = PairingSRS<E>;
+
+    fn open<EFqSponge, RNG, D: EvaluationDomain<E::Fr>>(
+        srs: &Self::SRS,
+        _group_map: &<E::G1Affine as CommitmentCurve>::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<E::Fr, D>,
+            Option<usize>,
+            PolyComm<E::Fr>,
+        )],
+        elm: &[E::Fr],
+        polyscale: E::Fr,
+        _evalscale: E::Fr,
+        _sponge: EFqSponge,
+        _rng: &mut RNG,
+    ) -> Result<Self, CommitmentError>
+    where
+        EFqSponge: Clone + FqSponge<E::Fq, E::G1Affine, E::Fr>,
+        RNG: RngCore + CryptoRng,
+    {
+        KZGProof::create(srs, plnms, elm, polyscale)
+    }
+
+    fn verify<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        _group_map: &<E::G1Affine as CommitmentCurve>::Map,
+        batch: &mut [BatchEvaluationProof<E::G1Affine, EFqSponge, Self>],
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<E::Fq, E::G1Affine, E::Fr>,
+        RNG: RngCore + CryptoRng,
+    {
+        srs.verify(batch, rng)
+    }
+}
+
+//
+// Verification
+//
+
+/// Verifies a batch of openings, given as their evaluations, evaluation points, `polyscale` and proof.
+///
+/// For each opening, with `C` the combined commitment, `I` the polynomial interpolating the combined evaluations,
+/// `Z` the vanishing polynomial of the points and `Q` the quotient, it checks that
+///
+/// e(C - [I(tau)], [1]) = e(Q, [Z(tau)])
+///
+/// and it checks all of them at once by combining them with the powers of `rand_base`.
+fn verify_openings<'a, E: PairingEngine>(
+    srs: &PairingSRS<E>,
+    openings: impl Iterator<
+        Item = (
+            &'a [Evaluation<E::G1Affine>],
+            &'a [E::Fr],
+            E::Fr,
+            &'a KZGProof<E>,
+        ),
+    >,
+    rand_base: E::Fr,
+) -> bool
+where
+    E::G1Affine: CommitmentCurve,
+{
+    let g2 = match srs.verifier_srs.first() {
+        Some(g2) => *g2,
+        None => return false,
+    };
+
+    // the terms paired with the generator of the second group, ending with the powers of tau
+    let mut points = vec![];
+    let mut scalars = vec![];
+    let mut powers_scalars: Vec<E::Fr> = vec![];
+
+    let mut pairs: Vec<(E::G1Prepared, E::G2Prepared)> = vec![];
+    let mut rand_base_i = E::Fr::one();
+
+    for (evaluations, evaluation_points, polyscale, proof) in openings {
+        let (commitments, evals) =
+            match combine_evaluations(evaluations, polyscale, evaluation_points.len()) {
+                Some(combined) => combined,
+                None => return false,
+            };
+        let interpolant = match interpolate(evaluation_points, &evals) {
+            Some(interpolant) => interpolant,
+            None => return false,
+        };
+        let divisor = vanishing_polynomial(evaluation_points);
+        if divisor.coeffs.len() > srs.verifier_srs.len()
+            || interpolant.coeffs.len() > srs.full_srs.g.len()
+        {
+            return false;
+        }
+
+        // rand_base_i (C - [I(tau)])
+        for (chunk, scale) in commitments {
+            points.push(chunk);
+            scalars.push(rand_base_i * scale);
+        }
+        if powers_scalars.len() < interpolant.coeffs.len() {
+            powers_scalars.resize(interpolant.coeffs.len(), E::Fr::zero());
+        }
+        for (scalar, coeff) in powers_scalars.iter_mut().zip(&interpolant.coeffs) {
+            *scalar -= rand_base_i * coeff;
+        }
+
+        // - rand_base_i Q, paired with [Z(tau)]
+        let divisor_coeffs: Vec<_> = divisor.coeffs.iter().map(|c| c.into_repr()).collect();
+        let divisor_commitment =
+            VariableBaseMSM::multi_scalar_mul(&srs.verifier_srs, &divisor_coeffs);
+        pairs.push((
+            (-proof.quotient.mul(rand_base_i)).into_affine().into(),
+            divisor_commitment.into_affine().into(),
+        ));
+
+        rand_base_i *= rand_base;
+    }
+
+    points.extend(&srs.full_srs.g[..powers_scalars.len()]);
+    scalars.extend(powers_scalars);
+    let scalars: Vec<_> = scalars.iter().map(|s| s.into_repr()).collect();
+    let combined = VariableBaseMSM::multi_scalar_mul(&points, &scalars);
+    pairs.push((combined.into_affine().into(), g2.into()));
+
+    E::product_of_pairings(&pairs).is_one()
+}
+
+//
+// Setup
+//
+
+/// The sections of a `.ptau` file used by [`PairingSRS::load_ptau`]
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+
+/// Returns the `n` first powers of `tau` in the group of `P`.
+fn powers_of_generator<P: ProjectiveCurve>(tau: P::ScalarField, n: usize) -> Vec<P::Affine> {
+    let mut power = P::ScalarField::one();
+    let powers: Vec<_> = (0..n)
+        .map(|_| {
+            let current = power;
+            power *= tau;
+            current
+        })
+        .collect();
+
+    let scalar_size = P::ScalarField::size_in_bits();
+    let window = FixedBaseMSM::get_mul_window_size(n);
+    let table = FixedBaseMSM::get_window_table(scalar_size, window, P::prime_subgroup_generator());
+    let powers = FixedBaseMSM::multi_scalar_mul::<P>(scalar_size, window, &table, &powers);
+    P::batch_normalization_into_affine(&powers)
+}
+
+/// Returns `sum_i rho^i p_i` and `sum_i rho^i p_{i+1}`,
+/// the second one being `tau` times the first one if the `p_i` are consecutive powers of `tau`.
+fn shifted_combinations<G: AffineCurve>(points: &[G], rho: G::ScalarField) -> (G, G) {
+    let n = points.len() - 1;
+    let mut scalar = G::ScalarField::one();
+    let scalars: Vec<_> = (0..n)
+        .map(|_| {
+            let current = scalar;
+            scalar *= rho;
+            current.into_repr()
+        })
+        .collect();
+    (
+        VariableBaseMSM::multi_scalar_mul(&points[..n], &scalars).into_affine(),
+        VariableBaseMSM::multi_scalar_mul(&points[1..], &scalars).into_affine(),
+    )
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Reads a point of a `.ptau` file, whose coordinates are made of elements of a prime field
+/// of `n8` bytes each, in little-endian Montgomery form.
+/// The point at infinity has zero coordinates.
+/// The point is checked to be on the curve and in its prime-order subgroup,
+/// but the consistency of the powers is checked by [`PairingSRS::check_powers`].
+fn read_point<G: AffineCurve, R: Read>(reader: &mut R, n8: usize) -> Result<G, PtauError> {
+    type BasePrimeField<G> = <<G as AffineCurve>::BaseField as Field>::BasePrimeField;
+
+    // the Montgomery form of x is x * 2^(8 * n8)
+    let montgomery_inv = BasePrimeField::<G>::from(2u64)
+        .pow(&[8 * n8 as u64])
+        .inverse()
+        .unwrap();
+    let degree = G::BaseField::extension_degree() as usize;
+    let mut coordinate = || -> Result<G::BaseField, PtauError> {
+        let elems = (0..degree)
+            .map(|_| {
+                let mut bytes = vec![0u8; n8];
+                reader.read_exact(&mut bytes)?;
+                Ok(BasePrimeField::<G>::from_le_bytes_mod_order(&bytes) * montgomery_inv)
+            })
+            .collect::<Result<Vec<_>, PtauError>>()?;
+        G::BaseField::from_base_prime_field_elems(&elems).ok_or(PtauError::InvalidPoint)
+    };
+    let x = coordinate()?;
+    let y = coordinate()?;
+    if x.is_zero() && y.is_zero() {
+        return Ok(G::zero());
+    }
+
+    let mut bytes = vec![];
+    x.serialize(&mut bytes)
+        .and_then(|()| y.serialize(&mut bytes))
+        .map_err(|_| PtauError::InvalidPoint)?;
+    let point = G::deserialize_unchecked(&bytes[..]).map_err(|_| PtauError::InvalidPoint)?;
+    if is_valid_point(&point) {
+        Ok(point)
+    } else {
+        Err(PtauError::InvalidPoint)
+    }
+}
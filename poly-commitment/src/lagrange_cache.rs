@@ -0,0 +1,349 @@
+//! This module implements a persistent cache of the commitments to the Lagrange bases of an SRS,
+//! which are expensive to compute (see [`SRS::add_lagrange_basis`]) and are not serialized with the SRS.
+//!
+//! The cache is a directory, usually stored alongside the SRS file (see [`LagrangeCache::alongside`]),
+//! with one file per domain size, so that only the bases of the domains actually used are loaded.
+//! Each file is laid out as follows:
+//!
+//! | field          | content                                                              |
+//! | -------------- | -------------------------------------------------------------------- |
+//! | magic          | `b"lagb"`                                                            |
+//! | version        | `u32`, little-endian                                                 |
+//! | domain size    | `u64`, little-endian                                                 |
+//! | SRS digest     | the [digest](SRS::digest) of the SRS                                 |
+//! | chunks         | `u64`, little-endian: the number of unshifted chunks of a commitment |
+//! | shifted        | `u8`: whether the commitments have a shifted part                    |
+//! | commitments    | the uncompressed points of each commitment, unshifted chunks first   |
+//! | checksum       | Blake2b-512 digest of all the previous fields                        |
+//!
+//! The checksum detects accidental corruption, and the SRS digest a file computed from another SRS,
+//! but neither protects against a crafted file. So the loaded points are checked
+//! to be on the curve and in its prime-order subgroup, unless the cache is trusted
+//! and these checks are explicitly skipped with [`LagrangeCache::unchecked`], which makes loading much faster.
+
+use crate::{
+    commitment::{is_valid_point, CommitmentCurve},
+    error::LagrangeCacheError,
+    srs::{SRS, SRS_DIGEST_SIZE},
+    PolyComm,
+};
+use ark_ff::PrimeField;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain as D};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+use blake2::{Blake2b512, Digest};
+use rayon::prelude::*;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
+
+const MAGIC: &[u8; 4] = b"lagb";
+const VERSION: u32 = 2;
+const DIGEST_SIZE: usize = 64;
+
+/// A directory of commitments to Lagrange bases, with one file per domain size
+#[derive(Clone, Debug)]
+pub struct LagrangeCache {
+    dir: PathBuf,
+    check_points: bool,
+}
+
+impl LagrangeCache {
+    /// A cache stored in the directory `dir`, which is created when the first basis is saved.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        LagrangeCache {
+            dir: dir.into(),
+            check_points: true,
+        }
+    }
+
+    /// The same cache, but whose loaded points are not checked to be on the curve.
+    /// Only use it if the files of the cache cannot have been tampered with.
+    pub fn unchecked(self) -> Self {
+        LagrangeCache {
+            check_points: false,
+            ..self
+        }
+    }
+
+    /// The cache of the SRS stored in `srs_file`, in the directory `<srs_file>.lagrange`.
+    pub fn alongside(srs_file: impl AsRef<Path>) -> Self {
+        let mut dir = srs_file.as_ref().as_os_str().to_owned();
+        dir.push(".lagrange");
+        Self::new(dir)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The file of the Lagrange basis of the domain of size `domain_size`
+    pub fn path(&self, domain_size: usize) -> PathBuf {
+        self.dir.join(format!("lagrange_basis_{domain_size}.bin"))
+    }
+
+    /// Loads the commitments to the Lagrange basis of the domain of size `domain_size`
+    /// of the SRS whose [digest](SRS::digest) is `srs_digest`,
+    /// or returns `None` if they are not in the cache.
+    /// Fails if the cache file is corrupted, was computed from another SRS,
+    /// or contains invalid points (unless the cache is [unchecked](Self::unchecked)).
+    pub fn load<G: CommitmentCurve>(
+        &self,
+        srs_digest: &[u8; SRS_DIGEST_SIZE],
+        domain_size: usize,
+    ) -> Result<Option<Vec<PolyComm<G>>>, LagrangeCacheError> {
+        let file = match File::open(self.path(domain_size)) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let basis = read_basis(BufReader::new(file), srs_digest, domain_size)?;
+        if self.check_points
+            && !basis.par_iter().all(|comm| {
+                comm.unshifted
+                    .iter()
+                    .chain(&comm.shifted)
+                    .all(is_valid_point)
+            })
+        {
+            return Err(LagrangeCacheError::InvalidPoint);
+        }
+        Ok(Some(basis))
+    }
+
+    /// Saves the commitments `basis` to the Lagrange basis of the domain of size `domain_size`
+    /// of the SRS whose [digest](SRS::digest) is `srs_digest`, replacing the cached ones if any.
+    /// The file is written atomically, so that concurrent readers never see a partial file.
+    pub fn save<G: CommitmentCurve>(
+        &self,
+        srs_digest: &[u8; SRS_DIGEST_SIZE],
+        domain_size: usize,
+        basis: &[PolyComm<G>],
+    ) -> Result<(), LagrangeCacheError> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.path(domain_size);
+        let mut tmp = path.clone().into_os_string();
+        tmp.push(format!(".{}.tmp", std::process::id()));
+
+        let result = (|| -> Result<(), LagrangeCacheError> {
+            let mut writer = BufWriter::new(File::create(&tmp)?);
+            write_basis(&mut writer, srs_digest, domain_size, basis)?;
+            writer
+                .into_inner()
+                .map_err(|e| e.into_error())?
+                .sync_all()?;
+            fs::rename(&tmp, &path)?;
+            Ok(())
+        })();
+        if result.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        result
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G>
+where
+    G::BaseField: PrimeField,
+{
+    /// Same as [`SRS::add_lagrange_basis`], but loads the commitments from `cache` if they are there,
+    /// and saves them to `cache` otherwise.
+    /// A stale cache file, that is corrupted, of another version or domain, or computed from another SRS,
+    /// or that is not a cache file at all, is recomputed and overwritten.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the cache file cannot be read or contains an invalid point,
+    /// and if the commitments cannot be saved, in which case they are still added to the SRS.
+    pub fn add_lagrange_basis_cached(
+        &mut self,
+        domain: D<G::ScalarField>,
+        cache: &LagrangeCache,
+    ) -> Result<(), LagrangeCacheError> {
+        let n = domain.size();
+        if self.lagrange_bases.contains_key(&n) {
+            return Ok(());
+        }
+
+        // the SRS is hashed once, for both loading and saving
+        let digest = self.digest();
+        match cache.load(&digest, n) {
+            Ok(Some(basis)) => {
+                self.lagrange_bases.insert(n, basis);
+                return Ok(());
+            }
+            Ok(None)
+            | Err(
+                LagrangeCacheError::Corrupted
+                | LagrangeCacheError::WrongSrs
+                | LagrangeCacheError::UnsupportedVersion(_)
+                | LagrangeCacheError::InvalidMagic
+                | LagrangeCacheError::WrongDomainSize { .. },
+            ) => (),
+            Err(e) => return Err(e),
+        }
+
+        self.add_lagrange_basis(domain);
+        cache.save(&digest, n, &self.lagrange_bases[&n])
+    }
+
+    /// Saves all the commitments to Lagrange bases of the SRS to `cache`.
+    pub fn save_lagrange_bases(&self, cache: &LagrangeCache) -> Result<(), LagrangeCacheError> {
+        let digest = self.digest();
+        for (n, basis) in &self.lagrange_bases {
+            cache.save(&digest, *n, basis)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes everything read from or written to the inner reader or writer
+struct Hashing<T> {
+    inner: T,
+    hasher: Blake2b512,
+}
+
+impl<T> Hashing<T> {
+    fn new(inner: T) -> Self {
+        Hashing {
+            inner,
+            hasher: Blake2b512::new(),
+        }
+    }
+
+    fn digest(&self) -> [u8; DIGEST_SIZE] {
+        let mut digest = [0u8; DIGEST_SIZE];
+        digest.copy_from_slice(&self.hasher.clone().finalize());
+        digest
+    }
+}
+
+impl<R: Read> Read for Hashing<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<W: Write> Write for Hashing<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+fn write_basis<G: CommitmentCurve, W: Write>(
+    writer: W,
+    srs_digest: &[u8; SRS_DIGEST_SIZE],
+    domain_size: usize,
+    basis: &[PolyComm<G>],
+) -> Result<(), LagrangeCacheError> {
+    let (chunks, shifted) = match basis.first() {
+        Some(comm) => (comm.unshifted.len(), comm.shifted.is_some()),
+        None => (0, false),
+    };
+    assert!(basis
+        .iter()
+        .all(|comm| comm.unshifted.len() == chunks && comm.shifted.is_some() == shifted));
+
+    let mut writer = Hashing::new(writer);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&VERSION.to_le_bytes())?;
+    writer.write_all(&(domain_size as u64).to_le_bytes())?;
+    writer.write_all(srs_digest)?;
+    writer.write_all(&(chunks as u64).to_le_bytes())?;
+    writer.write_all(&[shifted as u8])?;
+    for comm in basis {
+        for p in comm.unshifted.iter().chain(&comm.shifted) {
+            p.serialize_uncompressed(&mut writer)
+                .map_err(serialization_error)?;
+        }
+    }
+
+    let checksum = writer.digest();
+    writer.inner.write_all(&checksum)?;
+    Ok(())
+}
+
+fn read_basis<G: CommitmentCurve, R: Read>(
+    reader: R,
+    srs_digest: &[u8; SRS_DIGEST_SIZE],
+    domain_size: usize,
+) -> Result<Vec<PolyComm<G>>, LagrangeCacheError> {
+    let mut reader = Hashing::new(reader);
+
+    let magic: [u8; 4] = read_bytes(&mut reader)?;
+    if magic != *MAGIC {
+        return Err(LagrangeCacheError::InvalidMagic);
+    }
+    let version = u32::from_le_bytes(read_bytes(&mut reader)?);
+    if version != VERSION {
+        return Err(LagrangeCacheError::UnsupportedVersion(version));
+    }
+    let found = u64::from_le_bytes(read_bytes(&mut reader)?) as usize;
+    if found != domain_size {
+        return Err(LagrangeCacheError::WrongDomainSize {
+            expected: domain_size,
+            found,
+        });
+    }
+    let digest: [u8; SRS_DIGEST_SIZE] = read_bytes(&mut reader)?;
+    if digest != *srs_digest {
+        return Err(LagrangeCacheError::WrongSrs);
+    }
+    let chunks = u64::from_le_bytes(read_bytes(&mut reader)?) as usize;
+    let shifted = match read_bytes(&mut reader)? {
+        [0] => false,
+        [1] => true,
+        _ => return Err(LagrangeCacheError::Corrupted),
+    };
+
+    let mut read_point = || G::deserialize_unchecked(&mut reader).map_err(serialization_error);
+    let basis = (0..domain_size)
+        .map(|_| {
+            Ok(PolyComm {
+                unshifted: (0..chunks)
+                    .map(|_| read_point())
+                    .collect::<Result<_, _>>()?,
+                shifted: if shifted { Some(read_point()?) } else { None },
+            })
+        })
+        .collect::<Result<Vec<_>, LagrangeCacheError>>()?;
+
+    let checksum = reader.digest();
+    let mut reader = reader.inner;
+    let expected: [u8; DIGEST_SIZE] = read_bytes(&mut reader)?;
+    if checksum != expected || reader.read(&mut [0u8])? != 0 {
+        return Err(LagrangeCacheError::Corrupted);
+    }
+    Ok(basis)
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], LagrangeCacheError> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes).map_err(io_error)?;
+    Ok(bytes)
+}
+
+/// A truncated file is a corrupted file
+fn io_error(e: io::Error) -> LagrangeCacheError {
+    if e.kind() == io::ErrorKind::UnexpectedEof {
+        LagrangeCacheError::Corrupted
+    } else {
+        LagrangeCacheError::Io(e)
+    }
+}
+
+fn serialization_error(e: SerializationError) -> LagrangeCacheError {
+    match e {
+        SerializationError::IoError(e) => io_error(e),
+        _ => LagrangeCacheError::Corrupted,
+    }
+}
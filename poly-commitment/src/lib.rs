@@ -1,8 +1,16 @@
+pub mod accumulator;
 pub mod chunked;
 mod combine;
 pub mod commitment;
 pub mod error;
 pub mod evaluation_proof;
+pub mod inner_product;
+pub mod kzg;
+pub mod lagrange_cache;
+pub mod msm;
+pub mod multi_point;
+pub mod pcs;
+pub mod range_proof;
 pub mod srs;
 
 #[cfg(test)]
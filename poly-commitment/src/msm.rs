@@ -0,0 +1,399 @@
+//! This module implements a multi-scalar multiplication accelerated with the GLV endomorphism,
+//! for curves of the form `y^2 = x^3 + b` such as Pallas and Vesta.
+//!
+//! On such curves, `phi(x, y) = (endo_q x, y)` is an endomorphism acting as the multiplication by `endo_r`
+//! (see [`endos`]). Each scalar `k` is split as `k = k1 + k2 endo_r`, with `k1` and `k2` of half the size of `k`,
+//! so that `k P = k1 P + k2 phi(P)` is a multi-scalar multiplication of twice as many points,
+//! but with scalars of half the size.
+//!
+//! The multi-scalar multiplication itself is Pippenger's bucket method,
+//! in which the points of all the buckets of a window are summed with batched affine additions
+//! (as in the `combine` module): at each round, the points of each bucket are added pairwise,
+//! with a single batch inversion for all the additions of the round.
+//! The size of the windows is chosen from the number of points.
+//!
+//! The multi-scalar multiplications of commitments and of the verification of opening proofs
+//! go through [`CommitmentCurve::msm`](crate::commitment::CommitmentCurve::msm),
+//! which uses this one on such curves when there are enough points.
+
+use crate::{combine::batch_add_assign, srs::endos};
+use ark_ec::{
+    models::short_weierstrass_jacobian::{GroupAffine as SWJAffine, GroupProjective},
+    ProjectiveCurve, SWModelParameters,
+};
+use ark_ff::{BigInteger, FpParameters, PrimeField, Zero};
+use num_bigint::{BigInt, BigUint, Sign};
+use o1_utils::math;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    sync::Mutex,
+};
+
+/// The number of 64-bit limbs of the scalars that can be decomposed
+const LIMBS: usize = 4;
+
+/// The decomposition of scalars with the GLV endomorphism of a curve
+#[derive(Clone, Debug)]
+pub struct Glv<P: SWModelParameters> {
+    /// the endomorphism acts as the multiplication of the x-coordinate by `endo_q`
+    endo_q: P::BaseField,
+    /// the endomorphism acts as the multiplication by `endo_r`
+    endo_r: P::ScalarField,
+    /// a short basis `(a1, b1), (a2, b2)` of the lattice of the `(a, b)` such that `a + b endo_r = 0`
+    basis: [(P::ScalarField, P::ScalarField); 2],
+    /// the coordinates `b2 / det` and `-b1 / det` of `(1, 0)` in the basis, where `det` is its determinant,
+    /// as a sign and the little-endian limbs of their magnitude multiplied by `2^(64 LIMBS)`
+    coordinates: [(bool, [u64; LIMBS]); 2],
+}
+
+/// The decomposition `k1 + k2 endo_r` of a scalar, with signed `k1` and `k2`
+pub type Decomposition = [(bool, u128); 2];
+
+impl<P: SWModelParameters + Clone> Glv<P>
+where
+    P::BaseField: PrimeField,
+{
+    /// Computes a short basis of the lattice of the GLV decomposition,
+    /// with the extended Euclidean algorithm on the order of the scalar field and `endo_r` [GLV01].
+    ///
+    /// [GLV01]: https://www.iacr.org/archive/crypto2001/21390189.pdf
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the scalar field has more than 256 bits.
+    pub fn new() -> Self {
+        assert!(
+            <P::ScalarField as PrimeField>::Params::MODULUS_BITS as usize <= 64 * LIMBS,
+            "the scalar field is too large for the GLV decomposition"
+        );
+        let (endo_q, endo_r) = endos::<SWJAffine<P>>();
+        let modulus = BigInt::from(BigUint::from_bytes_le(
+            &<P::ScalarField as PrimeField>::Params::MODULUS.to_bytes_le(),
+        ));
+        let sqrt_modulus = modulus.sqrt();
+
+        // the remainders r_i satisfy r_i = s_i modulus + t_i endo_r, so (r_i, -t_i) is in the lattice
+        let (mut r0, mut r1) = (modulus, to_bigint(endo_r));
+        let (mut t0, mut t1) = (BigInt::zero(), BigInt::from(1u8));
+        while r1 >= sqrt_modulus {
+            let q = &r0 / &r1;
+            let r2 = &r0 - &q * &r1;
+            let t2 = &t0 - &q * &t1;
+            (r0, r1, t0, t1) = (r1, r2, t1, t2);
+        }
+        let q = &r0 / &r1;
+        let (r2, t2) = (&r0 - &q * &r1, &t0 - &q * &t1);
+
+        // the first remainder smaller than the square root of the modulus,
+        // and the shortest of its neighbours
+        let v1 = (r1, -t1);
+        let norm = |a: &BigInt, b: &BigInt| a * a + b * b;
+        let v2 = if norm(&r0, &t0) <= norm(&r2, &t2) {
+            (r0, -t0)
+        } else {
+            (r2, -t2)
+        };
+        let det = &v1.0 * &v2.1 - &v2.0 * &v1.1;
+
+        let coordinate = |x: &BigInt| {
+            let c = round_div(&(x << (64 * LIMBS)), &det);
+            let digits = c.magnitude().to_u64_digits();
+            assert!(digits.len() <= LIMBS);
+            let mut limbs = [0u64; LIMBS];
+            limbs[..digits.len()].copy_from_slice(&digits);
+            (c.sign() == Sign::Minus, limbs)
+        };
+        let coordinates = [coordinate(&v2.1), coordinate(&-&v1.1)];
+
+        Glv {
+            endo_q,
+            endo_r,
+            basis: [v1, v2].map(|(a, b)| (to_field(&a), to_field(&b))),
+            coordinates,
+        }
+    }
+
+    /// Splits `k` as `k1 + k2 endo_r`, where `k1` and `k2` are about the square root of the order of the scalar field.
+    pub fn decompose(&self, k: P::ScalarField) -> Decomposition {
+        let repr = k.into_repr();
+        let mut limbs = [0u64; LIMBS];
+        for (limb, x) in limbs.iter_mut().zip(repr.as_ref()) {
+            *limb = *x;
+        }
+
+        // (k1, k2) = (k, 0) - c1 v1 - c2 v2, with (c1, c2) the rounded coordinates of (k, 0) in the basis
+        let [c1, c2] = self.coordinates.map(|(neg, coordinate)| {
+            let c = P::ScalarField::from(mul_shift_round(&limbs, &coordinate));
+            if neg {
+                -c
+            } else {
+                c
+            }
+        });
+        let [(a1, b1), (a2, b2)] = self.basis;
+        let k1 = k - c1 * a1 - c2 * a2;
+        let k2 = -(c1 * b1 + c2 * b2);
+        debug_assert_eq!(k1 + k2 * self.endo_r, k);
+
+        [k1, k2].map(|k| {
+            to_u128(k)
+                .map(|k| (false, k))
+                .or_else(|| to_u128(-k).map(|k| (true, k)))
+                .expect("the parts of the GLV decomposition have at most 128 bits")
+        })
+    }
+
+    /// Applies the endomorphism to `p`, which multiplies it by `endo_r`.
+    pub fn endomorphism(&self, p: &SWJAffine<P>) -> SWJAffine<P> {
+        if p.is_zero() {
+            *p
+        } else {
+            SWJAffine::new(p.x * self.endo_q, p.y, false)
+        }
+    }
+
+    /// Computes `sum_i scalars[i] bases[i]`.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `bases` and `scalars` have different lengths.
+    pub fn msm(&self, bases: &[SWJAffine<P>], scalars: &[P::ScalarField]) -> GroupProjective<P> {
+        assert_eq!(bases.len(), scalars.len());
+
+        // k P = k1 P + k2 phi(P), where the signs of k1 and k2 are moved to the points
+        let (points, scalars): (Vec<_>, Vec<_>) = bases
+            .par_iter()
+            .zip(scalars.par_iter())
+            .flat_map_iter(|(p, k)| {
+                let [(neg1, k1), (neg2, k2)] = self.decompose(*k);
+                let p1 = if neg1 { -*p } else { *p };
+                let p2 = self.endomorphism(p);
+                let p2 = if neg2 { -p2 } else { p2 };
+                [(p1, k1), (p2, k2)]
+            })
+            .unzip();
+
+        bucket_msm(&points, &scalars)
+    }
+}
+
+impl<P: SWModelParameters + Clone> Default for Glv<P>
+where
+    P::BaseField: PrimeField,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the GLV decomposition of the curve, which is only computed the first time it is requested.
+///
+/// # Panics
+///
+/// Will panic if the scalar field has more than 256 bits.
+pub fn glv<P: SWModelParameters + Clone>() -> &'static Glv<P>
+where
+    P::BaseField: PrimeField,
+{
+    // statics cannot be generic, so the decompositions are indexed by their curve
+    static GLVS: Lazy<Mutex<HashMap<TypeId, &'static (dyn Any + Send + Sync)>>> =
+        Lazy::new(Default::default);
+    let glv = *GLVS
+        .lock()
+        .unwrap()
+        .entry(TypeId::of::<P>())
+        .or_insert_with(|| Box::leak(Box::new(Glv::<P>::new())));
+    glv.downcast_ref().unwrap()
+}
+
+/// Computes `sum_i scalars[i] bases[i]` with the GLV endomorphism of the curve, see the [module documentation](self).
+///
+/// # Panics
+///
+/// Will panic if `bases` and `scalars` have different lengths.
+pub fn glv_msm<P: SWModelParameters + Clone>(
+    bases: &[SWJAffine<P>],
+    scalars: &[P::ScalarField],
+) -> GroupProjective<P>
+where
+    P::BaseField: PrimeField,
+{
+    glv::<P>().msm(bases, scalars)
+}
+
+/// Returns the size of the windows of Pippenger's method for `num_points` points.
+/// Batched affine additions make buckets cheaper to fill than to sum,
+/// so the windows are one bit larger than with projective additions.
+pub fn window_size(num_points: usize) -> usize {
+    if num_points < 32 {
+        3
+    } else {
+        // about ln(num_points) + 3
+        math::ceil_log2(num_points) * 69 / 100 + 3
+    }
+}
+
+/// Pippenger's bucket method, for scalars of at most 128 bits.
+fn bucket_msm<P: SWModelParameters>(
+    points: &[SWJAffine<P>],
+    scalars: &[u128],
+) -> GroupProjective<P> {
+    let num_bits = scalars
+        .iter()
+        .map(|k| 128 - k.leading_zeros() as usize)
+        .max()
+        .unwrap_or(0);
+    if num_bits == 0 {
+        return GroupProjective::zero();
+    }
+
+    let c = window_size(points.len());
+    let num_windows = (num_bits + c - 1) / c;
+
+    let window_sums: Vec<_> = (0..num_windows)
+        .into_par_iter()
+        .map(|w| {
+            let digits = scalars
+                .iter()
+                .map(|k| ((k >> (w * c)) & ((1 << c) - 1)) as usize);
+            let buckets = bucket_sums(points, digits, (1 << c) - 1);
+
+            // sum_j (j + 1) buckets[j]
+            let mut running_sum = GroupProjective::<P>::zero();
+            let mut window_sum = GroupProjective::<P>::zero();
+            for bucket in buckets.iter().rev() {
+                running_sum.add_assign_mixed(bucket);
+                window_sum += &running_sum;
+            }
+            window_sum
+        })
+        .collect();
+
+    // sum_w 2^(c w) window_sums[w]
+    let mut res = GroupProjective::<P>::zero();
+    for window_sum in window_sums.iter().rev() {
+        for _ in 0..c {
+            res.double_in_place();
+        }
+        res += window_sum;
+    }
+    res
+}
+
+/// Returns the sum of the points of each of the `num_buckets` buckets,
+/// where the point `points[i]` goes to the bucket `digits[i] - 1`, or nowhere if `digits[i]` is zero.
+/// The points of all the buckets are added pairwise at each round,
+/// with a single batch inversion per round.
+fn bucket_sums<P: SWModelParameters>(
+    points: &[SWJAffine<P>],
+    digits: impl Iterator<Item = usize>,
+    num_buckets: usize,
+) -> Vec<SWJAffine<P>> {
+    let mut buckets = vec![vec![]; num_buckets];
+    for (p, digit) in points.iter().zip(digits) {
+        if digit != 0 {
+            buckets[digit - 1].push(*p);
+        }
+    }
+
+    let mut v0 = vec![];
+    let mut v1 = vec![];
+    let mut denominators = vec![];
+    loop {
+        v0.clear();
+        v1.clear();
+        for bucket in &buckets {
+            let half = bucket.len() / 2;
+            v0.extend_from_slice(&bucket[..half]);
+            v1.extend_from_slice(&bucket[half..2 * half]);
+        }
+        if v0.is_empty() {
+            break;
+        }
+
+        denominators.resize(v0.len(), P::BaseField::zero());
+        batch_add_assign(&mut denominators, &mut v0, &v1);
+
+        // each bucket is replaced by its pairwise sums, and its last point if it has an odd number of points
+        let mut sums = v0.iter();
+        for bucket in &mut buckets {
+            let half = bucket.len() / 2;
+            let last = if bucket.len() % 2 == 1 {
+                bucket.last().copied()
+            } else {
+                None
+            };
+            bucket.clear();
+            bucket.extend(sums.by_ref().take(half));
+            bucket.extend(last);
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|bucket| bucket.first().copied().unwrap_or_else(SWJAffine::zero))
+        .collect()
+}
+
+/// Returns `k x / 2^(64 LIMBS)` rounded to the nearest integer,
+/// for `k` and `x` given as little-endian limbs, and a result of at most 128 bits.
+fn mul_shift_round(k: &[u64; LIMBS], x: &[u64; LIMBS]) -> u128 {
+    let mut product = [0u64; 2 * LIMBS];
+    for (i, ki) in k.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, xj) in x.iter().enumerate() {
+            let t =
+                u128::from(*ki) * u128::from(*xj) + u128::from(product[i + j]) + u128::from(carry);
+            product[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        product[i + LIMBS] = carry;
+    }
+
+    // adds 2^(64 LIMBS - 1) to round the shifted product
+    let mut carry = 1u64 << 63;
+    for limb in &mut product[LIMBS - 1..] {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        carry = u64::from(overflow);
+    }
+    debug_assert!(product[LIMBS + 2..].iter().all(|limb| *limb == 0));
+    u128::from(product[LIMBS]) | (u128::from(product[LIMBS + 1]) << 64)
+}
+
+/// Returns `x` if it has at most 128 bits.
+fn to_u128<F: PrimeField>(x: F) -> Option<u128> {
+    let repr = x.into_repr();
+    let limbs = repr.as_ref();
+    limbs[2..]
+        .iter()
+        .all(|limb| *limb == 0)
+        .then(|| u128::from(limbs[0]) | (u128::from(limbs[1]) << 64))
+}
+
+fn to_bigint<F: PrimeField>(x: F) -> BigInt {
+    BigInt::from(BigUint::from_bytes_le(&x.into_repr().to_bytes_le()))
+}
+
+fn to_field<F: PrimeField>(x: &BigInt) -> F {
+    let magnitude = F::from_le_bytes_mod_order(&x.magnitude().to_bytes_le());
+    if x.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Returns `x / d` rounded to the nearest integer.
+fn round_div(x: &BigInt, d: &BigInt) -> BigInt {
+    let q = (x.magnitude() + (d.magnitude() >> 1)) / d.magnitude();
+    let sign = if x.sign() == d.sign() {
+        Sign::Plus
+    } else {
+        Sign::Minus
+    };
+    BigInt::from_biguint(sign, q)
+}
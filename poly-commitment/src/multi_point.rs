@@ -0,0 +1,266 @@
+//! This module implements batched openings of polynomials at different subsets of points,
+//! on top of any polynomial commitment scheme implementing [`OpenProof`].
+//!
+//! A [`BatchEvaluationProof`] opens all its polynomials at the same evaluation points.
+//! Here, each polynomial `p_j` is opened at its own set of points `S_j`,
+//! and the openings are reduced to the opening of a single polynomial at a single point,
+//! as in Shplonk [BDFG20]:
+//!
+//! 1. the prover commits to `h = sum_j polyscale^j (p_j - r_j) / Z_j`,
+//!    where `r_j` interpolates the evaluations of `p_j` over `S_j` and `Z_j` is the vanishing polynomial of `S_j`,
+//! 2. the verifier samples a point `z` with the sponge, after absorbing the commitment to `h`,
+//! 3. the polynomial `L = sum_j polyscale^j / Z_j(z) p_j - h`, whose commitment is computed by the verifier,
+//!    is opened at `z` with the scheme, and must evaluate to `sum_j polyscale^j r_j(z) / Z_j(z)`.
+//!
+//! The chunks of the polynomials are opened as separate polynomials,
+//! and degree bounds (shifted commitments) are not supported.
+//! The sponge is only used for `z` and the opening of the scheme:
+//! the evaluations must have been absorbed before sampling `polyscale`.
+//!
+//! [BDFG20]: https://eprint.iacr.org/2020/081
+
+use crate::{
+    commitment::{BatchEvaluationProof, BlindedCommitment, CommitmentCurve, Evaluation},
+    error::CommitmentError,
+    evaluation_proof::DensePolynomialOrEvaluations,
+    pcs::{CommitmentKey, OpenProof},
+    PolyComm,
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{Field, One, PrimeField, Zero};
+use ark_poly::{
+    univariate::{DenseOrSparsePolynomial, DensePolynomial},
+    Polynomial, Radix2EvaluationDomain,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use mina_poseidon::FqSponge;
+use o1_utils::{
+    dense_polynomial::{interpolate, vanishing_polynomial},
+    ExtendedDensePolynomial as _,
+};
+use rand_core::{CryptoRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// The claimed evaluations of a committed polynomial at its own set of points
+pub struct PointSetEvaluation<G: AffineCurve> {
+    /// The commitment to the polynomial, without degree bound
+    pub commitment: PolyComm<G>,
+    /// The points at which the polynomial is opened, which must be distinct
+    pub points: Vec<G::ScalarField>,
+    /// The evaluations of the chunks of the polynomial, at each point
+    pub evaluations: Vec<Vec<G::ScalarField>>,
+}
+
+/// The opening of a batch of polynomials at different sets of points, see the [module documentation](self).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(
+    bound = "G: CanonicalDeserialize + CanonicalSerialize, OpeningProof: Serialize + DeserializeOwned"
+)]
+pub struct MultiPointProof<G: AffineCurve, OpeningProof = crate::evaluation_proof::OpeningProof<G>>
+{
+    /// The commitment to the combination `h` of the quotients of the polynomials
+    /// by the vanishing polynomials of their points
+    pub quotient: PolyComm<G>,
+    /// The opening of the reduced polynomial `L` at the challenge point `z`
+    pub opening: OpeningProof,
+}
+
+impl<G: CommitmentCurve, OpeningProof: OpenProof<G>> MultiPointProof<G, OpeningProof> {
+    /// Opens each polynomial of `plnms` at its own set of points.
+    /// Each polynomial comes with the blinders of its commitment and its points.
+    /// The polynomials (or rather, their chunks) are combined with the powers of `polyscale`.
+    ///
+    /// Returns `None` if the challenge point is one of the points, which only happens with negligible probability.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the scheme cannot open the reduced commitment,
+    /// for instance if it cannot open hiding commitments (see [`OpenProof::open`]).
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a polynomial has more chunks than its blinders.
+    /// The points of each polynomial must be distinct, or the proof does not verify.
+    #[allow(clippy::type_complexity)]
+    pub fn create<EFqSponge, RNG>(
+        srs: &OpeningProof::SRS,
+        group_map: &G::Map,
+        plnms: &[(
+            &DensePolynomial<G::ScalarField>,
+            PolyComm<G::ScalarField>,
+            Vec<G::ScalarField>,
+        )],
+        polyscale: G::ScalarField,
+        mut sponge: EFqSponge,
+        rng: &mut RNG,
+    ) -> Result<Option<Self>, CommitmentError>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        // the chunks of the polynomials, with their scale, their blinders and the vanishing polynomial of their points
+        let mut chunks = vec![];
+        let mut quotient = DensePolynomial::zero();
+        let mut scale = G::ScalarField::one();
+        for (plnm, blinders, points) in plnms {
+            let vanishing = vanishing_polynomial(points);
+            let mut polys = plnm.to_chunked_polynomial(srs.max_poly_size()).polys;
+            assert!(polys.len() <= blinders.unshifted.len());
+            polys.resize(blinders.unshifted.len(), DensePolynomial::zero());
+
+            for (chunk, blinder) in polys.into_iter().zip(&blinders.unshifted) {
+                let (q, _) = DenseOrSparsePolynomial::from(&chunk)
+                    .divide_with_q_and_r(&DenseOrSparsePolynomial::from(&vanishing))
+                    .unwrap();
+                quotient += (scale, &q);
+                chunks.push((scale, chunk, *blinder, vanishing.clone()));
+                scale *= polyscale;
+            }
+        }
+
+        // the quotient is only hidden if one of the polynomials is,
+        // so that schemes that cannot open hiding commitments can open non-hiding ones
+        let hiding = plnms
+            .iter()
+            .any(|(_, blinders, _)| blinders.unshifted.iter().any(|b| !b.is_zero()));
+        let quotient_comm = if hiding {
+            srs.commit(&quotient, None, rng)
+        } else {
+            let commitment = srs.commit_non_hiding(&quotient, None);
+            let blinders = commitment.map(|_| G::ScalarField::zero());
+            BlindedCommitment {
+                commitment,
+                blinders,
+            }
+        };
+        sponge.absorb_g(&quotient_comm.commitment.unshifted);
+        let z = sponge.challenge();
+
+        // L = sum_j polyscale^j / Z_j(z) p_j - h, with the same combination of the blinders
+        let mut reduced = DensePolynomial::zero();
+        let mut blinder = G::ScalarField::zero();
+        for (scale, chunk, chunk_blinder, vanishing) in &chunks {
+            let scale = match vanishing.evaluate(&z).inverse() {
+                Some(inv) => *scale * inv,
+                None => return Ok(None),
+            };
+            reduced += (scale, chunk);
+            blinder += scale * chunk_blinder;
+        }
+        reduced -= &quotient;
+        blinder -= quotient_comm.blinders.unshifted[0];
+
+        let opening = OpeningProof::open(
+            srs,
+            group_map,
+            &[(
+                DensePolynomialOrEvaluations::<_, Radix2EvaluationDomain<_>>::DensePolynomial(
+                    &reduced,
+                ),
+                None,
+                PolyComm::new(vec![blinder], None),
+            )],
+            &[z],
+            G::ScalarField::one(),
+            G::ScalarField::one(),
+            sponge,
+            rng,
+        )?;
+
+        Ok(Some(MultiPointProof {
+            quotient: quotient_comm.commitment,
+            opening,
+        }))
+    }
+
+    /// Reduces this proof to the opening of a single commitment at a single point,
+    /// to be verified by the scheme, possibly in a batch with other openings.
+    /// `polyscale` and `sponge` must be the ones given to [`MultiPointProof::create`].
+    ///
+    /// Returns `None` if the evaluations are malformed:
+    /// the number of evaluations of a polynomial does not match its points or its chunks,
+    /// the points of a polynomial are not distinct or contain the challenge point,
+    /// or a commitment is shifted.
+    pub fn reduce<EFqSponge>(
+        &self,
+        evaluations: &[PointSetEvaluation<G>],
+        polyscale: G::ScalarField,
+        mut sponge: EFqSponge,
+    ) -> Option<BatchEvaluationProof<'_, G, EFqSponge, OpeningProof>>
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+    {
+        if self.quotient.unshifted.len() != 1 || self.quotient.shifted.is_some() {
+            return None;
+        }
+        sponge.absorb_g(&self.quotient.unshifted);
+        let z = sponge.challenge();
+
+        // the commitment to L, and its evaluation at z
+        let mut points = vec![];
+        let mut scalars = vec![];
+        let mut value = G::ScalarField::zero();
+        let mut scale = G::ScalarField::one();
+        for eval in evaluations {
+            let chunks = eval.commitment.unshifted.len();
+            if eval.commitment.shifted.is_some()
+                || eval.points.is_empty()
+                || eval.evaluations.len() != eval.points.len()
+                || eval.evaluations.iter().any(|e| e.len() != chunks)
+            {
+                return None;
+            }
+            let inv_vanishing = vanishing_polynomial(&eval.points).evaluate(&z).inverse()?;
+
+            for (i, comm) in eval.commitment.unshifted.iter().enumerate() {
+                let chunk_evals: Vec<_> = eval.evaluations.iter().map(|e| e[i]).collect();
+                let interpolated = interpolate(&eval.points, &chunk_evals)?.evaluate(&z);
+
+                let chunk_scale = scale * inv_vanishing;
+                points.push(*comm);
+                scalars.push(chunk_scale);
+                value += chunk_scale * interpolated;
+                scale *= polyscale;
+            }
+        }
+        points.push(self.quotient.unshifted[0]);
+        scalars.push(-G::ScalarField::one());
+
+        let commitment = G::msm(&points, &scalars).into_affine();
+
+        Some(BatchEvaluationProof {
+            sponge,
+            evaluations: vec![Evaluation {
+                commitment: PolyComm::new(vec![commitment], None),
+                evaluations: vec![vec![value]],
+                degree_bound: None,
+            }],
+            evaluation_points: vec![z],
+            polyscale: G::ScalarField::one(),
+            evalscale: G::ScalarField::one(),
+            opening: &self.opening,
+        })
+    }
+
+    /// Verifies this opening of the commitments of `evaluations` at their sets of points.
+    pub fn verify<EFqSponge, RNG>(
+        &self,
+        srs: &OpeningProof::SRS,
+        group_map: &G::Map,
+        evaluations: &[PointSetEvaluation<G>],
+        polyscale: G::ScalarField,
+        sponge: EFqSponge,
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        match self.reduce(evaluations, polyscale, sponge) {
+            Some(batch) => OpeningProof::verify(srs, group_map, &mut [batch], rng),
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! This module defines the interface of a polynomial commitment scheme, as used by kimchi:
+//!
+//! - [`CommitmentKey`] is implemented by the structured reference string of the scheme,
+//!   and commits to polynomials given in coefficient or in evaluation form,
+//! - [`OpenProof`] is implemented by the batched opening proofs of the scheme,
+//!   and opens a batch of commitments at a set of evaluation points.
+//!
+//! Commitments are [`PolyComm`]s of points of the curve `G`:
+//! the verifier of kimchi combines commitments homomorphically
+//! (for example, to commit to the linearization polynomial),
+//! so any scheme committing to polynomials with curve points can be used.
+//!
+//! The inner-product argument of this crate ([`SRS`](crate::srs::SRS)
+//! and [`OpeningProof`](crate::evaluation_proof::OpeningProof)) implements both traits.
+//!
+//! Schemes that open the combination of the polynomials of a batch,
+//! like [KZG](crate::kzg), can use [`combine_polys`] and [`combine_evaluations`].
+
+use crate::{
+    commitment::{
+        BatchEvaluationProof, BlindedCommitment, CommitmentCurve, Evaluation, OpeningCheck,
+    },
+    error::CommitmentError,
+    evaluation_proof::DensePolynomialOrEvaluations,
+    PolyComm,
+};
+use ark_ec::AffineCurve;
+use ark_ff::{FftField, One, PrimeField, UniformRand, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain,
+    UVPolynomial,
+};
+use mina_poseidon::FqSponge;
+use rand_core::{CryptoRng, RngCore};
+use std::fmt::Debug;
+
+/// The structured reference string of a polynomial commitment scheme,
+/// used to commit to polynomials.
+pub trait CommitmentKey<G: CommitmentCurve>: Clone + Debug + Default + Send + Sync {
+    /// Creates a commitment key for polynomials of up to `depth` coefficients per chunk.
+    ///
+    /// # Panics
+    ///
+    /// Schemes with a trusted setup panic, as their commitment key cannot be derived from `depth` alone:
+    /// it must be loaded from the output of a setup ceremony.
+    fn create(depth: usize) -> Self
+    where
+        G::BaseField: PrimeField;
+
+    /// The maximum number of coefficients of a chunk of a committed polynomial.
+    /// Larger polynomials are committed to in several chunks.
+    fn max_poly_size(&self) -> usize;
+
+    /// The curve point used to blind commitments.
+    fn blinding_commitment(&self) -> G;
+
+    /// Computes the commitments to the Lagrange basis of `domain`, if they are not cached yet.
+    fn add_lagrange_basis(&mut self, domain: Radix2EvaluationDomain<G::ScalarField>)
+    where
+        G::BaseField: PrimeField;
+
+    /// Returns the cached commitments to the Lagrange basis of the domain of size `domain_size`.
+    fn lagrange_basis(&self, domain_size: usize) -> Option<&[PolyComm<G>]>;
+
+    /// Commits to a polynomial without hiding it, in chunks of [`max_poly_size`](Self::max_poly_size).
+    /// If `max` is given, the commitment also enforces that the degree of the polynomial is less than `max`.
+    fn commit_non_hiding(
+        &self,
+        plnm: &DensePolynomial<G::ScalarField>,
+        max: Option<usize>,
+    ) -> PolyComm<G>;
+
+    /// Commits to a polynomial given by its evaluations over a domain, without hiding it.
+    /// The commitments to the Lagrange basis of `domain` must have been computed
+    /// with [`add_lagrange_basis`](Self::add_lagrange_basis).
+    fn commit_evaluations_non_hiding(
+        &self,
+        domain: Radix2EvaluationDomain<G::ScalarField>,
+        plnm: &Evaluations<G::ScalarField, Radix2EvaluationDomain<G::ScalarField>>,
+    ) -> PolyComm<G>;
+
+    /// Turns a non-hiding commitment into a hiding commitment, with the given blinders.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if the number of blinders does not match the number of chunks of the commitment.
+    fn mask_custom(
+        &self,
+        com: PolyComm<G>,
+        blinders: &PolyComm<G::ScalarField>,
+    ) -> Result<BlindedCommitment<G>, CommitmentError>;
+
+    /// Turns a non-hiding commitment into a hiding commitment, with random blinders.
+    fn mask(
+        &self,
+        comm: PolyComm<G>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> BlindedCommitment<G> {
+        let blinders = comm.map(|_| G::ScalarField::rand(rng));
+        self.mask_custom(comm, &blinders).unwrap()
+    }
+
+    /// Commits to a polynomial, hiding it with random blinders.
+    fn commit(
+        &self,
+        plnm: &DensePolynomial<G::ScalarField>,
+        max: Option<usize>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> BlindedCommitment<G> {
+        self.mask(self.commit_non_hiding(plnm, max), rng)
+    }
+
+    /// Commits to a polynomial given by its evaluations over a domain, hiding it with random blinders.
+    fn commit_evaluations(
+        &self,
+        domain: Radix2EvaluationDomain<G::ScalarField>,
+        plnm: &Evaluations<G::ScalarField, Radix2EvaluationDomain<G::ScalarField>>,
+        rng: &mut (impl RngCore + CryptoRng),
+    ) -> BlindedCommitment<G> {
+        self.mask(self.commit_evaluations_non_hiding(domain, plnm), rng)
+    }
+}
+
+/// The batched opening proof of a polynomial commitment scheme.
+pub trait OpenProof<G: CommitmentCurve>: Sized + Clone + Debug + Send + Sync {
+    /// The structured reference string of the scheme.
+    type SRS: CommitmentKey<G>;
+
+    /// Opens a batch of polynomials at the points `elm`.
+    /// Each polynomial comes with an optional degree bound and the blinders of its commitment.
+    /// The polynomials are combined with the powers of `polyscale`,
+    /// and their evaluations with the powers of `evalscale`.
+    ///
+    /// # Errors
+    ///
+    /// Schemes that are not zero-knowledge give [`CommitmentError::HidingNotSupported`]
+    /// if a commitment is hiding (has non-zero blinders), rather than reveal its blinders.
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    fn open<EFqSponge, RNG, D: EvaluationDomain<G::ScalarField>>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<G::ScalarField, D>,
+            Option<usize>,
+            PolyComm<G::ScalarField>,
+        )],
+        elm: &[G::ScalarField],
+        polyscale: G::ScalarField,
+        evalscale: G::ScalarField,
+        sponge: EFqSponge,
+        rng: &mut RNG,
+    ) -> Result<Self, CommitmentError>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField;
+
+    /// Same as [`open`](Self::open), except that `on_round` is called with the index of each round
+    /// of the opening, for schemes that open in several rounds (by default, none is reported).
+    /// If `on_round` returns an error, the opening stops there and returns that error.
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `on_round` or [`open`](Self::open) does.
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    fn open_with_progress<EFqSponge, RNG, D: EvaluationDomain<G::ScalarField>, E>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        plnms: &[(
+            DensePolynomialOrEvaluations<G::ScalarField, D>,
+            Option<usize>,
+            PolyComm<G::ScalarField>,
+        )],
+        elm: &[G::ScalarField],
+        polyscale: G::ScalarField,
+        evalscale: G::ScalarField,
+        sponge: EFqSponge,
+        rng: &mut RNG,
+        _on_round: &mut dyn FnMut(usize) -> Result<(), E>,
+    ) -> Result<Self, E>
+    where
+        EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+        E: From<CommitmentError>,
+    {
+        Self::open(
+            srs, group_map, plnms, elm, polyscale, evalscale, sponge, rng,
+        )
+        .map_err(E::from)
+    }
+
+    /// Verifies a batch of batched opening proofs.
+    fn verify<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge, Self>],
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField;
+
+    /// Same as [`verify`](Self::verify), but only performs the given `checks`,
+    /// to find out which check makes a batch fail.
+    /// By default, the scheme has no challenge polynomial,
+    /// and [`OpeningCheck::CombinedInnerProduct`] stands for the whole verification.
+    fn verify_checks<EFqSponge, RNG>(
+        srs: &Self::SRS,
+        group_map: &G::Map,
+        batch: &mut [BatchEvaluationProof<G, EFqSponge, Self>],
+        rng: &mut RNG,
+        checks: &[OpeningCheck],
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+        G::BaseField: PrimeField,
+    {
+        !checks.contains(&OpeningCheck::CombinedInnerProduct)
+            || Self::verify(srs, group_map, batch, rng)
+    }
+}
+
+//
+// Helpers for the schemes opening a combination of the polynomials
+//
+
+/// Combines the chunks of the polynomials `plnms`, and their blinders, with the powers of `polyscale`.
+///
+/// # Panics
+///
+/// Will panic if a polynomial has a degree bound,
+/// or is given by its evaluations over a domain larger than `srs_length`.
+#[allow(clippy::type_complexity)]
+pub fn combine_polys<F: FftField, D: EvaluationDomain<F>>(
+    plnms: &[(
+        DensePolynomialOrEvaluations<F, D>,
+        Option<usize>,
+        PolyComm<F>,
+    )],
+    polyscale: F,
+    srs_length: usize,
+) -> (DensePolynomial<F>, F) {
+    let mut plnm = DensePolynomial::zero();
+    let mut blinding = F::zero();
+    let mut scale = F::one();
+
+    for (p_i, degree_bound, omegas) in plnms {
+        assert!(degree_bound.is_none(), "degree bounds are not supported");
+        match p_i {
+            DensePolynomialOrEvaluations::Evaluations(evals_i, sub_domain) => {
+                assert!(sub_domain.size() <= srs_length);
+                assert_eq!(omegas.unshifted.len(), 1);
+                let stride = evals_i.evals.len() / sub_domain.size();
+                let evals = evals_i.evals.iter().step_by(stride).copied().collect();
+                plnm += (
+                    scale,
+                    &Evaluations::from_vec_and_domain(evals, *sub_domain).interpolate(),
+                );
+                blinding += omegas.unshifted[0] * scale;
+                scale *= polyscale;
+            }
+
+            DensePolynomialOrEvaluations::DensePolynomial(p_i) => {
+                // iterating over chunks of the polynomial
+                for (j, omega) in omegas.unshifted.iter().enumerate() {
+                    let start = std::cmp::min(j * srs_length, p_i.coeffs.len());
+                    let end = std::cmp::min(start + srs_length, p_i.coeffs.len());
+                    plnm += (
+                        scale,
+                        &DensePolynomial::from_coefficients_slice(&p_i.coeffs[start..end]),
+                    );
+                    blinding += *omega * scale;
+                    scale *= polyscale;
+                }
+            }
+        }
+    }
+
+    (plnm, blinding)
+}
+
+/// Combines the chunks of the commitments of `evaluations`, and their evaluations at each point,
+/// with the powers of `polyscale`, in the same order as [`combine_polys`].
+/// The combined commitment is returned as a list of chunks with their scalars.
+/// Returns `None` if the evaluations are malformed or have a degree bound.
+#[allow(clippy::type_complexity)]
+pub fn combine_evaluations<G: AffineCurve>(
+    evaluations: &[Evaluation<G>],
+    polyscale: G::ScalarField,
+    num_points: usize,
+) -> Option<(Vec<(G, G::ScalarField)>, Vec<G::ScalarField>)> {
+    let mut commitments = vec![];
+    let mut combined_evals = vec![G::ScalarField::zero(); num_points];
+    let mut scale = G::ScalarField::one();
+
+    for Evaluation {
+        commitment,
+        evaluations,
+        degree_bound,
+    } in evaluations
+        .iter()
+        .filter(|e| !e.commitment.unshifted.is_empty())
+    {
+        if degree_bound.is_some() || evaluations.len() != num_points {
+            return None;
+        }
+        // iterating over the polynomial segments
+        for (k, chunk) in commitment.unshifted.iter().enumerate() {
+            commitments.push((*chunk, scale));
+            for (combined, point_evals) in combined_evals.iter_mut().zip(evaluations) {
+                *combined += scale * point_evals.get(k)?;
+            }
+            scale *= polyscale;
+        }
+    }
+
+    Some((commitments, combined_evals))
+}
@@ -0,0 +1,370 @@
+//! This module implements the aggregated range proofs of Bulletproofs [BBBPWM18],
+//! on top of the [inner-product argument](crate::inner_product) and the points of an [`SRS`].
+//!
+//! A value `v` is committed to as `V = v B + gamma h`, where `B` is the [`value_generator`]
+//! and `h` is the blinding generator of the SRS (see [`SRS::commit_value`]).
+//! A [`RangeProof`] proves that each of `m` committed values is in `[0, 2^n)`,
+//! with `2 log(nm) + 4` points and five scalars, where `n` and `m` are powers of two with `n <= 64`.
+//! The bases of the argument are the first `2nm` points of the SRS.
+//!
+//! The Fiat-Shamir challenges are squeezed from the given sponge (usually a `DefaultFqSponge`),
+//! which absorbs the number of bits, the commitments to the values and the messages of the prover.
+//! A batch of range proofs, with different numbers of values and bits,
+//! is verified with a single multi-scalar multiplication by [`SRS::verify_range_proofs`].
+//!
+//! [BBBPWM18]: https://eprint.iacr.org/2017/1066
+
+use crate::{
+    commitment::{inner_prod, pows, squeeze_challenge, CommitmentCurve},
+    error::RangeProofError,
+    inner_product::InnerProductProof,
+    srs::{endos, point_of_random_bytes, SRS},
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{Field, One, PrimeField, UniformRand, Zero};
+use blake2::{Blake2b512, Digest};
+use mina_poseidon::FqSponge;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+
+/// A proof that committed values are in a range, see the [module documentation](self).
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound = "G: ark_serialize::CanonicalDeserialize + ark_serialize::CanonicalSerialize")]
+pub struct RangeProof<G: AffineCurve> {
+    /// The commitment to the bits of the values, and to the bits minus one
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub a: G,
+    /// The commitment to the blinding vectors of the bits
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub s: G,
+    /// The commitment to the coefficient of degree 1 of `t(X) = <l(X), r(X)>`
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub t1: G,
+    /// The commitment to the coefficient of degree 2 of `t(X)`
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub t2: G,
+    /// The evaluation of `t(X)` at the challenge `x`
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub t_hat: G::ScalarField,
+    /// The blinder of the commitment to `t_hat`
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub tau_x: G::ScalarField,
+    /// The blinder of the commitment to `l(x)` and `r(x)`
+    #[serde_as(as = "o1_utils::serialization::SerdeAs")]
+    pub mu: G::ScalarField,
+    /// The inner-product argument of `l(x)` and `r(x)`
+    pub ipa: InnerProductProof<G>,
+}
+
+/// A range proof and its statement, to be verified in a batch
+pub struct BatchRangeProof<'a, G, EFqSponge>
+where
+    G: AffineCurve,
+    EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+{
+    pub sponge: EFqSponge,
+    /// The commitments to the values
+    pub commitments: Vec<G>,
+    /// The values are in `[0, 2^bits)`
+    pub bits: usize,
+    pub proof: &'a RangeProof<G>,
+}
+
+/// Returns the generator `B` of the values in their commitments `v B + gamma h`.
+/// Nobody knows its discrete logarithm in any basis, and it is not one of the points of an SRS.
+pub fn value_generator<G: CommitmentCurve>(group_map: &G::Map) -> G
+where
+    G::BaseField: PrimeField,
+{
+    let mut h = Blake2b512::new();
+    h.update("range_proof_value".as_bytes());
+    point_of_random_bytes(group_map, &h.finalize())
+}
+
+impl<G: CommitmentCurve> RangeProof<G>
+where
+    G::BaseField: PrimeField,
+{
+    /// Proves that each of the `values` is in `[0, 2^bits)`,
+    /// given their commitments with the `blinders` (see [`SRS::commit_value`]).
+    ///
+    /// # Errors
+    ///
+    /// Will give error if `bits` or the number of values is not a power of two,
+    /// if the numbers of values and blinders differ, if a value is out of the range,
+    /// or if the SRS has less than `2 bits values.len()` points.
+    pub fn create<EFqSponge, RNG>(
+        srs: &SRS<G>,
+        group_map: &G::Map,
+        mut sponge: EFqSponge,
+        bits: usize,
+        values: &[u64],
+        blinders: &[G::ScalarField],
+        rng: &mut RNG,
+    ) -> Result<Self, RangeProofError>
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+    {
+        let m = values.len();
+        let n = total_bits(srs, bits, m)?;
+        if blinders.len() != m {
+            return Err(RangeProofError::BlindersDontMatch {
+                values: m,
+                blinders: blinders.len(),
+            });
+        }
+        if let Some(v) = values.iter().find(|v| bits < 64 && **v >> bits != 0) {
+            return Err(RangeProofError::ValueOutOfRange(*v));
+        }
+        let (_, endo_r) = endos::<G>();
+        let (g, h) = srs.inner_product_bases(n).unwrap();
+        let b = value_generator::<G>(group_map);
+
+        let commitments: Vec<_> = values
+            .iter()
+            .zip(blinders)
+            .map(|(v, gamma)| commit(b, srs.h, *v, *gamma))
+            .collect();
+        absorb_statement(&mut sponge, bits, &commitments);
+
+        // the bits of the values, and the bits minus one
+        let a_l: Vec<_> = values
+            .iter()
+            .flat_map(|v| (0..bits).map(move |k| G::ScalarField::from((v >> k) & 1)))
+            .collect();
+        let a_r: Vec<_> = a_l.iter().map(|bit| *bit - G::ScalarField::one()).collect();
+        let alpha = G::ScalarField::rand(rng);
+        let a = srs.commit_vectors(&a_l, &a_r, alpha);
+
+        let s_l: Vec<_> = (0..n).map(|_| G::ScalarField::rand(rng)).collect();
+        let s_r: Vec<_> = (0..n).map(|_| G::ScalarField::rand(rng)).collect();
+        let rho = G::ScalarField::rand(rng);
+        let s = srs.commit_vectors(&s_l, &s_r, rho);
+
+        sponge.absorb_g(&[a, s]);
+        let y = squeeze_challenge(&endo_r, &mut sponge);
+        let z = squeeze_challenge(&endo_r, &mut sponge);
+
+        // l(X) = a_l - z + s_l X
+        // r(X) = y^i (a_r + z + s_r X) + z^(2+j) 2^k, for the bit k of the value j
+        let y_pows = pows(n, y);
+        let z_pows = pows(m + 2, z);
+        let two_pows = pows(bits, G::ScalarField::from(2u64));
+        let l0: Vec<_> = a_l.iter().map(|a| *a - z).collect();
+        let l1 = s_l;
+        let r0: Vec<_> = (0..n)
+            .map(|i| y_pows[i] * (a_r[i] + z) + z_pows[2 + i / bits] * two_pows[i % bits])
+            .collect();
+        let r1: Vec<_> = (0..n).map(|i| y_pows[i] * s_r[i]).collect();
+
+        // t(X) = <l(X), r(X)> = t0 + t1 X + t2 X^2
+        let t1 = inner_prod(&l0, &r1) + inner_prod(&l1, &r0);
+        let t2 = inner_prod(&l1, &r1);
+        let tau1 = G::ScalarField::rand(rng);
+        let tau2 = G::ScalarField::rand(rng);
+        let t1_comm = commit(b, srs.h, t1, tau1);
+        let t2_comm = commit(b, srs.h, t2, tau2);
+
+        sponge.absorb_g(&[t1_comm, t2_comm]);
+        let x = squeeze_challenge(&endo_r, &mut sponge);
+
+        let l: Vec<_> = l0.iter().zip(&l1).map(|(l0, l1)| *l0 + x * l1).collect();
+        let r: Vec<_> = r0.iter().zip(&r1).map(|(r0, r1)| *r0 + x * r1).collect();
+        let t_hat = inner_prod(&l, &r);
+        let tau_x = blinders
+            .iter()
+            .zip(&z_pows[2..])
+            .fold(tau2 * x * x + tau1 * x, |acc, (gamma, z_j)| {
+                acc + *z_j * gamma
+            });
+        let mu = alpha + rho * x;
+        sponge.absorb_fr(&[t_hat, tau_x, mu]);
+
+        // the points of H are scaled by y^-i, so that <r(x), H'> contains the y^i (a_r + s_r x) H_i of A + x S
+        let y_inv_pows = pows(n, y.inverse().unwrap());
+        let ipa = InnerProductProof::create(group_map, &mut sponge, g, h, Some(&y_inv_pows), l, r);
+
+        Ok(RangeProof {
+            a,
+            s,
+            t1: t1_comm,
+            t2: t2_comm,
+            t_hat,
+            tau_x,
+            mu,
+            ipa,
+        })
+    }
+
+    /// Verifies that the values committed to in `commitments` are in `[0, 2^bits)`.
+    pub fn verify<EFqSponge, RNG>(
+        &self,
+        srs: &SRS<G>,
+        group_map: &G::Map,
+        sponge: EFqSponge,
+        bits: usize,
+        commitments: &[G],
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+    {
+        srs.verify_range_proofs(
+            group_map,
+            &mut [BatchRangeProof {
+                sponge,
+                commitments: commitments.to_vec(),
+                bits,
+                proof: self,
+            }],
+            rng,
+        )
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G>
+where
+    G::BaseField: PrimeField,
+{
+    /// Commits to `value` as `value B + blinder h`, where `B` is the [`value_generator`].
+    pub fn commit_value(&self, group_map: &G::Map, value: u64, blinder: G::ScalarField) -> G {
+        commit(value_generator(group_map), self.h, value, blinder)
+    }
+
+    /// Verifies a batch of range proofs, with a single multi-scalar multiplication.
+    /// The two verification equations of each proof are combined with random scalars.
+    pub fn verify_range_proofs<EFqSponge, RNG>(
+        &self,
+        group_map: &G::Map,
+        batch: &mut [BatchRangeProof<G, EFqSponge>],
+        rng: &mut RNG,
+    ) -> bool
+    where
+        EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+        RNG: RngCore + CryptoRng,
+    {
+        let (_, endo_r) = endos::<G>();
+
+        // the scalars of B and h are shared by all the proofs
+        let mut points = vec![value_generator(group_map), self.h];
+        let mut scalars = vec![G::ScalarField::zero(); 2];
+
+        for BatchRangeProof {
+            sponge,
+            commitments,
+            bits,
+            proof,
+        } in batch.iter_mut()
+        {
+            let (bits, m) = (*bits, commitments.len());
+            let n = match total_bits(self, bits, m) {
+                Ok(n) => n,
+                Err(_) => return false,
+            };
+            let (g, h) = self.inner_product_bases(n).unwrap();
+
+            absorb_statement(sponge, bits, commitments);
+            sponge.absorb_g(&[proof.a, proof.s]);
+            let y = squeeze_challenge(&endo_r, sponge);
+            let z = squeeze_challenge(&endo_r, sponge);
+            sponge.absorb_g(&[proof.t1, proof.t2]);
+            let x = squeeze_challenge(&endo_r, sponge);
+            sponge.absorb_fr(&[proof.t_hat, proof.tau_x, proof.mu]);
+
+            let y_inv = match y.inverse() {
+                Some(y_inv) => y_inv,
+                None => return false,
+            };
+            let y_pows = pows(n, y);
+            let y_inv_pows = pows(n, y_inv);
+            let z_pows = pows(m + 3, z);
+            let two_pows = pows(bits, G::ScalarField::from(2u64));
+            let sum =
+                |xs: &[G::ScalarField]| xs.iter().fold(G::ScalarField::zero(), |acc, x| acc + x);
+
+            // t_hat B + tau_x h = sum_j z^(2+j) V_j + delta B + x T1 + x^2 T2, with
+            // delta = (z - z^2) <1, y^i> - sum_j z^(3+j) <1, 2^k>
+            let w = G::ScalarField::rand(rng);
+            let delta = (z - z * z) * sum(&y_pows) - sum(&two_pows) * sum(&z_pows[3..]);
+            scalars[0] += w * (proof.t_hat - delta);
+            scalars[1] += w * proof.tau_x;
+            points.extend(commitments.iter());
+            scalars.extend(z_pows[2..m + 2].iter().map(|z_j| -w * z_j));
+            points.extend([proof.t1, proof.t2]);
+            scalars.extend([-w * x, -w * x * x]);
+
+            // the inner-product argument of l(x) and r(x), with
+            // P = A + x S - z <1, G> + <z y^i + z^(2+j) 2^k, H'> - mu h and H'_i = y^-i H_i
+            let w = G::ScalarField::rand(rng);
+            points.extend([proof.a, proof.s]);
+            scalars.extend([w, w * x]);
+            scalars[1] -= w * proof.mu;
+            points.extend(g);
+            scalars.extend(std::iter::repeat(-w * z).take(n));
+            points.extend(h);
+            scalars.extend(
+                (0..n).map(|i| w * (z + z_pows[2 + i / bits] * two_pows[i % bits] * y_inv_pows[i])),
+            );
+            if !proof.ipa.push_verification_terms(
+                group_map,
+                sponge,
+                g,
+                h,
+                Some(&y_inv_pows),
+                proof.t_hat,
+                w,
+                &mut points,
+                &mut scalars,
+            ) {
+                return false;
+            }
+        }
+
+        G::msm(&points, &scalars).is_zero()
+    }
+}
+
+/// Returns the total number of bits of a range proof of `m` values of `bits` bits.
+fn total_bits<G: CommitmentCurve>(
+    srs: &SRS<G>,
+    bits: usize,
+    m: usize,
+) -> Result<usize, RangeProofError> {
+    if !bits.is_power_of_two() || bits > 64 {
+        return Err(RangeProofError::InvalidBits(bits));
+    }
+    if !m.is_power_of_two() {
+        return Err(RangeProofError::InvalidNumberOfValues(m));
+    }
+    let n = bits * m;
+    if srs.inner_product_bases(n).is_none() {
+        return Err(RangeProofError::SrsTooSmall(n));
+    }
+    Ok(n)
+}
+
+fn absorb_statement<G, EFqSponge>(sponge: &mut EFqSponge, bits: usize, commitments: &[G])
+where
+    G: AffineCurve,
+    EFqSponge: FqSponge<G::BaseField, G, G::ScalarField>,
+{
+    sponge.absorb_fr(&[
+        G::ScalarField::from(bits as u64),
+        G::ScalarField::from(commitments.len() as u64),
+    ]);
+    sponge.absorb_g(commitments);
+}
+
+/// Returns `value b + blinder h`
+fn commit<G: AffineCurve>(
+    b: G,
+    h: G,
+    value: impl Into<G::ScalarField>,
+    blinder: G::ScalarField,
+) -> G {
+    (b.mul(value.into()) + h.mul(blinder)).into_affine()
+}
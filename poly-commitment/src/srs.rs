@@ -1,17 +1,25 @@
 //! This module implements the Marlin structured reference string primitive
 
-use crate::commitment::CommitmentCurve;
+use crate::commitment::{BlindedCommitment, CommitmentCurve};
+use crate::error::{CommitmentError, SrsFileError};
+use crate::pcs::CommitmentKey;
 use crate::PolyComm;
 use ark_ec::{AffineCurve, ProjectiveCurve};
 use ark_ff::{BigInteger, PrimeField, Zero};
-use ark_poly::{EvaluationDomain, Radix2EvaluationDomain as D};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain as D,
+};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use blake2::{Blake2b512, Digest};
 use groupmap::GroupMap;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::array;
 use std::cmp::min;
 use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::ops::Range;
 
 #[serde_as]
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -48,7 +56,7 @@ where
     (endo_q, endo_r)
 }
 
-fn point_of_random_bytes<G: CommitmentCurve>(map: &G::Map, random_bytes: &[u8]) -> G
+pub(crate) fn point_of_random_bytes<G: CommitmentCurve>(map: &G::Map, random_bytes: &[u8]) -> G
 where
     G::BaseField: PrimeField,
 {
@@ -67,6 +75,23 @@ where
     G::of_coordinates(x, y)
 }
 
+const MISC: usize = 1;
+
+/// The group elements of an SRS that are not used to commit to coefficients,
+/// currently only the blinding generator `h`.
+/// Nobody knows their discrete logarithms in any basis.
+pub(crate) fn misc_points<G: CommitmentCurve>(map: &G::Map) -> [G; MISC]
+where
+    G::BaseField: PrimeField,
+{
+    array::from_fn(|i| {
+        let mut h = Blake2b512::new();
+        h.update("srs_misc".as_bytes());
+        h.update(&(i as u32).to_be_bytes());
+        point_of_random_bytes(map, &h.finalize())
+    })
+}
+
 impl<G: CommitmentCurve> SRS<G>
 where
     G::BaseField: PrimeField,
@@ -214,21 +239,9 @@ where
     pub fn create(depth: usize) -> Self {
         let m = G::Map::setup();
 
-        let g: Vec<_> = (0..depth)
-            .map(|i| {
-                let mut h = Blake2b512::new();
-                h.update(&(i as u32).to_be_bytes());
-                point_of_random_bytes(&m, &h.finalize())
-            })
-            .collect();
+        let g = g_points(0..depth);
 
-        const MISC: usize = 1;
-        let [h]: [G; MISC] = array::from_fn(|i| {
-            let mut h = Blake2b512::new();
-            h.update("srs_misc".as_bytes());
-            h.update(&(i as u32).to_be_bytes());
-            point_of_random_bytes(&m, &h.finalize())
-        });
+        let [h] = misc_points(&m);
 
         SRS {
             g,
@@ -236,4 +249,220 @@ where
             lagrange_bases: HashMap::new(),
         }
     }
+
+    /// Extends an SRS created with [`SRS::create`] to `depth` points,
+    /// so that it is the same as the SRS created with this depth.
+    /// Does nothing if the SRS already has `depth` points or more.
+    ///
+    /// The commitments to the Lagrange bases depend on the size of the SRS, so they are discarded.
+    pub fn extend(&mut self, depth: usize) {
+        if depth > self.g.len() {
+            self.g.extend(g_points::<G>(self.g.len()..depth));
+            self.lagrange_bases.clear();
+        }
+    }
+}
+
+impl<G: CommitmentCurve> SRS<G> {
+    /// Trims the SRS to its first `depth` points:
+    /// an SRS created with [`SRS::create`] becomes the SRS created with this depth.
+    /// Does nothing if the SRS has `depth` points or less.
+    ///
+    /// The commitments to the Lagrange bases depend on the size of the SRS, so they are discarded.
+    pub fn trim(&mut self, depth: usize) {
+        if depth < self.g.len() {
+            self.g.truncate(depth);
+            self.lagrange_bases.clear();
+        }
+    }
+
+    /// Returns a hash of the points of the SRS, which identifies it.
+    /// The commitments to the Lagrange bases are not hashed, as they are computed from the points.
+    pub fn digest(&self) -> [u8; SRS_DIGEST_SIZE] {
+        let mut hasher = Blake2b512::new();
+        hasher.update("srs_digest".as_bytes());
+        hasher.update(&(self.g.len() as u64).to_le_bytes());
+        let mut bytes = vec![];
+        for p in self.g.iter().chain([&self.h]) {
+            bytes.clear();
+            p.serialize(&mut bytes)
+                .expect("serializing to a vector does not fail");
+            hasher.update(&bytes);
+        }
+
+        let mut digest = [0u8; SRS_DIGEST_SIZE];
+        digest.copy_from_slice(&hasher.finalize());
+        digest
+    }
+
+    /// Writes the SRS in a compact format, with compressed points:
+    ///
+    /// | field   | content                                |
+    /// | ------- | -------------------------------------- |
+    /// | magic   | `b"srsc"`                              |
+    /// | version | `u32`, little-endian                   |
+    /// | depth   | `u64`, little-endian                   |
+    /// | points  | `h`, followed by the `depth` points `g` |
+    /// | digest  | the [digest](SRS::digest) of the SRS   |
+    ///
+    /// The commitments to the Lagrange bases are not written.
+    pub fn write_compressed<W: Write>(&self, mut writer: W) -> Result<(), SrsFileError> {
+        writer.write_all(SRS_MAGIC)?;
+        writer.write_all(&SRS_VERSION.to_le_bytes())?;
+        writer.write_all(&(self.g.len() as u64).to_le_bytes())?;
+
+        let size = self.h.serialized_size();
+        let mut bytes = vec![0u8; size * (self.g.len() + 1)];
+        bytes
+            .par_chunks_mut(size)
+            .zip([&self.h].into_par_iter().chain(&self.g))
+            .try_for_each(|(bytes, p)| p.serialize(bytes))
+            .map_err(serialization_error)?;
+        writer.write_all(&bytes)?;
+
+        writer.write_all(&self.digest())?;
+        Ok(())
+    }
+
+    /// Reads an SRS written by [`SRS::write_compressed`], decompressing its points in parallel.
+    /// Fails if a point is not in the prime-order subgroup, or if the digest of the SRS is wrong.
+    pub fn read_compressed<R: Read>(mut reader: R) -> Result<Self, SrsFileError> {
+        let magic: [u8; 4] = read_bytes(&mut reader)?;
+        if magic != *SRS_MAGIC {
+            return Err(SrsFileError::InvalidMagic);
+        }
+        let version = u32::from_le_bytes(read_bytes(&mut reader)?);
+        if version != SRS_VERSION {
+            return Err(SrsFileError::UnsupportedVersion(version));
+        }
+        let depth = u64::from_le_bytes(read_bytes(&mut reader)?);
+
+        // the buffer grows with the data actually read, whatever the depth in the header
+        let size = G::zero().serialized_size();
+        let len = depth
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(size as u64))
+            .ok_or(SrsFileError::Corrupted)?;
+        let mut bytes = vec![];
+        reader.by_ref().take(len).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != len {
+            return Err(SrsFileError::Corrupted);
+        }
+
+        let mut points = bytes
+            .par_chunks(size)
+            .map(G::deserialize)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(serialization_error)?;
+        let h = points.remove(0);
+        let srs = SRS {
+            g: points,
+            h,
+            lagrange_bases: HashMap::new(),
+        };
+
+        let digest: [u8; SRS_DIGEST_SIZE] = read_bytes(&mut reader)?;
+        if digest != srs.digest() {
+            return Err(SrsFileError::Corrupted);
+        }
+        Ok(srs)
+    }
+}
+
+/// The size of the [digest](SRS::digest) of an SRS
+pub const SRS_DIGEST_SIZE: usize = 64;
+
+const SRS_MAGIC: &[u8; 4] = b"srsc";
+const SRS_VERSION: u32 = 1;
+
+/// The `i`-th point of `g`, nobody knows its discrete logarithm in any basis
+fn g_point<G: CommitmentCurve>(map: &G::Map, i: usize) -> G
+where
+    G::BaseField: PrimeField,
+{
+    let mut h = Blake2b512::new();
+    h.update(&(i as u32).to_be_bytes());
+    point_of_random_bytes(map, &h.finalize())
+}
+
+/// Generates the points `g[range]` of the SRS in parallel
+fn g_points<G: CommitmentCurve>(range: Range<usize>) -> Vec<G>
+where
+    G::BaseField: PrimeField,
+{
+    range
+        .into_par_iter()
+        .map_init(G::Map::setup, |map, i| g_point(map, i))
+        .collect()
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> Result<[u8; N], SrsFileError> {
+    let mut bytes = [0u8; N];
+    reader.read_exact(&mut bytes).map_err(|e| {
+        if e.kind() == io::ErrorKind::UnexpectedEof {
+            SrsFileError::Corrupted
+        } else {
+            SrsFileError::Io(e)
+        }
+    })?;
+    Ok(bytes)
+}
+
+fn serialization_error(e: SerializationError) -> SrsFileError {
+    match e {
+        SerializationError::IoError(e) => SrsFileError::Io(e),
+        _ => SrsFileError::InvalidPoint,
+    }
+}
+
+impl<G: CommitmentCurve> CommitmentKey<G> for SRS<G> {
+    fn create(depth: usize) -> Self
+    where
+        G::BaseField: PrimeField,
+    {
+        SRS::create(depth)
+    }
+
+    fn max_poly_size(&self) -> usize {
+        self.g.len()
+    }
+
+    fn blinding_commitment(&self) -> G {
+        self.h
+    }
+
+    fn add_lagrange_basis(&mut self, domain: D<G::ScalarField>)
+    where
+        G::BaseField: PrimeField,
+    {
+        SRS::add_lagrange_basis(self, domain)
+    }
+
+    fn lagrange_basis(&self, domain_size: usize) -> Option<&[PolyComm<G>]> {
+        self.lagrange_bases.get(&domain_size).map(Vec::as_slice)
+    }
+
+    fn commit_non_hiding(
+        &self,
+        plnm: &DensePolynomial<G::ScalarField>,
+        max: Option<usize>,
+    ) -> PolyComm<G> {
+        SRS::commit_non_hiding(self, plnm, max)
+    }
+
+    fn commit_evaluations_non_hiding(
+        &self,
+        domain: D<G::ScalarField>,
+        plnm: &Evaluations<G::ScalarField, D<G::ScalarField>>,
+    ) -> PolyComm<G> {
+        SRS::commit_evaluations_non_hiding(self, domain, plnm)
+    }
+
+    fn mask_custom(
+        &self,
+        com: PolyComm<G>,
+        blinders: &PolyComm<G::ScalarField>,
+    ) -> Result<BlindedCommitment<G>, CommitmentError> {
+        SRS::mask_custom(self, com, blinders)
+    }
 }
@@ -0,0 +1,148 @@
+use crate::{
+    accumulator::Accumulators,
+    commitment::{BatchEvaluationProof, CommitmentCurve, Evaluation},
+    evaluation_proof::{DensePolynomialOrEvaluations, OpeningProof},
+    srs::SRS,
+};
+use ark_ff::UniformRand;
+use ark_poly::{univariate::DensePolynomial, Polynomial, Radix2EvaluationDomain, UVPolynomial};
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi as SC, sponge::DefaultFqSponge, FqSponge as _,
+};
+use o1_utils::ExtendedDensePolynomial as _;
+use rand::{rngs::StdRng, SeedableRng};
+
+type BaseSponge = DefaultFqSponge<VestaParameters, SC>;
+
+/// An opening proof of two polynomials at two points, with its evaluations
+struct Opening {
+    evaluations: Vec<Evaluation<Vesta>>,
+    points: Vec<Fp>,
+    polyscale: Fp,
+    evalscale: Fp,
+    proof: OpeningProof<Vesta>,
+}
+
+impl Opening {
+    fn create(
+        srs: &SRS<Vesta>,
+        group_map: &<Vesta as CommitmentCurve>::Map,
+        rng: &mut StdRng,
+    ) -> Self {
+        let polys: Vec<_> = [srs.g.len() - 3, 2 * srs.g.len() + 1]
+            .iter()
+            .map(|degree| DensePolynomial::<Fp>::rand(*degree, rng))
+            .collect();
+        let comms: Vec<_> = polys.iter().map(|p| srs.commit(p, None, rng)).collect();
+
+        let points = vec![Fp::rand(rng), Fp::rand(rng)];
+        let polyscale = Fp::rand(rng);
+        let evalscale = Fp::rand(rng);
+        let plnms: Vec<_> = polys
+            .iter()
+            .zip(&comms)
+            .map(|(p, c)| {
+                (
+                    DensePolynomialOrEvaluations::<_, Radix2EvaluationDomain<_>>::DensePolynomial(
+                        p,
+                    ),
+                    None,
+                    c.blinders.clone(),
+                )
+            })
+            .collect();
+        let proof = srs.open(
+            group_map,
+            &plnms,
+            &points,
+            polyscale,
+            evalscale,
+            sponge(),
+            rng,
+        );
+
+        let evaluations = polys
+            .iter()
+            .zip(comms)
+            .map(|(p, c)| Evaluation {
+                commitment: c.commitment,
+                evaluations: points
+                    .iter()
+                    .map(|x| p.to_chunked_polynomial(srs.g.len()).evaluate_chunks(*x))
+                    .collect(),
+                degree_bound: None,
+            })
+            .collect();
+        Opening {
+            evaluations,
+            points,
+            polyscale,
+            evalscale,
+            proof,
+        }
+    }
+
+    fn to_batch(&self) -> BatchEvaluationProof<Vesta, BaseSponge> {
+        BatchEvaluationProof {
+            sponge: sponge(),
+            evaluations: self
+                .evaluations
+                .iter()
+                .map(|e| Evaluation {
+                    commitment: e.commitment.clone(),
+                    evaluations: e.evaluations.clone(),
+                    degree_bound: e.degree_bound,
+                })
+                .collect(),
+            evaluation_points: self.points.clone(),
+            polyscale: self.polyscale,
+            evalscale: self.evalscale,
+            opening: &self.proof,
+        }
+    }
+}
+
+fn sponge() -> BaseSponge {
+    BaseSponge::new(mina_poseidon::pasta::fq_kimchi::static_params())
+}
+
+#[test]
+fn test_accumulators() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let srs = SRS::<Vesta>::create(1 << 5);
+
+    let openings: Vec<_> = (0..4)
+        .map(|_| Opening::create(&srs, &group_map, rng))
+        .collect();
+
+    // succinct checks of two batches, merged and decided together
+    let mut first: Vec<_> = openings[..3].iter().map(Opening::to_batch).collect();
+    let mut accumulators = srs.succinct_verify(&group_map, &mut first, rng).unwrap();
+    let mut second = vec![openings[3].to_batch()];
+    accumulators.merge(srs.succinct_verify(&group_map, &mut second, rng).unwrap());
+    assert_eq!(accumulators.len(), 4);
+    assert!(srs.decide(&accumulators, rng));
+
+    // an accumulator is a commitment to its challenge polynomial
+    for (acc, opening) in accumulators.iter().zip(&openings) {
+        assert_eq!(acc.sg, opening.proof.sg);
+        let poly = acc.challenge_polynomial();
+        assert_eq!(srs.commit_non_hiding(&poly, None).unshifted, vec![acc.sg]);
+        let x = Fp::rand(rng);
+        assert_eq!(acc.evaluate(x), poly.evaluate(&x));
+    }
+
+    // a single wrong accumulator makes the decision fail
+    let mut wrong = accumulators.clone();
+    wrong.accumulators[1].sg = accumulators.accumulators[2].sg;
+    assert!(!srs.decide(&wrong, rng));
+    assert!(srs.decide(&Accumulators::default(), rng));
+
+    // the succinct check fails on wrong evaluations
+    let mut batch = vec![openings[0].to_batch()];
+    batch[0].evaluations[0].evaluations[0][0] += Fp::from(1u8);
+    assert!(srs.succinct_verify(&group_map, &mut batch, rng).is_none());
+}
@@ -0,0 +1,74 @@
+use crate::{commitment::CommitmentCurve, inner_product::InnerProductProof, srs::SRS};
+use ark_ff::{UniformRand, Zero};
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi as SC, sponge::DefaultFqSponge, FqSponge as _,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+type BaseSponge = DefaultFqSponge<VestaParameters, SC>;
+
+fn sponge() -> BaseSponge {
+    BaseSponge::new(mina_poseidon::pasta::fq_kimchi::static_params())
+}
+
+fn random_vector(n: usize, rng: &mut StdRng) -> Vec<Fp> {
+    (0..n).map(|_| Fp::rand(rng)).collect()
+}
+
+#[test]
+fn test_inner_product_argument() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let srs = SRS::<Vesta>::create(32);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let n = 8;
+    let (g, h) = srs.inner_product_bases(n).unwrap();
+
+    let a = random_vector(n, rng);
+    let b = random_vector(n, rng);
+    let c: Fp = a.iter().zip(&b).map(|(a, b)| *a * b).sum();
+    let p = srs.commit_vectors(&a, &b, Fp::zero());
+
+    let proof =
+        InnerProductProof::create(&group_map, &mut sponge(), g, h, None, a.clone(), b.clone());
+    assert_eq!(proof.lr.len(), 3);
+    assert!(proof.verify(&group_map, &mut sponge(), g, h, None, p, c));
+
+    // a wrong inner product, a wrong commitment, or a wrong proof
+    assert!(!proof.verify(&group_map, &mut sponge(), g, h, None, p, c + Fp::from(1u8)));
+    let other = srs.commit_vector(&a, Fp::zero());
+    assert!(!proof.verify(&group_map, &mut sponge(), g, h, None, other, c));
+    let mut wrong = proof.clone();
+    wrong.lr.swap(0, 1);
+    assert!(!wrong.verify(&group_map, &mut sponge(), g, h, None, p, c));
+    wrong.lr.pop();
+    assert!(!wrong.verify(&group_map, &mut sponge(), g, h, None, p, c));
+}
+
+#[test]
+fn test_inner_product_argument_scaled_bases() {
+    let rng = &mut StdRng::from_seed([1u8; 32]);
+    let srs = SRS::<Vesta>::create(16);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let n = 8;
+    let (g, h) = srs.inner_product_bases(n).unwrap();
+    assert!(srs.inner_product_bases(16).is_none());
+    assert!(srs.inner_product_bases(6).is_none());
+
+    // P = <a, g> + <b, h'> with h'_i = factors_i h_i
+    let a = random_vector(n, rng);
+    let b = random_vector(n, rng);
+    let factors = random_vector(n, rng);
+    let c: Fp = a.iter().zip(&b).map(|(a, b)| *a * b).sum();
+    let scaled_b: Vec<_> = b.iter().zip(&factors).map(|(b, f)| *b * f).collect();
+    let p = srs.commit_vectors(&a, &scaled_b, Fp::zero());
+    let blinded = srs.commit_vectors(&a, &scaled_b, Fp::from(1u8));
+
+    let proof = InnerProductProof::create(&group_map, &mut sponge(), g, h, Some(&factors), a, b);
+    assert!(proof.verify(&group_map, &mut sponge(), g, h, Some(&factors), p, c));
+    assert!(!proof.verify(&group_map, &mut sponge(), g, h, None, p, c));
+
+    // the blinding point is not one of the bases
+    assert!(!proof.verify(&group_map, &mut sponge(), g, h, Some(&factors), blinded, c));
+}
@@ -0,0 +1,316 @@
+use crate::{
+    commitment::{BatchEvaluationProof, CommitmentCurve, Evaluation},
+    error::{CommitmentError, PtauError},
+    evaluation_proof::DensePolynomialOrEvaluations,
+    kzg::{BN254Proof, BN254SRS, MAX_EVALUATION_POINTS},
+    pcs::{CommitmentKey, OpenProof},
+    PolyComm,
+};
+use ark_bn254::{Fq, Fr, G1Affine};
+use ark_ff::{BigInteger, FpParameters, PrimeField, UniformRand, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Polynomial, Radix2EvaluationDomain, UVPolynomial,
+};
+use groupmap::GroupMap;
+use mina_poseidon::{poseidon::ArithmeticSpongeParams, FqSponge};
+use o1_utils::ExtendedDensePolynomial as _;
+use rand::{rngs::StdRng, SeedableRng};
+use std::io::Cursor;
+
+/// KZG openings do not use the sponge of a batch
+struct UnusedSponge;
+
+impl FqSponge<Fq, G1Affine, Fr> for UnusedSponge {
+    fn new(_: &'static ArithmeticSpongeParams<Fq>) -> Self {
+        unimplemented!()
+    }
+    fn absorb_g(&mut self, _: &[G1Affine]) {
+        unimplemented!()
+    }
+    fn absorb_fq(&mut self, _: &[Fq]) {
+        unimplemented!()
+    }
+    fn absorb_fr(&mut self, _: &[Fr]) {
+        unimplemented!()
+    }
+    fn challenge(&mut self) -> Fr {
+        unimplemented!()
+    }
+    fn challenge_fq(&mut self) -> Fq {
+        unimplemented!()
+    }
+    fn digest(self) -> Fr {
+        unimplemented!()
+    }
+    fn digest_fq(self) -> Fq {
+        unimplemented!()
+    }
+}
+
+const SRS_SIZE: usize = 16;
+
+/// Commits to a chunked polynomial and to a polynomial in evaluation form, without hiding them,
+/// and opens them at two points.
+fn open_two_polynomials(
+    srs: &mut BN254SRS,
+    rng: &mut StdRng,
+) -> (Vec<Evaluation<G1Affine>>, Vec<Fr>, Fr, BN254Proof) {
+    let domain = Radix2EvaluationDomain::<Fr>::new(SRS_SIZE).unwrap();
+    srs.add_lagrange_basis(domain);
+
+    let chunked = DensePolynomial::<Fr>::rand(2 * SRS_SIZE + 7, rng);
+    let chunked_comm = srs.commit_non_hiding(&chunked, None);
+    assert_eq!(chunked_comm.unshifted.len(), 3);
+
+    let evals = DensePolynomial::<Fr>::rand(SRS_SIZE - 1, rng).evaluate_over_domain(domain);
+    let evals_comm = srs.commit_evaluations_non_hiding(domain, &evals);
+    let evals_poly = evals.interpolate_by_ref();
+
+    let points = vec![Fr::rand(rng), Fr::rand(rng)];
+    let polyscale = Fr::rand(rng);
+    let proof = BN254Proof::create(
+        srs,
+        &[
+            (
+                DensePolynomialOrEvaluations::DensePolynomial(&chunked),
+                None,
+                chunked_comm.map(|_| Fr::zero()),
+            ),
+            (
+                DensePolynomialOrEvaluations::Evaluations(&evals, domain),
+                None,
+                evals_comm.map(|_| Fr::zero()),
+            ),
+        ],
+        &points,
+        polyscale,
+    )
+    .unwrap();
+
+    let evaluations = vec![
+        Evaluation {
+            commitment: chunked_comm,
+            evaluations: points
+                .iter()
+                .map(|x| chunked.to_chunked_polynomial(SRS_SIZE).evaluate_chunks(*x))
+                .collect(),
+            degree_bound: None,
+        },
+        Evaluation {
+            commitment: evals_comm,
+            evaluations: points
+                .iter()
+                .map(|x| vec![evals_poly.evaluate(x)])
+                .collect(),
+            degree_bound: None,
+        },
+    ];
+    (evaluations, points, polyscale, proof)
+}
+
+#[test]
+fn test_kzg_open_verify() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let mut srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    assert!(srs.check_powers(rng));
+
+    let (mut evaluations, points, polyscale, proof) = open_two_polynomials(&mut srs, rng);
+    assert!(proof.verify(&srs, &evaluations, &points, polyscale));
+
+    // a wrong evaluation is rejected
+    evaluations[1].evaluations[0][0] += Fr::from(1u8);
+    assert!(!proof.verify(&srs, &evaluations, &points, polyscale));
+}
+
+#[test]
+fn test_kzg_batch_verify() {
+    let rng = &mut StdRng::from_seed([1u8; 32]);
+    let mut srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+
+    let openings: Vec<_> = (0..3)
+        .map(|_| open_two_polynomials(&mut srs, rng))
+        .collect();
+    let wrong_proof = BN254Proof {
+        quotient: openings[0].3.quotient,
+    };
+    let mut batch: Vec<_> = openings
+        .iter()
+        .map(
+            |(evaluations, points, polyscale, proof)| BatchEvaluationProof {
+                sponge: UnusedSponge,
+                evaluations: evaluations
+                    .iter()
+                    .map(|e| Evaluation {
+                        commitment: e.commitment.clone(),
+                        evaluations: e.evaluations.clone(),
+                        degree_bound: None,
+                    })
+                    .collect(),
+                evaluation_points: points.clone(),
+                polyscale: *polyscale,
+                evalscale: Fr::from(0u8),
+                opening: proof,
+            },
+        )
+        .collect();
+    assert!(BN254Proof::verify(&srs, &group_map, &mut batch, rng));
+
+    // a single wrong proof makes the whole batch fail
+    batch[2].opening = &wrong_proof;
+    assert!(!BN254Proof::verify(&srs, &group_map, &mut batch, rng));
+}
+
+#[test]
+fn test_kzg_too_many_points() {
+    let rng = &mut StdRng::from_seed([2u8; 32]);
+    let srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+
+    let poly = DensePolynomial::<Fr>::rand(SRS_SIZE - 1, rng);
+    let points: Vec<_> = (0..=MAX_EVALUATION_POINTS).map(|_| Fr::rand(rng)).collect();
+    let polyscale = Fr::rand(rng);
+    let proof = BN254Proof::create::<Radix2EvaluationDomain<Fr>>(
+        &srs,
+        &[(
+            DensePolynomialOrEvaluations::DensePolynomial(&poly),
+            None,
+            PolyComm::new(vec![Fr::from(0u8)], None),
+        )],
+        &points,
+        polyscale,
+    )
+    .unwrap();
+    let evaluations = [Evaluation {
+        commitment: srs.commit_non_hiding(&poly, None),
+        evaluations: points.iter().map(|x| vec![poly.evaluate(x)]).collect(),
+        degree_bound: None,
+    }];
+    assert!(!proof.verify(&srs, &evaluations, &points, polyscale));
+}
+
+#[test]
+fn test_kzg_hiding_commitment() {
+    let rng = &mut StdRng::from_seed([5u8; 32]);
+    let srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+
+    let poly = DensePolynomial::<Fr>::rand(SRS_SIZE - 1, rng);
+    let comm = srs.commit(&poly, None, rng);
+    let res = BN254Proof::create::<Radix2EvaluationDomain<Fr>>(
+        &srs,
+        &[(
+            DensePolynomialOrEvaluations::DensePolynomial(&poly),
+            None,
+            comm.blinders,
+        )],
+        &[Fr::rand(rng)],
+        Fr::rand(rng),
+    );
+    assert!(matches!(res, Err(CommitmentError::HidingNotSupported)));
+}
+
+#[test]
+#[should_panic(expected = "a pairing SRS cannot be created without a trusted setup")]
+fn test_kzg_create_without_setup() {
+    <BN254SRS as CommitmentKey<G1Affine>>::create(SRS_SIZE);
+}
+
+#[test]
+fn test_kzg_check_powers() {
+    let rng = &mut StdRng::from_seed([3u8; 32]);
+    let mut srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    assert!(srs.check_powers(rng));
+
+    srs.full_srs.g.swap(3, 4);
+    assert!(!srs.check_powers(rng));
+}
+
+//
+// .ptau files
+//
+
+/// Writes a field element of BN254 in the little-endian Montgomery form of `.ptau` files
+fn write_fq(bytes: &mut Vec<u8>, x: Fq) {
+    // the internal representation is the Montgomery form
+    bytes.extend(x.0.to_bytes_le());
+}
+
+fn write_section(bytes: &mut Vec<u8>, typ: u32, data: &[u8]) {
+    bytes.extend(typ.to_le_bytes());
+    bytes.extend((data.len() as u64).to_le_bytes());
+    bytes.extend(data);
+}
+
+/// Writes a `.ptau` file with `2^(power+1) - 1` powers in the first group and `2^power` in the second group
+fn write_ptau(srs: &BN254SRS, power: u32) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend(32u32.to_le_bytes());
+    header.extend(<Fq as PrimeField>::Params::MODULUS.to_bytes_le());
+    header.extend(power.to_le_bytes());
+    header.extend(power.to_le_bytes());
+
+    let mut tau_g1 = vec![];
+    for g in &srs.full_srs.g[..(1 << (power + 1)) - 1] {
+        write_fq(&mut tau_g1, g.x);
+        write_fq(&mut tau_g1, g.y);
+    }
+
+    let mut tau_g2 = vec![];
+    for h in &srs.verifier_srs[..1 << power] {
+        for coordinate in [h.x.c0, h.x.c1, h.y.c0, h.y.c1] {
+            write_fq(&mut tau_g2, coordinate);
+        }
+    }
+
+    let mut bytes = b"ptau".to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(3u32.to_le_bytes());
+    // the sections do not have to be in order
+    write_section(&mut bytes, 2, &tau_g1);
+    write_section(&mut bytes, 1, &header);
+    write_section(&mut bytes, 3, &tau_g2);
+    bytes
+}
+
+#[test]
+fn test_load_ptau() {
+    let rng = &mut StdRng::from_seed([4u8; 32]);
+    let srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    let ptau = write_ptau(&srs, 2);
+
+    let loaded = BN254SRS::load_ptau(Cursor::new(&ptau), 5).unwrap();
+    assert_eq!(loaded.full_srs.g, srs.full_srs.g[..5]);
+    assert_eq!(loaded.full_srs.h, srs.full_srs.h);
+    assert_eq!(loaded.verifier_srs, srs.verifier_srs[..4]);
+
+    assert!(matches!(
+        BN254SRS::load_ptau(Cursor::new(&ptau), 8),
+        Err(PtauError::NotEnoughPowers {
+            requested: 8,
+            available: 7
+        })
+    ));
+    assert!(matches!(
+        BN254SRS::load_ptau(Cursor::new(&ptau[1..]), 5),
+        Err(PtauError::InvalidMagic)
+    ));
+
+    // a point that is not on the curve, after the 12 bytes of the file header,
+    // the 12 bytes of the header of the section of the first group and the first coordinate
+    let mut off_curve = ptau.clone();
+    off_curve[12 + 12 + 32] ^= 1;
+    assert!(matches!(
+        BN254SRS::load_ptau(Cursor::new(&off_curve), 5),
+        Err(PtauError::InvalidPoint)
+    ));
+
+    // powers of another tau in the second group
+    let other = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    let mixed = BN254SRS {
+        full_srs: srs.full_srs.clone(),
+        verifier_srs: other.verifier_srs,
+    };
+    assert!(matches!(
+        BN254SRS::load_ptau(Cursor::new(write_ptau(&mixed, 2)), 5),
+        Err(PtauError::InconsistentPowers)
+    ));
+}
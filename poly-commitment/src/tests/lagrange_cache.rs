@@ -0,0 +1,160 @@
+use crate::{error::LagrangeCacheError, lagrange_cache::LagrangeCache, srs::SRS};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use blake2::{Blake2b512, Digest};
+use mina_curves::pasta::{Fp, Vesta};
+use std::fs;
+
+const SRS_SIZE: usize = 16;
+
+fn cache(name: &str) -> LagrangeCache {
+    let dir = std::env::temp_dir().join(format!("{name}_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    LagrangeCache::new(dir)
+}
+
+#[test]
+fn test_lagrange_cache_round_trip() {
+    let cache = cache("lagrange_cache_round_trip");
+    let mut srs = SRS::<Vesta>::create(SRS_SIZE);
+
+    // a domain smaller than the SRS, and a larger one with chunked commitments
+    for n in [SRS_SIZE / 2, 2 * SRS_SIZE] {
+        let domain = Radix2EvaluationDomain::<Fp>::new(n).unwrap();
+        assert!(cache.load::<Vesta>(&srs.digest(), n).unwrap().is_none());
+        srs.add_lagrange_basis_cached(domain, &cache).unwrap();
+        assert!(cache.path(n).exists());
+
+        // another process only loads the basis it needs
+        let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+        fresh.add_lagrange_basis_cached(domain, &cache).unwrap();
+        assert_eq!(fresh.lagrange_bases.len(), 1);
+        assert_eq!(fresh.lagrange_bases[&n], srs.lagrange_bases[&n]);
+
+        let mut computed = SRS::<Vesta>::create(SRS_SIZE);
+        computed.add_lagrange_basis(domain);
+        assert_eq!(fresh.lagrange_bases[&n], computed.lagrange_bases[&n]);
+    }
+
+    let _ = fs::remove_dir_all(cache.dir());
+}
+
+#[test]
+fn test_lagrange_cache_integrity() {
+    let cache = cache("lagrange_cache_integrity");
+    let n = SRS_SIZE;
+    let domain = Radix2EvaluationDomain::<Fp>::new(n).unwrap();
+    let mut srs = SRS::<Vesta>::create(SRS_SIZE);
+    srs.add_lagrange_basis_cached(domain, &cache).unwrap();
+    let bytes = fs::read(cache.path(n)).unwrap();
+
+    // the basis of another SRS
+    let other = SRS::<Vesta>::create(SRS_SIZE + 1);
+    assert!(matches!(
+        cache.load::<Vesta>(&other.digest(), n),
+        Err(LagrangeCacheError::WrongSrs)
+    ));
+
+    // the file of another domain
+    fs::copy(cache.path(n), cache.path(2 * n)).unwrap();
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), 2 * n),
+        Err(LagrangeCacheError::WrongDomainSize {
+            expected: 32,
+            found: 16
+        })
+    ));
+
+    // a modified point
+    let mut corrupted = bytes.clone();
+    corrupted[bytes.len() / 2] ^= 1;
+    fs::write(cache.path(n), &corrupted).unwrap();
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), n),
+        Err(LagrangeCacheError::Corrupted)
+    ));
+
+    // a truncated file
+    fs::write(cache.path(n), &bytes[..bytes.len() - 1]).unwrap();
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), n),
+        Err(LagrangeCacheError::Corrupted)
+    ));
+
+    // a corrupted file is recomputed and overwritten
+    let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+    fresh.add_lagrange_basis_cached(domain, &cache).unwrap();
+    assert_eq!(fresh.lagrange_bases[&n], srs.lagrange_bases[&n]);
+    assert_eq!(fs::read(cache.path(n)).unwrap(), bytes);
+
+    // so is a file that is not a cache file
+    fs::write(cache.path(n), b"not a cache file").unwrap();
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), n),
+        Err(LagrangeCacheError::InvalidMagic)
+    ));
+    let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+    fresh.add_lagrange_basis_cached(domain, &cache).unwrap();
+    assert_eq!(fresh.lagrange_bases[&n], srs.lagrange_bases[&n]);
+    assert_eq!(fs::read(cache.path(n)).unwrap(), bytes);
+
+    // or a file of the first version, which did not contain the SRS digest
+    let mut old = bytes.clone();
+    old[4..8].copy_from_slice(&1u32.to_le_bytes());
+    fs::write(cache.path(n), &old).unwrap();
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), n),
+        Err(LagrangeCacheError::UnsupportedVersion(1))
+    ));
+    let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+    fresh.add_lagrange_basis_cached(domain, &cache).unwrap();
+    assert_eq!(fs::read(cache.path(n)).unwrap(), bytes);
+
+    // or the file of another domain
+    let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+    let domain2 = Radix2EvaluationDomain::<Fp>::new(2 * n).unwrap();
+    fresh.add_lagrange_basis_cached(domain2, &cache).unwrap();
+    assert_eq!(
+        cache.load::<Vesta>(&srs.digest(), 2 * n).unwrap().unwrap(),
+        fresh.lagrange_bases[&(2 * n)]
+    );
+
+    let _ = fs::remove_dir_all(cache.dir());
+}
+
+#[test]
+fn test_lagrange_cache_invalid_point() {
+    let cache = cache("lagrange_cache_invalid_point");
+    let n = SRS_SIZE;
+    let domain = Radix2EvaluationDomain::<Fp>::new(n).unwrap();
+    let mut srs = SRS::<Vesta>::create(SRS_SIZE);
+    srs.add_lagrange_basis_cached(domain, &cache).unwrap();
+
+    // a point that is not on the curve, in a file with a valid checksum
+    let mut bytes = fs::read(cache.path(n)).unwrap();
+    let checksum_start = bytes.len() - 64;
+    bytes[checksum_start / 2] ^= 1;
+    let checksum = Blake2b512::digest(&bytes[..checksum_start]);
+    bytes[checksum_start..].copy_from_slice(&checksum);
+    fs::write(cache.path(n), &bytes).unwrap();
+
+    assert!(matches!(
+        cache.load::<Vesta>(&srs.digest(), n),
+        Err(LagrangeCacheError::InvalidPoint)
+    ));
+    let mut fresh = SRS::<Vesta>::create(SRS_SIZE);
+    assert!(matches!(
+        fresh.add_lagrange_basis_cached(domain, &cache),
+        Err(LagrangeCacheError::InvalidPoint)
+    ));
+
+    // unless the points are not checked
+    let loaded = cache
+        .clone()
+        .unchecked()
+        .load::<Vesta>(&srs.digest(), n)
+        .unwrap()
+        .unwrap();
+    assert_ne!(loaded, srs.lagrange_bases[&n]);
+
+    let _ = fs::remove_dir_all(cache.dir());
+}
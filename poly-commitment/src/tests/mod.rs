@@ -1,2 +1,11 @@
+mod accumulator;
 mod batch_15_wires;
 mod commitment;
+mod inner_product;
+mod kzg;
+mod lagrange_cache;
+mod msm;
+mod multi_point;
+mod range_proof;
+mod sparse;
+mod srs;
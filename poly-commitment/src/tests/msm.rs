@@ -0,0 +1,110 @@
+use crate::{
+    commitment::CommitmentCurve,
+    msm::{glv, glv_msm, Glv},
+};
+use ark_ec::{msm::VariableBaseMSM, AffineCurve, ProjectiveCurve, SWModelParameters};
+use ark_ff::{One, PrimeField, UniformRand, Zero};
+use mina_curves::pasta::{Fp, PallasParameters, VestaParameters};
+use rand::{rngs::StdRng, SeedableRng};
+
+type Affine<P> = ark_ec::models::short_weierstrass_jacobian::GroupAffine<P>;
+
+/// Compares the GLV multi-scalar multiplication with the one of arkworks,
+/// including zero scalars, points at infinity and repeated points
+fn check_msm<P: SWModelParameters + Clone>(rng: &mut StdRng)
+where
+    P::BaseField: PrimeField,
+{
+    let glv = Glv::<P>::new();
+    for n in [0, 1, 2, 7, 100, 1000] {
+        let mut bases: Vec<Affine<P>> = (0..n)
+            .map(|_| Affine::<P>::prime_subgroup_generator().mul(P::ScalarField::rand(rng)))
+            .map(|p| p.into_affine())
+            .collect();
+        let mut scalars: Vec<_> = (0..n).map(|_| P::ScalarField::rand(rng)).collect();
+        if n >= 7 {
+            scalars[0] = P::ScalarField::zero();
+            scalars[1] = P::ScalarField::one();
+            scalars[2] = -P::ScalarField::one();
+            bases[3] = Affine::<P>::zero();
+            bases[4] = bases[5];
+            bases[6] = -bases[5];
+        }
+
+        let scalars_repr: Vec<_> = scalars.iter().map(|k| k.into_repr()).collect();
+        let expected = VariableBaseMSM::multi_scalar_mul(&bases, &scalars_repr);
+        assert_eq!(glv.msm(&bases, &scalars), expected);
+        assert_eq!(glv_msm(&bases, &scalars), expected);
+        assert_eq!(Affine::<P>::msm(&bases, &scalars), expected);
+    }
+}
+
+#[test]
+fn test_glv_msm_vesta() {
+    check_msm::<VestaParameters>(&mut StdRng::from_seed([0u8; 32]));
+}
+
+#[test]
+fn test_glv_msm_pallas() {
+    check_msm::<PallasParameters>(&mut StdRng::from_seed([1u8; 32]));
+}
+
+#[test]
+fn test_glv_msm_bn254() {
+    check_msm::<ark_bn254::g1::Parameters>(&mut StdRng::from_seed([3u8; 32]));
+}
+
+#[test]
+fn test_glv_cached_per_curve() {
+    assert!(std::ptr::eq(
+        glv::<VestaParameters>(),
+        glv::<VestaParameters>()
+    ));
+
+    // each curve has its own decomposition
+    let vesta = Affine::<VestaParameters>::prime_subgroup_generator();
+    let pallas = Affine::<PallasParameters>::prime_subgroup_generator();
+    assert_eq!(
+        glv::<VestaParameters>().endomorphism(&vesta),
+        Glv::<VestaParameters>::new().endomorphism(&vesta)
+    );
+    assert_eq!(
+        glv::<PallasParameters>().endomorphism(&pallas),
+        Glv::<PallasParameters>::new().endomorphism(&pallas)
+    );
+}
+
+#[test]
+fn test_glv_decomposition() {
+    let rng = &mut StdRng::from_seed([2u8; 32]);
+    let glv = Glv::<VestaParameters>::new();
+    let g = Affine::<VestaParameters>::prime_subgroup_generator();
+
+    for _ in 0..100 {
+        let k = Fp::rand(rng);
+        let [(neg1, k1), (neg2, k2)] = glv.decompose(k);
+
+        // k G = k1 G + k2 phi(G)
+        let to_scalar = |neg: bool, k: u128| {
+            let k = Fp::from(k);
+            if neg {
+                -k
+            } else {
+                k
+            }
+        };
+        let k1 = to_scalar(neg1, k1);
+        let k2 = to_scalar(neg2, k2);
+        assert_eq!(
+            g.mul(k1.into_repr()) + glv.endomorphism(&g).mul(k2.into_repr()),
+            g.mul(k.into_repr())
+        );
+    }
+
+    // the decomposition of small scalars is trivial
+    let one = Fp::one();
+    let [(neg1, k1), (_, k2)] = glv.decompose(one);
+    assert!(!neg1);
+    assert_eq!(k1, 1);
+    assert_eq!(k2, 0);
+}
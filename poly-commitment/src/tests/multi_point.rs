@@ -0,0 +1,180 @@
+use crate::{
+    commitment::{BlindedCommitment, CommitmentCurve},
+    evaluation_proof::OpeningProof,
+    kzg::{BN254Proof, BN254SRS},
+    multi_point::{MultiPointProof, PointSetEvaluation},
+    pcs::{CommitmentKey, OpenProof},
+    srs::SRS,
+};
+use ark_bn254::{Fq, Fr, G1Affine};
+use ark_ff::{PrimeField, UniformRand, Zero};
+use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+use ark_serialize::CanonicalSerialize;
+use blake2::{Blake2b512, Digest};
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi as SC, poseidon::ArithmeticSpongeParams,
+    sponge::DefaultFqSponge, FqSponge,
+};
+use o1_utils::ExtendedDensePolynomial as _;
+use rand::{rngs::StdRng, SeedableRng};
+
+type BaseSponge = DefaultFqSponge<VestaParameters, SC>;
+type IpaProof = OpeningProof<Vesta>;
+
+const SRS_SIZE: usize = 16;
+
+/// Commits to polynomials of different numbers of chunks, hiding them if `hiding` is set,
+/// and opens each of them at its own set of points, some of which are shared.
+fn open_at_point_sets<G, Proof, EFqSponge>(
+    srs: &Proof::SRS,
+    hiding: bool,
+    sponge: EFqSponge,
+    rng: &mut StdRng,
+) -> (
+    Vec<PointSetEvaluation<G>>,
+    G::ScalarField,
+    MultiPointProof<G, Proof>,
+)
+where
+    G: CommitmentCurve,
+    G::BaseField: PrimeField,
+    Proof: OpenProof<G>,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+{
+    let zeta = G::ScalarField::rand(rng);
+    let zeta_omega = G::ScalarField::rand(rng);
+    let polys_and_points = [
+        (2 * SRS_SIZE + 3, vec![zeta, zeta_omega]),
+        (SRS_SIZE - 1, vec![zeta]),
+        (
+            SRS_SIZE / 2,
+            vec![G::ScalarField::rand(rng), zeta_omega, zeta],
+        ),
+    ];
+
+    let mut plnms = vec![];
+    let mut evaluations = vec![];
+    for (degree, points) in polys_and_points {
+        let poly = DensePolynomial::<G::ScalarField>::rand(degree, rng);
+        let comm = if hiding {
+            srs.commit(&poly, None, rng)
+        } else {
+            let commitment = srs.commit_non_hiding(&poly, None);
+            let blinders = commitment.map(|_| G::ScalarField::zero());
+            BlindedCommitment {
+                commitment,
+                blinders,
+            }
+        };
+        let chunked = poly.to_chunked_polynomial(SRS_SIZE);
+        evaluations.push(PointSetEvaluation {
+            commitment: comm.commitment,
+            evaluations: points.iter().map(|x| chunked.evaluate_chunks(*x)).collect(),
+            points: points.clone(),
+        });
+        plnms.push((poly, comm.blinders, points));
+    }
+
+    let plnms: Vec<_> = plnms
+        .iter()
+        .map(|(poly, blinders, points)| (poly, blinders.clone(), points.clone()))
+        .collect();
+    let polyscale = G::ScalarField::rand(rng);
+    let group_map = G::Map::setup();
+    let proof = MultiPointProof::create(srs, &group_map, &plnms, polyscale, sponge, rng)
+        .unwrap()
+        .unwrap();
+    (evaluations, polyscale, proof)
+}
+
+#[test]
+fn test_multi_point_ipa() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let srs = SRS::<Vesta>::create(SRS_SIZE);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let sponge = || BaseSponge::new(mina_poseidon::pasta::fq_kimchi::static_params());
+
+    let (mut evaluations, polyscale, proof) =
+        open_at_point_sets::<Vesta, IpaProof, _>(&srs, true, sponge(), rng);
+    assert!(proof.verify(&srs, &group_map, &evaluations, polyscale, sponge(), rng));
+
+    // two proofs reduced to single-point openings, and verified in a batch
+    let (other_evaluations, other_polyscale, other_proof) =
+        open_at_point_sets::<Vesta, IpaProof, _>(&srs, true, sponge(), rng);
+    let mut batch = vec![
+        proof.reduce(&evaluations, polyscale, sponge()).unwrap(),
+        other_proof
+            .reduce(&other_evaluations, other_polyscale, sponge())
+            .unwrap(),
+    ];
+    assert!(<IpaProof as OpenProof<Vesta>>::verify(
+        &srs, &group_map, &mut batch, rng
+    ));
+
+    // a wrong evaluation of a chunk
+    evaluations[0].evaluations[1][2] += Fp::from(1u8);
+    assert!(!proof.verify(&srs, &group_map, &evaluations, polyscale, sponge(), rng));
+    evaluations[0].evaluations[1][2] -= Fp::from(1u8);
+
+    // an evaluation claimed at another point
+    evaluations[1].points[0] = evaluations[0].points[1];
+    assert!(!proof.verify(&srs, &group_map, &evaluations, polyscale, sponge(), rng));
+
+    // malformed evaluations
+    evaluations[1].points.push(evaluations[0].points[1]);
+    assert!(proof.reduce(&evaluations, polyscale, sponge()).is_none());
+}
+
+/// A sponge hashing the points it absorbs with Blake2b, as there are no Poseidon parameters for BN254
+#[derive(Clone)]
+struct HashSponge(Blake2b512);
+
+impl FqSponge<Fq, G1Affine, Fr> for HashSponge {
+    fn new(_: &'static ArithmeticSpongeParams<Fq>) -> Self {
+        HashSponge(Blake2b512::new())
+    }
+    fn absorb_g(&mut self, g: &[G1Affine]) {
+        for p in g {
+            let mut bytes = vec![];
+            p.serialize(&mut bytes).unwrap();
+            self.0.update(&bytes);
+        }
+    }
+    fn absorb_fq(&mut self, _: &[Fq]) {
+        unimplemented!()
+    }
+    fn absorb_fr(&mut self, _: &[Fr]) {
+        unimplemented!()
+    }
+    fn challenge(&mut self) -> Fr {
+        let digest = self.0.clone().finalize();
+        self.0.update(&digest);
+        Fr::from_le_bytes_mod_order(&digest[..16])
+    }
+    fn challenge_fq(&mut self) -> Fq {
+        unimplemented!()
+    }
+    fn digest(self) -> Fr {
+        unimplemented!()
+    }
+    fn digest_fq(self) -> Fq {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_multi_point_kzg() {
+    let rng = &mut StdRng::from_seed([1u8; 32]);
+    let srs = BN254SRS::create_trusted_setup(Fr::rand(rng), SRS_SIZE);
+    let group_map = <G1Affine as CommitmentCurve>::Map::setup();
+    let sponge = || HashSponge(Blake2b512::new());
+
+    let (mut evaluations, polyscale, proof) =
+        open_at_point_sets::<G1Affine, BN254Proof, _>(&srs, false, sponge(), rng);
+    assert!(proof.verify(&srs, &group_map, &evaluations, polyscale, sponge(), rng));
+
+    evaluations[2].evaluations[0][0] += Fr::from(1u8);
+    assert!(!proof.verify(&srs, &group_map, &evaluations, polyscale, sponge(), rng));
+}
@@ -0,0 +1,201 @@
+use crate::{
+    commitment::CommitmentCurve,
+    error::RangeProofError,
+    range_proof::{BatchRangeProof, RangeProof},
+    srs::SRS,
+};
+use ark_ff::UniformRand;
+use groupmap::GroupMap;
+use mina_curves::pasta::{Fp, Fq, Pallas, PallasParameters, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi as SC, sponge::DefaultFqSponge, FqSponge as _,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+type VestaSponge = DefaultFqSponge<VestaParameters, SC>;
+type PallasSponge = DefaultFqSponge<PallasParameters, SC>;
+
+fn vesta_sponge() -> VestaSponge {
+    VestaSponge::new(mina_poseidon::pasta::fq_kimchi::static_params())
+}
+
+fn pallas_sponge() -> PallasSponge {
+    PallasSponge::new(mina_poseidon::pasta::fp_kimchi::static_params())
+}
+
+#[test]
+fn test_range_proof() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let srs = SRS::<Vesta>::create(128);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+
+    let value = u64::MAX - 7;
+    let blinder = Fp::rand(rng);
+    let commitment = srs.commit_value(&group_map, value, blinder);
+    let proof = RangeProof::create(
+        &srs,
+        &group_map,
+        vesta_sponge(),
+        64,
+        &[value],
+        &[blinder],
+        rng,
+    )
+    .unwrap();
+    assert_eq!(proof.ipa.lr.len(), 6);
+    assert!(proof.verify(&srs, &group_map, vesta_sponge(), 64, &[commitment], rng));
+
+    // another value, another number of bits, or a wrong proof
+    let other = srs.commit_value(&group_map, value, Fp::rand(rng));
+    assert!(!proof.verify(&srs, &group_map, vesta_sponge(), 64, &[other], rng));
+    assert!(!proof.verify(&srs, &group_map, vesta_sponge(), 32, &[commitment], rng));
+    let mut wrong = proof.clone();
+    wrong.t_hat += Fp::from(1u8);
+    assert!(!wrong.verify(&srs, &group_map, vesta_sponge(), 64, &[commitment], rng));
+}
+
+#[test]
+fn test_aggregated_range_proofs() {
+    let rng = &mut StdRng::from_seed([1u8; 32]);
+    let srs = SRS::<Pallas>::create(256);
+    let group_map = <Pallas as CommitmentCurve>::Map::setup();
+
+    let values = [0, 1, u32::MAX as u64, 1 << 20];
+    let blinders: Vec<_> = values.iter().map(|_| Fq::rand(rng)).collect();
+    let commitments: Vec<_> = values
+        .iter()
+        .zip(&blinders)
+        .map(|(v, gamma)| srs.commit_value(&group_map, *v, *gamma))
+        .collect();
+    let proof = RangeProof::create(
+        &srs,
+        &group_map,
+        pallas_sponge(),
+        32,
+        &values,
+        &blinders,
+        rng,
+    )
+    .unwrap();
+    assert!(proof.verify(&srs, &group_map, pallas_sponge(), 32, &commitments, rng));
+
+    // the commitments are bound to their positions
+    let mut swapped = commitments.clone();
+    swapped.swap(1, 2);
+    assert!(!proof.verify(&srs, &group_map, pallas_sponge(), 32, &swapped, rng));
+
+    // a batch of proofs with different numbers of values and bits, then with a bad proof
+    let value_blinder = Fq::rand(rng);
+    let single = srs.commit_value(&group_map, 255, value_blinder);
+    let small_proof = RangeProof::create(
+        &srs,
+        &group_map,
+        pallas_sponge(),
+        8,
+        &[255],
+        &[value_blinder],
+        rng,
+    )
+    .unwrap();
+    let mut batch = vec![
+        BatchRangeProof {
+            sponge: pallas_sponge(),
+            commitments: commitments.clone(),
+            bits: 32,
+            proof: &proof,
+        },
+        BatchRangeProof {
+            sponge: pallas_sponge(),
+            commitments: vec![single],
+            bits: 8,
+            proof: &small_proof,
+        },
+    ];
+    assert!(srs.verify_range_proofs(&group_map, &mut batch, rng));
+
+    let mut wrong = small_proof.clone();
+    wrong.mu += Fq::from(1u8);
+    let mut batch = vec![
+        BatchRangeProof {
+            sponge: pallas_sponge(),
+            commitments,
+            bits: 32,
+            proof: &proof,
+        },
+        BatchRangeProof {
+            sponge: pallas_sponge(),
+            commitments: vec![single],
+            bits: 8,
+            proof: &wrong,
+        },
+    ];
+    assert!(!srs.verify_range_proofs(&group_map, &mut batch, rng));
+}
+
+#[test]
+fn test_range_proof_errors() {
+    let rng = &mut StdRng::from_seed([2u8; 32]);
+    let srs = SRS::<Vesta>::create(64);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let blinders = [Fp::rand(rng), Fp::rand(rng)];
+    let mut create = |bits, values: &[u64], blinders: &[Fp]| {
+        RangeProof::create(
+            &srs,
+            &group_map,
+            vesta_sponge(),
+            bits,
+            values,
+            blinders,
+            rng,
+        )
+        .unwrap_err()
+    };
+
+    assert_eq!(
+        create(16, &[1 << 16], &blinders[..1]),
+        RangeProofError::ValueOutOfRange(1 << 16)
+    );
+    assert_eq!(
+        create(24, &[1], &blinders[..1]),
+        RangeProofError::InvalidBits(24)
+    );
+    assert_eq!(
+        create(128, &[1], &blinders[..1]),
+        RangeProofError::InvalidBits(128)
+    );
+    assert_eq!(
+        create(8, &[], &[]),
+        RangeProofError::InvalidNumberOfValues(0)
+    );
+    assert_eq!(
+        create(8, &[1, 2, 3], &blinders),
+        RangeProofError::InvalidNumberOfValues(3)
+    );
+    assert_eq!(
+        create(8, &[1, 2], &blinders[..1]),
+        RangeProofError::BlindersDontMatch {
+            values: 2,
+            blinders: 1
+        }
+    );
+    assert_eq!(
+        create(64, &[1, 2], &blinders),
+        RangeProofError::SrsTooSmall(128)
+    );
+}
+
+#[test]
+fn test_range_proof_serialization() {
+    let rng = &mut StdRng::from_seed([3u8; 32]);
+    let srs = SRS::<Vesta>::create(64);
+    let group_map = <Vesta as CommitmentCurve>::Map::setup();
+    let blinder = Fp::rand(rng);
+    let commitment = srs.commit_value(&group_map, 42, blinder);
+    let proof =
+        RangeProof::create(&srs, &group_map, vesta_sponge(), 16, &[42], &[blinder], rng).unwrap();
+
+    let bytes = rmp_serde::to_vec(&proof).unwrap();
+    let deserialized: RangeProof<Vesta> = rmp_serde::from_slice(&bytes).unwrap();
+    assert_eq!(deserialized, proof);
+    assert!(deserialized.verify(&srs, &group_map, vesta_sponge(), 16, &[commitment], rng));
+}
@@ -0,0 +1,166 @@
+use crate::{
+    commitment::{is_sparse, PolyComm},
+    srs::SRS,
+};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{One, UniformRand, Zero};
+use ark_poly::{
+    univariate::DensePolynomial, EvaluationDomain, Evaluations, Radix2EvaluationDomain as D,
+    UVPolynomial,
+};
+use mina_curves::pasta::{Fp, Vesta};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Returns a vector of `n` scalars, of which about one in `inverse_density` is non-zero,
+/// half of them being one.
+fn sparse_vector(n: usize, inverse_density: u32, rng: &mut StdRng) -> Vec<Fp> {
+    (0..n)
+        .map(|_| {
+            if !rng.gen_ratio(1, inverse_density) {
+                Fp::zero()
+            } else if rng.gen() {
+                Fp::one()
+            } else {
+                Fp::rand(rng)
+            }
+        })
+        .collect()
+}
+
+/// Commits to each chunk of `coeffs` with scalar multiplications only
+fn naive_commitment(srs: &SRS<Vesta>, coeffs: &[Fp]) -> Vec<Vesta> {
+    coeffs
+        .chunks(srs.g.len())
+        .map(|chunk| {
+            chunk
+                .iter()
+                .zip(&srs.g)
+                .fold(<Vesta as AffineCurve>::Projective::zero(), |acc, (c, g)| {
+                    acc + g.mul(*c)
+                })
+                .into_affine()
+        })
+        .collect()
+}
+
+#[test]
+fn test_sparse_commit_non_hiding() {
+    let rng = &mut StdRng::from_seed([0u8; 32]);
+    let srs = SRS::<Vesta>::create(16);
+
+    // a sparse polynomial of three chunks, with degree bounds that shift its last chunk or not
+    let mut coeffs = sparse_vector(40, 8, rng);
+    coeffs.push(Fp::one());
+    let sparse = DensePolynomial::from_coefficients_vec(coeffs.clone());
+    assert!(is_sparse(&sparse.coeffs));
+    for max in [None, Some(41), Some(48), Some(100)] {
+        let comm = srs.commit_non_hiding(&sparse, max);
+        assert_eq!(comm.unshifted, naive_commitment(&srs, &coeffs));
+        assert_eq!(comm, srs.commit_sparse_non_hiding(&sparse, max));
+    }
+    assert_eq!(
+        srs.commit_non_hiding(&sparse, Some(41)).shifted,
+        Some(naive_commitment(&srs, &[vec![Fp::zero(); 7], coeffs[32..].to_vec()].concat())[0])
+    );
+
+    // the sparse path gives the same commitments for dense polynomials
+    let dense = DensePolynomial::<Fp>::rand(40, rng);
+    assert!(!is_sparse(&dense.coeffs));
+    for max in [None, Some(41), Some(48)] {
+        assert_eq!(
+            srs.commit_non_hiding(&dense, max),
+            srs.commit_sparse_non_hiding(&dense, max)
+        );
+    }
+    let zero = DensePolynomial::<Fp>::zero();
+    assert_eq!(
+        srs.commit_non_hiding(&zero, Some(20)),
+        PolyComm::new(vec![Vesta::zero()], Some(Vesta::zero()))
+    );
+}
+
+#[test]
+fn test_sparse_commit_evaluations_non_hiding() {
+    let rng = &mut StdRng::from_seed([1u8; 32]);
+    let mut srs = SRS::<Vesta>::create(16);
+    let domain = D::<Fp>::new(64).unwrap();
+    let domain8 = D::<Fp>::new(512).unwrap();
+    srs.add_lagrange_basis(domain);
+
+    // the commitments with the Lagrange basis are the commitments to the interpolated polynomials
+    for inverse_density in [1, 2, 16, 64] {
+        let mut evals = sparse_vector(64, inverse_density, rng);
+        evals[5] = Fp::rand(rng);
+        let evals = Evaluations::from_vec_and_domain(evals, domain);
+        let expected = srs.commit_non_hiding(&evals.interpolate_by_ref(), None);
+        assert_eq!(expected.unshifted.len(), 4);
+        assert_eq!(srs.commit_evaluations_non_hiding(domain, &evals), expected);
+
+        let non_zero: Vec<_> = evals
+            .evals
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.is_zero())
+            .map(|(i, e)| (i, *e))
+            .collect();
+        assert_eq!(
+            srs.commit_sparse_evaluations_non_hiding(domain, &non_zero),
+            expected
+        );
+
+        // the same evaluations, over a larger domain
+        let evals8 = Evaluations::from_vec_and_domain(
+            evals
+                .interpolate_by_ref()
+                .evaluate_over_domain(domain8)
+                .evals,
+            domain8,
+        );
+        assert_eq!(srs.commit_evaluations_non_hiding(domain, &evals8), expected);
+    }
+
+    assert_eq!(
+        srs.commit_sparse_evaluations_non_hiding(domain, &[]),
+        PolyComm::new(vec![Vesta::zero(); 4], None)
+    );
+}
+
+#[test]
+fn test_commit_evaluations_shifted_basis() {
+    let rng = &mut StdRng::from_seed([2u8; 32]);
+    let mut srs = SRS::<Vesta>::create(16);
+    let domain = D::<Fp>::new(64).unwrap();
+    srs.add_lagrange_basis(domain);
+
+    // a basis whose commitments have a shifted part, which is combined like the unshifted ones
+    let basis: Vec<_> = srs.lagrange_bases[&64]
+        .iter()
+        .map(|comm| {
+            let shifted = Vesta::prime_subgroup_generator().mul(Fp::rand(rng));
+            PolyComm::new(comm.unshifted.clone(), Some(shifted.into_affine()))
+        })
+        .collect();
+    srs.lagrange_bases.insert(64, basis.clone());
+    let basis: Vec<_> = basis.iter().collect();
+
+    for inverse_density in [1, 16] {
+        let mut evals = sparse_vector(64, inverse_density, rng);
+        evals[5] = Fp::rand(rng);
+        let expected = PolyComm::multi_scalar_mul(&basis, &evals);
+        assert!(expected.shifted.is_some());
+
+        let evals = Evaluations::from_vec_and_domain(evals, domain);
+        assert_eq!(srs.commit_evaluations_non_hiding(domain, &evals), expected);
+        let non_zero: Vec<_> = evals
+            .evals
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.is_zero())
+            .map(|(i, e)| (i, *e))
+            .collect();
+        assert_eq!(
+            srs.commit_sparse_evaluations_non_hiding(domain, &non_zero),
+            expected
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{error::SrsFileError, srs::SRS};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use ark_serialize::CanonicalSerialize;
+use mina_curves::pasta::{Fp, Vesta};
+
+#[test]
+fn test_srs_extend_trim() {
+    let small = SRS::<Vesta>::create(8);
+    let large = SRS::<Vesta>::create(16);
+
+    let mut extended = small.clone();
+    extended.add_lagrange_basis(Radix2EvaluationDomain::<Fp>::new(8).unwrap());
+    extended.extend(16);
+    assert_eq!(extended.g, large.g);
+    assert_eq!(extended.h, large.h);
+    assert!(extended.lagrange_bases.is_empty());
+    assert_eq!(extended.digest(), large.digest());
+
+    let mut trimmed = large.clone();
+    trimmed.trim(8);
+    assert_eq!(trimmed.g, small.g);
+    assert_eq!(trimmed.digest(), small.digest());
+
+    // extending to a smaller depth and trimming to a larger one do nothing
+    trimmed.extend(4);
+    trimmed.trim(12);
+    assert_eq!(trimmed.g.len(), 8);
+
+    assert_ne!(small.digest(), large.digest());
+}
+
+#[test]
+fn test_srs_compressed_file() {
+    let srs = SRS::<Vesta>::create(16);
+    let mut bytes = vec![];
+    srs.write_compressed(&mut bytes).unwrap();
+    // the header, the compressed points and the digest
+    let size = srs.h.serialized_size();
+    assert_eq!(bytes.len(), 16 + 17 * size + 64);
+
+    let read = SRS::<Vesta>::read_compressed(&bytes[..]).unwrap();
+    assert_eq!(read.g, srs.g);
+    assert_eq!(read.h, srs.h);
+
+    assert!(matches!(
+        SRS::<Vesta>::read_compressed(&bytes[1..]),
+        Err(SrsFileError::InvalidMagic)
+    ));
+    assert!(matches!(
+        SRS::<Vesta>::read_compressed(&bytes[..bytes.len() - 1]),
+        Err(SrsFileError::Corrupted)
+    ));
+
+    // a modified point is either not on the curve, or another point
+    let mut corrupted = bytes.clone();
+    corrupted[16 + 5 * size] ^= 1;
+    assert!(matches!(
+        SRS::<Vesta>::read_compressed(&corrupted[..]),
+        Err(SrsFileError::InvalidPoint | SrsFileError::Corrupted)
+    ));
+}
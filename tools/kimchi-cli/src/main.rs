@@ -0,0 +1,388 @@
+//! `kimchi` is a command-line tool to set up, prove and verify kimchi circuits,
+//! working only on files.
+//!
+//! - SRS are stored in their [compressed format](commitment_dlog::srs::SRS::write_compressed),
+//!   and the commitments to the Lagrange bases of an SRS are [cached](commitment_dlog::lagrange_cache) next to it,
+//! - prover indexes are stored with MessagePack,
+//! - verifier indexes and proofs are stored in the [container](kimchi::container) format,
+//! - circuits are read from [circuit descriptions](kimchi::circuits::description),
+//!   and witnesses from [canonical JSON](kimchi::json).
+
+use ark_ff::{PrimeField, SquareRootField};
+use commitment_dlog::{
+    commitment::CommitmentCurve, error::SrsFileError, lagrange_cache::LagrangeCache, srs::SRS,
+};
+use groupmap::GroupMap;
+use kimchi::{
+    circuits::{constraints::ConstraintSystem, description::CircuitDescription, wires::COLUMNS},
+    container::Container,
+    curve::KimchiCurve,
+    json::Json,
+    linearization::expr_linearization,
+    plonk_sponge::FrSponge,
+    proof::ProverProof,
+    prover_index::ProverIndex,
+    verifier::verify,
+    verifier_index::VerifierIndex,
+};
+use mina_curves::pasta::{Fp, Fq, Pallas, PallasParameters, Vesta, VestaParameters};
+use mina_poseidon::{
+    constants::PlonkSpongeConstantsKimchi,
+    sponge::{DefaultFqSponge, DefaultFrSponge},
+    FqSponge,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    env,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    process,
+    sync::Arc,
+};
+
+const USAGE: &str = "\
+usage: kimchi [--curve vesta|pallas] <COMMAND>
+
+commands:
+  srs generate <SIZE> <SRS_FILE>
+      generate an SRS able to commit to polynomials of SIZE coefficients
+  srs info <SRS_FILE>
+      load an SRS and print its size and its hash
+  srs resize <SIZE> <SRS_FILE> <OUT_SRS_FILE>
+      extend or trim a generated SRS to SIZE coefficients
+  compile <CIRCUIT_FILE> <SRS_FILE> <PROVER_INDEX_FILE> <VERIFIER_INDEX_FILE>
+      compile a circuit into a prover index and a verifier index
+  prove <PROVER_INDEX_FILE> <SRS_FILE> <WITNESS_FILE> <PROOF_FILE>
+      create a proof from a witness (the public inputs are the first rows of the first column)
+  verify <VERIFIER_INDEX_FILE> <PROOF_FILE> [<SRS_FILE>]
+      verify a proof (the SRS is regenerated if it is not given)
+  stats <CIRCUIT_FILE>
+      print statistics about a circuit
+  diff <OLD_CIRCUIT_FILE> <NEW_CIRCUIT_FILE>
+      print the structural differences between two circuits
+
+Circuits and witnesses are over the scalar field of the curve (Fp for vesta, Fq for pallas).
+Commitments to Lagrange bases are cached in the directory <SRS_FILE>.lagrange.";
+
+type Error = Box<dyn std::error::Error>;
+
+type SpongeParams = PlonkSpongeConstantsKimchi;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Err(e) = run(&args) {
+        eprintln!("error: {e}");
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), Error> {
+    let mut curve = "vesta";
+    let mut command = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--curve" => curve = args.next().ok_or("missing curve after --curve")?,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(());
+            }
+            _ => command.push(arg.as_str()),
+        }
+    }
+
+    match curve {
+        "vesta" => run_command::<
+            Vesta,
+            DefaultFqSponge<VestaParameters, SpongeParams>,
+            DefaultFrSponge<Fp, SpongeParams>,
+        >(&command),
+        "pallas" => run_command::<
+            Pallas,
+            DefaultFqSponge<PallasParameters, SpongeParams>,
+            DefaultFrSponge<Fq, SpongeParams>,
+        >(&command),
+        _ => Err(format!("unknown curve {curve} (expected vesta or pallas)").into()),
+    }
+}
+
+fn run_command<G, EFqSponge, EFrSponge>(command: &[&str]) -> Result<(), Error>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField + SquareRootField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    match command {
+        ["srs", "generate", size, srs_file] => srs_generate::<G>(size.parse()?, srs_file),
+        ["srs", "info", srs_file] => srs_info::<G>(srs_file),
+        ["srs", "resize", size, srs_file, out_srs_file] => {
+            srs_resize::<G>(size.parse()?, srs_file, out_srs_file)
+        }
+        ["compile", circuit_file, srs_file, prover_index_file, verifier_index_file] => {
+            compile::<G>(
+                circuit_file,
+                srs_file,
+                prover_index_file,
+                verifier_index_file,
+            )
+        }
+        ["prove", prover_index_file, srs_file, witness_file, proof_file] => {
+            prove::<G, EFqSponge, EFrSponge>(prover_index_file, srs_file, witness_file, proof_file)
+        }
+        ["verify", verifier_index_file, proof_file] => {
+            verify_proof::<G, EFqSponge, EFrSponge>(verifier_index_file, proof_file, None)
+        }
+        ["verify", verifier_index_file, proof_file, srs_file] => {
+            verify_proof::<G, EFqSponge, EFrSponge>(verifier_index_file, proof_file, Some(srs_file))
+        }
+        ["stats", circuit_file] => stats::<G>(circuit_file),
+        ["diff", old_circuit_file, new_circuit_file] => {
+            diff::<G>(old_circuit_file, new_circuit_file)
+        }
+        _ => Err(format!("invalid command\n\n{USAGE}").into()),
+    }
+}
+
+//
+// Files
+//
+
+fn open(path: &str) -> Result<BufReader<File>, Error> {
+    let file = File::open(path).map_err(|e| format!("could not open {path}: {e}"))?;
+    Ok(BufReader::new(file))
+}
+
+fn create(path: &str) -> Result<BufWriter<File>, Error> {
+    let file = File::create(path).map_err(|e| format!("could not create {path}: {e}"))?;
+    Ok(BufWriter::new(file))
+}
+
+fn read_msgpack<T: DeserializeOwned>(path: &str) -> Result<T, Error> {
+    rmp_serde::from_read(open(path)?).map_err(|e| format!("could not read {path}: {e}").into())
+}
+
+fn write_msgpack<T: Serialize>(path: &str, value: &T) -> Result<(), Error> {
+    let mut writer = create(path)?;
+    rmp_serde::encode::write(&mut writer, value)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads an SRS in the compressed format, or serialized with MessagePack
+fn read_srs<G: KimchiCurve>(path: &str) -> Result<SRS<G>, Error> {
+    match SRS::read_compressed(open(path)?) {
+        Err(SrsFileError::InvalidMagic) => read_msgpack(path),
+        res => res.map_err(|e| format!("could not read {path}: {e}").into()),
+    }
+}
+
+fn write_srs<G: KimchiCurve>(path: &str, srs: &SRS<G>) -> Result<(), Error> {
+    let mut writer = create(path)?;
+    srs.write_compressed(&mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn read_circuit<F: PrimeField>(path: &str) -> Result<CircuitDescription<F>, Error> {
+    let json = fs::read_to_string(path).map_err(|e| format!("could not read {path}: {e}"))?;
+    CircuitDescription::from_json(&json).map_err(|e| format!("could not read {path}: {e}").into())
+}
+
+fn build_constraint_system<F: PrimeField + SquareRootField>(
+    circuit: CircuitDescription<F>,
+) -> Result<ConstraintSystem<F>, Error> {
+    Ok(circuit.into_builder().build()?)
+}
+
+//
+// Commands
+//
+
+fn srs_generate<G: KimchiCurve>(size: usize, srs_file: &str) -> Result<(), Error>
+where
+    G::BaseField: PrimeField,
+{
+    let srs = SRS::<G>::create(size);
+    write_srs(srs_file, &srs)?;
+    println!("wrote an SRS of size {size} to {srs_file}");
+    Ok(())
+}
+
+fn srs_info<G: KimchiCurve>(srs_file: &str) -> Result<(), Error> {
+    let srs: SRS<G> = read_srs(srs_file)?;
+    println!("SRS of size {}", srs.g.len());
+    let digest: String = srs.digest().iter().map(|b| format!("{b:02x}")).collect();
+    println!("hash: {digest}");
+    Ok(())
+}
+
+fn srs_resize<G: KimchiCurve>(size: usize, srs_file: &str, out_srs_file: &str) -> Result<(), Error>
+where
+    G::BaseField: PrimeField,
+{
+    let mut srs: SRS<G> = read_srs(srs_file)?;
+    let old_size = srs.g.len();
+    srs.extend(size);
+    srs.trim(size);
+    write_srs(out_srs_file, &srs)?;
+    println!("resized the SRS of size {old_size} to {size} in {out_srs_file}");
+    Ok(())
+}
+
+fn compile<G: KimchiCurve>(
+    circuit_file: &str,
+    srs_file: &str,
+    prover_index_file: &str,
+    verifier_index_file: &str,
+) -> Result<(), Error>
+where
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let cs = build_constraint_system(read_circuit::<G::ScalarField>(circuit_file)?)?;
+
+    let mut srs: SRS<G> = read_srs(srs_file)?;
+    if cs.public > 0 && srs.g.len() < cs.domain.d1.size() {
+        return Err(format!(
+            "the SRS is too small: the circuit has public inputs and needs an SRS of size {}",
+            cs.domain.d1.size()
+        )
+        .into());
+    }
+    srs.add_lagrange_basis_cached(cs.domain.d1, &LagrangeCache::alongside(srs_file))?;
+
+    let endo_q = G::OtherCurve::endos().0;
+    let index = ProverIndex::<G>::create(cs, endo_q, Arc::new(srs));
+
+    write_msgpack(prover_index_file, &index)?;
+
+    let mut writer = create(verifier_index_file)?;
+    index.verifier_index().write_container(&mut writer)?;
+    writer.flush()?;
+
+    println!(
+        "compiled a circuit of {} gates over a domain of size {}",
+        index.cs.gates.len(),
+        index.cs.domain.d1.size()
+    );
+    Ok(())
+}
+
+fn prove<G, EFqSponge, EFrSponge>(
+    prover_index_file: &str,
+    srs_file: &str,
+    witness_file: &str,
+    proof_file: &str,
+) -> Result<(), Error>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    G::ScalarField: PrimeField + SquareRootField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    // restore the prover index
+    let mut index: ProverIndex<G> = read_msgpack(prover_index_file)?;
+    let mut srs: SRS<G> = read_srs(srs_file)?;
+    if srs.g.len() != index.max_poly_size {
+        return Err(format!(
+            "the prover index was compiled with an SRS of size {}, not {}",
+            index.max_poly_size,
+            srs.g.len()
+        )
+        .into());
+    }
+    srs.add_lagrange_basis_cached(index.cs.domain.d1, &LagrangeCache::alongside(srs_file))?;
+    index.srs = Arc::new(srs);
+    let (linearization, powers_of_alpha) = expr_linearization(Some(&index.cs.feature_flags), true);
+    index.linearization = linearization;
+    index.powers_of_alpha = powers_of_alpha;
+
+    // read the witness
+    let witness_json = fs::read_to_string(witness_file)
+        .map_err(|e| format!("could not read {witness_file}: {e}"))?;
+    let witness = <[Vec<G::ScalarField>; COLUMNS]>::from_json(&witness_json)
+        .map_err(|e| format!("could not read {witness_file}: {e}"))?;
+    let rows = witness[0].len();
+    if witness.iter().any(|column| column.len() != rows) {
+        return Err("the columns of the witness do not all have the same length".into());
+    }
+    if rows < index.cs.public || rows > index.cs.domain.d1.size() {
+        return Err(format!(
+            "the witness has {rows} rows, expected between {} and {}",
+            index.cs.public,
+            index.cs.domain.d1.size()
+        )
+        .into());
+    }
+    let public = witness[0][..index.cs.public].to_vec();
+
+    index
+        .cs
+        .verify(&witness, &public)
+        .map_err(|e| format!("the witness does not satisfy the circuit: {e:?}"))?;
+
+    // create the proof
+    let group_map = <G as CommitmentCurve>::Map::setup();
+    let proof = ProverProof::create::<EFqSponge, EFrSponge>(&group_map, witness, &[], &index)?;
+
+    let mut writer = create(proof_file)?;
+    proof.write_container(&mut writer)?;
+    writer.flush()?;
+
+    println!("wrote the proof to {proof_file}");
+    Ok(())
+}
+
+fn verify_proof<G, EFqSponge, EFrSponge>(
+    verifier_index_file: &str,
+    proof_file: &str,
+    srs_file: Option<&str>,
+) -> Result<(), Error>
+where
+    G: KimchiCurve,
+    G::BaseField: PrimeField,
+    EFqSponge: Clone + FqSponge<G::BaseField, G, G::ScalarField>,
+    EFrSponge: FrSponge<G::ScalarField>,
+{
+    let verifier_index = VerifierIndex::<G>::read_container(&mut open(verifier_index_file)?)?;
+    if let Some(srs_file) = srs_file {
+        let mut srs: SRS<G> = read_srs(srs_file)?;
+        srs.add_lagrange_basis_cached(verifier_index.domain, &LagrangeCache::alongside(srs_file))?;
+        verifier_index
+            .srs
+            .set(Arc::new(srs))
+            .map_err(|_| "the SRS of the verifier index has already been set")?;
+    }
+
+    let proof = ProverProof::<G>::read_container(&mut open(proof_file)?)?;
+
+    let group_map = <G as CommitmentCurve>::Map::setup();
+    verify::<G, EFqSponge, EFrSponge>(&group_map, &verifier_index, &proof)
+        .map_err(|e| format!("the proof is invalid: {e}"))?;
+
+    println!("the proof is valid");
+    Ok(())
+}
+
+fn stats<G: KimchiCurve>(circuit_file: &str) -> Result<(), Error>
+where
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let circuit = read_circuit::<G::ScalarField>(circuit_file)?;
+    let cs = build_constraint_system(circuit)?;
+    println!("{}", cs.stats());
+    Ok(())
+}
+
+fn diff<G: KimchiCurve>(old_circuit_file: &str, new_circuit_file: &str) -> Result<(), Error>
+where
+    G::ScalarField: PrimeField + SquareRootField,
+{
+    let old = build_constraint_system(read_circuit::<G::ScalarField>(old_circuit_file)?)?;
+    let new = build_constraint_system(read_circuit::<G::ScalarField>(new_circuit_file)?)?;
+    print!("{}", old.diff(&new));
+    Ok(())
+}
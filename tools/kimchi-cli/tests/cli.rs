@@ -0,0 +1,180 @@
+//! Smoke tests of the `kimchi` commands, on a tiny circuit and SRS.
+
+use ark_ff::Zero;
+use kimchi::{
+    circuits::{
+        description::CircuitDescription,
+        polynomials::generic::testing::{create_circuit, fill_in_witness},
+        wires::COLUMNS,
+    },
+    json::Json,
+};
+use mina_curves::pasta::Fp;
+use std::{
+    array, fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// The size of the SRS of the tests, large enough for the domain of the test circuit
+const SRS_SIZE: usize = 32;
+
+/// A directory for the files of a test, removed when dropped
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("kimchi-cli-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TestDir(dir)
+    }
+
+    fn path(&self, file: &str) -> String {
+        self.0.join(file).to_str().unwrap().to_string()
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Runs `kimchi` with `args`
+fn kimchi(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_kimchi"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs `kimchi` with `args`, checks that it succeeds, and returns its standard output
+fn kimchi_ok(args: &[&str]) -> String {
+    let output = kimchi(args);
+    assert!(
+        output.status.success(),
+        "kimchi {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+/// Runs `kimchi` with `args`, checks that it fails, and returns its standard error
+fn kimchi_err(args: &[&str]) -> String {
+    let output = kimchi(args);
+    assert!(!output.status.success(), "kimchi {args:?} should fail");
+    String::from_utf8(output.stderr).unwrap()
+}
+
+/// Writes the description of the test circuit with `public` public inputs to `path`
+fn write_circuit(path: &str, public: usize) {
+    let circuit = CircuitDescription::<Fp>::new(create_circuit(0, public), public);
+    fs::write(path, circuit.to_json().unwrap()).unwrap();
+}
+
+/// Writes a witness of the test circuit with a single public input to `path`
+fn write_witness(path: &str) {
+    let rows = create_circuit::<Fp>(0, 1).len();
+    let mut witness: [Vec<Fp>; COLUMNS] = array::from_fn(|_| vec![Fp::zero(); rows]);
+    fill_in_witness(0, &mut witness, &[Fp::from(3u8)]);
+    fs::write(path, witness.to_json().unwrap()).unwrap();
+}
+
+#[test]
+fn test_arguments() {
+    assert!(kimchi_ok(&["--help"]).contains("usage: kimchi"));
+    assert!(kimchi_err(&[]).contains("invalid command"));
+    assert!(kimchi_err(&["srs", "generate"]).contains("invalid command"));
+    assert!(kimchi_err(&["--curve"]).contains("missing curve"));
+    assert!(kimchi_err(&["--curve", "bn254", "stats", "circuit.json"]).contains("unknown curve"));
+    assert!(kimchi_err(&["srs", "generate", "many", "srs.bin"]).starts_with("error:"));
+    assert!(kimchi_err(&["stats", "missing-circuit.json"]).contains("could not read"));
+}
+
+#[test]
+fn test_srs() {
+    let dir = TestDir::new("srs");
+    let srs = dir.path("srs.bin");
+    let resized = dir.path("resized.bin");
+
+    for curve in ["vesta", "pallas"] {
+        let size = SRS_SIZE.to_string();
+        kimchi_ok(&["--curve", curve, "srs", "generate", &size, &srs]);
+        assert!(kimchi_ok(&["--curve", curve, "srs", "info", &srs])
+            .contains(&format!("SRS of size {SRS_SIZE}")));
+
+        let size = (2 * SRS_SIZE).to_string();
+        kimchi_ok(&["--curve", curve, "srs", "resize", &size, &srs, &resized]);
+        assert!(kimchi_ok(&["--curve", curve, "srs", "info", &resized])
+            .contains(&format!("SRS of size {}", 2 * SRS_SIZE)));
+    }
+
+    assert!(kimchi_err(&["srs", "info", &dir.path("missing.bin")]).contains("could not open"));
+}
+
+#[test]
+fn test_compile_prove_verify() {
+    let dir = TestDir::new("prove");
+    let [srs, circuit, witness, prover_index, verifier_index, proof] = [
+        "srs.bin",
+        "circuit.json",
+        "witness.json",
+        "prover_index.bin",
+        "verifier_index.kimc",
+        "proof.kimc",
+    ]
+    .map(|file| dir.path(file));
+
+    kimchi_ok(&["srs", "generate", &SRS_SIZE.to_string(), &srs]);
+    write_circuit(&circuit, 1);
+    write_witness(&witness);
+
+    assert!(
+        kimchi_ok(&["compile", &circuit, &srs, &prover_index, &verifier_index])
+            .contains("compiled a circuit")
+    );
+    assert!(Path::new(&format!("{srs}.lagrange")).is_dir());
+
+    kimchi_ok(&["prove", &prover_index, &srs, &witness, &proof]);
+
+    // with the SRS, or with a regenerated one
+    assert!(kimchi_ok(&["verify", &verifier_index, &proof, &srs]).contains("the proof is valid"));
+    assert!(kimchi_ok(&["verify", &verifier_index, &proof]).contains("the proof is valid"));
+
+    // a proof is not a verifier index
+    assert!(kimchi_err(&["verify", &proof, &proof]).starts_with("error:"));
+
+    // a witness that does not satisfy the circuit
+    let mut columns =
+        <[Vec<Fp>; COLUMNS]>::from_json(&fs::read_to_string(&witness).unwrap()).unwrap();
+    columns[2][1] += Fp::from(1u8);
+    fs::write(&witness, columns.to_json().unwrap()).unwrap();
+    assert!(
+        kimchi_err(&["prove", &prover_index, &srs, &witness, &proof])
+            .contains("does not satisfy the circuit")
+    );
+}
+
+#[test]
+fn test_stats() {
+    let dir = TestDir::new("stats");
+    let circuit = dir.path("circuit.json");
+    write_circuit(&circuit, 1);
+
+    let stats = kimchi_ok(&["stats", &circuit]);
+    assert!(stats.contains("public inputs"));
+    assert!(stats.contains("Generic"));
+}
+
+#[test]
+fn test_diff() {
+    let dir = TestDir::new("diff");
+    let old = dir.path("old.json");
+    let new = dir.path("new.json");
+    write_circuit(&old, 1);
+    write_circuit(&new, 2);
+
+    assert!(kimchi_ok(&["diff", &old, &old]).contains("no differences"));
+    assert!(kimchi_ok(&["diff", &old, &new]).contains("public inputs: 1 -> 2"));
+}
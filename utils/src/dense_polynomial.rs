@@ -67,6 +67,49 @@ impl<F: Field> ExtendedDensePolynomial<F> for DensePolynomial<F> {
     }
 }
 
+//
+// Vanishing polynomials and interpolation
+//
+
+/// Returns the coefficients of the vanishing polynomial of `points`, that is `prod_i (X - points_i)`.
+fn vanishing_coeffs<F: Field>(points: &[F]) -> Vec<F> {
+    let mut coeffs = vec![F::one()];
+    for point in points {
+        // multiply by (X - point)
+        coeffs.insert(0, F::zero());
+        for i in 0..coeffs.len() - 1 {
+            let next = coeffs[i + 1];
+            coeffs[i] -= *point * next;
+        }
+    }
+    coeffs
+}
+
+/// Returns the vanishing polynomial of `points`.
+pub fn vanishing_polynomial<F: Field>(points: &[F]) -> DensePolynomial<F> {
+    DensePolynomial::from_coefficients_vec(vanishing_coeffs(points))
+}
+
+/// Returns the polynomial of degree less than the number of points taking the given `values` at `points`,
+/// or `None` if two points are equal.
+pub fn interpolate<F: Field>(points: &[F], values: &[F]) -> Option<DensePolynomial<F>> {
+    let mut coeffs = vec![F::zero(); points.len()];
+    for (i, (x_i, y_i)) in points.iter().zip(values).enumerate() {
+        let others: Vec<_> = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, x_j)| *x_j)
+            .collect();
+        let denominator = others.iter().fold(F::one(), |acc, x_j| acc * (*x_i - x_j));
+        let scale = *y_i * denominator.inverse()?;
+        for (coeff, basis_coeff) in coeffs.iter_mut().zip(vanishing_coeffs(&others)) {
+            *coeff += scale * basis_coeff;
+        }
+    }
+    Some(DensePolynomial::from_coefficients_vec(coeffs))
+}
+
 //
 // Tests
 //
@@ -74,8 +117,8 @@ impl<F: Field> ExtendedDensePolynomial<F> for DensePolynomial<F> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ark_ff::One;
-    use ark_poly::{univariate::DensePolynomial, UVPolynomial};
+    use ark_ff::{One, Zero};
+    use ark_poly::{univariate::DensePolynomial, Polynomial, UVPolynomial};
     use mina_curves::pasta::Fp;
 
     #[test]
@@ -92,4 +135,21 @@ mod tests {
             assert!(eval == three);
         }
     }
+
+    #[test]
+    fn test_interpolate() {
+        let points: Vec<_> = [1u8, 2, 5].iter().map(|x| Fp::from(*x)).collect();
+        let vanishing = vanishing_polynomial(&points);
+        assert_eq!(vanishing.degree(), points.len());
+        assert!(points.iter().all(|x| vanishing.evaluate(x).is_zero()));
+
+        // 3 + 2x + x^2
+        let f =
+            DensePolynomial::from_coefficients_vec(vec![Fp::from(3u8), Fp::from(2u8), Fp::one()]);
+        let values: Vec<_> = points.iter().map(|x| f.evaluate(x)).collect();
+        assert_eq!(interpolate(&points, &values), Some(f));
+
+        // the points must be distinct
+        assert_eq!(interpolate(&[Fp::one(), Fp::one()], &values[..2]), None);
+    }
 }